@@ -32,22 +32,28 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             extensions: vec![
                 "GL_APPLE_vertex_array_object".to_string(),
                 "GL_ARB_bindless_texture".to_string(),
+                "GL_ARB_blend_func_extended".to_string(),
                 "GL_ARB_buffer_storage".to_string(),
                 "GL_ARB_compute_shader".to_string(),
                 "GL_ARB_copy_buffer".to_string(),
                 "GL_ARB_debug_output".to_string(),
+                "GL_ARB_depth_clamp".to_string(),
                 "GL_ARB_depth_texture".to_string(),
                 "GL_ARB_direct_state_access".to_string(),
+                "GL_ARB_draw_buffers_blend".to_string(),
                 "GL_ARB_ES2_compatibility".to_string(),
                 "GL_ARB_ES3_compatibility".to_string(),
                 "GL_ARB_ES3_1_compatibility".to_string(),
+                "GL_ARB_framebuffer_no_attachments".to_string(),
                 "GL_ARB_framebuffer_sRGB".to_string(),
                 "GL_ARB_geometry_shader4".to_string(),
                 "GL_ARB_invalidate_subdata".to_string(),
                 "GL_ARB_multi_draw_indirect".to_string(),
                 "GL_ARB_occlusion_query".to_string(),
                 "GL_ARB_pixel_buffer_object".to_string(),
+                "GL_ARB_provoking_vertex".to_string(),
                 "GL_ARB_robustness".to_string(),
+                "GL_ARB_sample_shading".to_string(),
                 "GL_ARB_shader_image_load_store".to_string(),
                 "GL_ARB_shader_objects".to_string(),
                 "GL_ARB_texture_float".to_string(),
@@ -57,10 +63,13 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_ARB_transform_feedback3".to_string(),
                 "GL_ARB_vertex_buffer_object".to_string(),
                 "GL_ARB_vertex_shader".to_string(),
+                "GL_ARB_viewport_array".to_string(),
                 "GL_ATI_meminfo".to_string(),
                 "GL_EXT_debug_marker".to_string(),
                 "GL_EXT_direct_state_access".to_string(),
+                "GL_EXT_draw_buffers2".to_string(),
                 "GL_EXT_framebuffer_blit".to_string(),
+                "GL_EXT_framebuffer_multisample".to_string(),
                 "GL_EXT_framebuffer_object".to_string(),
                 "GL_EXT_framebuffer_sRGB".to_string(),
                 "GL_EXT_gpu_shader4".to_string(),
@@ -94,6 +103,7 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
                 "GL_APPLE_sync".to_string(),
                 "GL_ARM_rgba8".to_string(),
                 "GL_EXT_buffer_storage".to_string(),
+                "GL_EXT_discard_framebuffer".to_string(),
                 "GL_EXT_disjoint_timer_query".to_string(),
                 "GL_EXT_multi_draw_indirect".to_string(),
                 "GL_EXT_occlusion_query_boolean".to_string(),
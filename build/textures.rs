@@ -22,6 +22,8 @@ enum TextureDimensions {
     Texture1dArray,
     Texture2dArray,
     Texture2dMultisampleArray,
+    Cubemap,
+    CubemapArray,
 }
 
 impl TextureDimensions {
@@ -40,6 +42,14 @@ impl TextureDimensions {
             _ => false
         }
     }
+
+    fn is_cubemap(&self) -> bool {
+        match self {
+            &TextureDimensions::Cubemap => true,
+            &TextureDimensions::CubemapArray => true,
+            _ => false
+        }
+    }
 }
 
 pub fn build_texture_file<W: Write>(mut dest: &mut W) {
@@ -102,6 +112,20 @@ pub fn build_texture_file<W: Write>(mut dest: &mut W) {
     build_texture(dest, TextureType::Depth, TextureDimensions::Texture2dMultisampleArray);
     build_texture(dest, TextureType::Stencil, TextureDimensions::Texture2dMultisampleArray);
     build_texture(dest, TextureType::DepthStencil, TextureDimensions::Texture2dMultisampleArray);
+    build_texture(dest, TextureType::Regular, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Srgb, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Integral, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Unsigned, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Depth, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Stencil, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::DepthStencil, TextureDimensions::Cubemap);
+    build_texture(dest, TextureType::Regular, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Srgb, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Integral, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Unsigned, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Depth, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::Stencil, TextureDimensions::CubemapArray);
+    build_texture(dest, TextureType::DepthStencil, TextureDimensions::CubemapArray);
 }
 
 fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: TextureDimensions) {
@@ -127,6 +151,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             TextureDimensions::Texture1dArray => "Texture1dArray",
             TextureDimensions::Texture2dArray => "Texture2dArray",
             TextureDimensions::Texture2dMultisampleArray => "Texture2dMultisampleArray",
+            TextureDimensions::Cubemap => "Cubemap",
+            TextureDimensions::CubemapArray => "CubemapArray",
         };
 
         format!("{}{}", prefix, suffix)
@@ -137,7 +163,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture1d | TextureDimensions::Texture1dArray => "Texture1dDataSource",
         TextureDimensions::Texture2d | TextureDimensions::Texture2dArray => "Texture2dDataSource",
         TextureDimensions::Texture3d => "Texture3dDataSource",
-        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture2dMultisampleArray => {
+        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture2dMultisampleArray |
+        TextureDimensions::Cubemap | TextureDimensions::CubemapArray => {
             "unreachable"
         },
     };
@@ -200,6 +227,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture1dArray => "width: u32, array_size: u32",
         TextureDimensions::Texture2dArray => "width: u32, height: u32, array_size: u32",
         TextureDimensions::Texture2dMultisampleArray => "width: u32, height: u32, array_size: u32, samples: u32",
+        TextureDimensions::Cubemap => "dimension: u32",
+        TextureDimensions::CubemapArray => "dimension: u32, array_size: u32",
     };
 
     let dimensions_parameters_passing = match dimensions {
@@ -224,15 +253,24 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         TextureDimensions::Texture2dMultisampleArray => {
             "Dimensions::Texture2dMultisampleArray { width: width, height: height, array_size: array_size, samples: samples }"
         },
+        TextureDimensions::Cubemap => {
+            "Dimensions::Cubemap { dimension: dimension }"
+        },
+        TextureDimensions::CubemapArray => {
+            "Dimensions::CubemapArray { dimension: dimension, array_size: array_size }"
+        },
     };
 
     // writing the struct with doc-comment
     (write!(dest, "/// ")).unwrap();
     (write!(dest, "{}", match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d => "A ",
+        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d |
+        TextureDimensions::Cubemap => "A ",
         TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
-        TextureDimensions::Texture2dMultisampleArray => "An array of ",
+        TextureDimensions::Texture2dMultisampleArray | TextureDimensions::CubemapArray => {
+            "An array of "
+        },
     })).unwrap();
     if is_compressed {
         (write!(dest, "compressed ")).unwrap();
@@ -244,12 +282,16 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
             "two-dimensional "
         },
         TextureDimensions::Texture3d => "three-dimensional ",
+        TextureDimensions::Cubemap | TextureDimensions::CubemapArray => "cube ",
     })).unwrap();
     (write!(dest, "{}", match dimensions {
         TextureDimensions::Texture1d | TextureDimensions::Texture2d |
-        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d => "texture ",
+        TextureDimensions::Texture2dMultisample | TextureDimensions::Texture3d |
+        TextureDimensions::Cubemap => "texture ",
         TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
-        TextureDimensions::Texture2dMultisampleArray => "textures ",
+        TextureDimensions::Texture2dMultisampleArray | TextureDimensions::CubemapArray => {
+            "textures "
+        },
     })).unwrap();
     (write!(dest, "{}", match ty {
         TextureType::Regular | TextureType::Compressed => " containing floating-point data",
@@ -361,55 +403,19 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // `ToXXXAttachment` trait impl
-    if dimensions == TextureDimensions::Texture2d || dimensions == TextureDimensions::Texture2dMultisample {
-        match ty {
-            TextureType::Regular => {
-                (writeln!(dest, "
-                        impl ::framebuffer::ToColorAttachment for {name} {{
-                            fn to_color_attachment(&self) -> ::framebuffer::ColorAttachment {{
-                                ::framebuffer::ColorAttachment::Texture(self.0.mipmap(0, 0).unwrap())
-                            }}
-                        }}
-                    ", name = name)).unwrap();
-            },
-            TextureType::Srgb => {
-                (writeln!(dest, "
-                        impl ::framebuffer::ToColorAttachment for {name} {{
-                            fn to_color_attachment(&self) -> ::framebuffer::ColorAttachment {{
-                                ::framebuffer::ColorAttachment::Texture(self.0.mipmap(0, 0).unwrap())
-                            }}
-                        }}
-                    ", name = name)).unwrap();
-            },
-            TextureType::Depth => {
-                (writeln!(dest, "
-                        impl ::framebuffer::ToDepthAttachment for {name} {{
-                            fn to_depth_attachment(&self) -> ::framebuffer::DepthAttachment {{
-                                ::framebuffer::DepthAttachment::Texture(self.0.mipmap(0, 0).unwrap())
-                            }}
-                        }}
-                    ", name = name)).unwrap();
-            },
-            TextureType::Stencil => {
-                (writeln!(dest, "
-                        impl ::framebuffer::ToStencilAttachment for {name} {{
-                            fn to_stencil_attachment(&self) -> ::framebuffer::StencilAttachment {{
-                                ::framebuffer::StencilAttachment::Texture(self.0.mipmap(0, 0).unwrap())
-                            }}
-                        }}
-                    ", name = name)).unwrap();
-            },
-            TextureType::DepthStencil => {
-                (writeln!(dest, "
-                        impl ::framebuffer::ToDepthStencilAttachment for {name} {{
-                            fn to_depth_stencil_attachment(&self) -> ::framebuffer::DepthStencilAttachment {{
-                                ::framebuffer::DepthStencilAttachment::Texture(self.0.mipmap(0, 0).unwrap())
-                            }}
-                        }}
-                    ", name = name)).unwrap();
-            },
-            _ => ()
-        }
+    // textures with layers are attached as a whole, so that the geometry shader can select
+    // the layer to render to
+    match dimensions {
+        TextureDimensions::Texture2d | TextureDimensions::Texture2dMultisample => {
+            write_attachment_impl(dest, &ty, "", &name, "Texture", "self.0.mipmap(0, 0).unwrap()");
+        },
+        TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+        TextureDimensions::Texture2dMultisampleArray | TextureDimensions::Texture3d |
+        TextureDimensions::Cubemap | TextureDimensions::CubemapArray => {
+            write_attachment_impl(dest, &ty, "", &name, "LayeredTexture",
+                                  "self.0.mipmap(0, 0).unwrap()");
+        },
+        _ => ()
     }
 
     // opening `impl Texture` block
@@ -427,7 +433,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
         ").unwrap();
 
     // writing the `new` function
-    if !dimensions.is_multisample() {
+    // multisample textures and cubemaps can only be created empty
+    if !dimensions.is_multisample() && !dimensions.is_cubemap() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -459,7 +466,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // writing the `with_mipmaps` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cubemap() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -483,7 +490,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // writing the `with_compressed_data` function
-    if is_compressed && !dimensions.is_multisample() {
+    if is_compressed && !dimensions.is_multisample() && !dimensions.is_cubemap() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "&[u8]",
@@ -514,7 +521,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // writing the `with_format` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cubemap() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -540,7 +547,7 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
     }
 
     // writing the `new_impl` function
-    if !dimensions.is_multisample() {
+    if !dimensions.is_multisample() && !dimensions.is_cubemap() {
         let param = match dimensions {
             TextureDimensions::Texture1d | TextureDimensions::Texture2d |
             TextureDimensions::Texture3d => "T",
@@ -690,7 +697,8 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
                 /// FBO and re-use it. When the texture is destroyed, the FBO is destroyed too.
                 ///
                 pub fn as_surface<'a>(&'a self) -> framebuffer::SimpleFrameBuffer<'a> {{
-                    framebuffer::SimpleFrameBuffer::new(self.0.get_context(), self)
+                    // a single non-layered attachment is always valid
+                    framebuffer::SimpleFrameBuffer::new(self.0.get_context(), self).unwrap()
                 }}
             ")).unwrap();
     }
@@ -1079,5 +1087,53 @@ fn build_texture<W: Write>(mut dest: &mut W, ty: TextureType, dimensions: Textur
 
         // closing `impl Mipmap` block
         (writeln!(dest, "}}")).unwrap();
+
+        // 'Deref' impl to `TextureAnyMipmap`
+        (write!(dest, "
+                impl<'t> ::std::ops::Deref for {name}Mipmap<'t> {{
+                    type Target = TextureAnyMipmap<'t>;
+
+                    fn deref(&self) -> &TextureAnyMipmap<'t> {{
+                        &self.0
+                    }}
+                }}
+            ", name = name)).unwrap();
+
+        // `ToXXXAttachment` trait impl for a single mipmap level of a single layer
+        match dimensions {
+            TextureDimensions::Texture2d | TextureDimensions::Texture2dMultisample |
+            TextureDimensions::Texture1dArray | TextureDimensions::Texture2dArray |
+            TextureDimensions::Texture2dMultisampleArray => {
+                write_attachment_impl(dest, &ty, "<'t>", &format!("{}Mipmap<'t>", name),
+                                      "Texture", "self.0");
+            },
+            _ => ()
+        }
     }
 }
+
+/// Writes the implementation of `ToColorAttachment`, `ToDepthAttachment`, etc. that corresponds
+/// to the type of texture.
+fn write_attachment_impl<W: Write>(dest: &mut W, ty: &TextureType, generics: &str, name: &str,
+                                   variant: &str, attachment: &str)
+{
+    let (trait_name, method, attachment_ty) = match *ty {
+        TextureType::Regular | TextureType::Srgb | TextureType::Integral |
+        TextureType::Unsigned => ("ToColorAttachment", "to_color_attachment", "ColorAttachment"),
+        TextureType::Depth => ("ToDepthAttachment", "to_depth_attachment", "DepthAttachment"),
+        TextureType::Stencil => ("ToStencilAttachment", "to_stencil_attachment",
+                                 "StencilAttachment"),
+        TextureType::DepthStencil => ("ToDepthStencilAttachment", "to_depth_stencil_attachment",
+                                      "DepthStencilAttachment"),
+        _ => return
+    };
+
+    (writeln!(dest, "
+            impl{generics} ::framebuffer::{trait_name} for {name} {{
+                fn {method}(&self) -> ::framebuffer::{attachment_ty} {{
+                    ::framebuffer::{attachment_ty}::{variant}({attachment})
+                }}
+            }}
+        ", generics = generics, trait_name = trait_name, name = name, method = method,
+           attachment_ty = attachment_ty, variant = variant, attachment = attachment)).unwrap();
+}
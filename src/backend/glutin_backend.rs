@@ -10,6 +10,7 @@ Only available if the 'glutin' feature is enabled.
 */
 extern crate glutin;
 
+use gl;
 use libc;
 
 use DisplayBuild;
@@ -198,7 +199,10 @@ impl DisplayBuild for glutin::HeadlessRendererBuilder {
     }
 
     fn rebuild_glium(self, _: &GlutinFacade) -> Result<(), GliumCreationError<glutin::CreationError>> {
-        unimplemented!()
+        // glutin's `HeadlessRendererBuilder` can't share its lists with an existing context,
+        // so the textures and buffers of the display would be lost
+        // TODO: implement this once glutin supports `with_shared_lists` for headless contexts
+        Err(GliumCreationError::BackendCreationError(glutin::CreationError::NotSupported))
     }
 }
 
@@ -274,6 +278,7 @@ impl GlutinWindowBackend {
 /// An implementation of the `Backend` trait for a glutin headless context.
 pub struct GlutinHeadlessBackend {
     context: glutin::HeadlessContext,
+    dimensions: (u32, u32),
 }
 
 unsafe impl Backend for GlutinHeadlessBackend {
@@ -286,7 +291,7 @@ unsafe impl Backend for GlutinHeadlessBackend {
     }
 
     fn get_framebuffer_dimensions(&self) -> (u32, u32) {
-        (800, 600)      // FIXME: these are random
+        self.dimensions
     }
 
     fn is_current(&self) -> bool {
@@ -305,8 +310,19 @@ impl GlutinHeadlessBackend {
     {
         let context = try!(builder.build());
 
+        // glutin doesn't give access to the dimensions passed to the builder, but the initial
+        // viewport of a context covers its whole default framebuffer
+        let dimensions = unsafe {
+            context.make_current().unwrap();
+            let gl = gl::Gl::load_with(|symbol| context.get_proc_address(symbol));
+            let mut viewport = [0; 4];
+            gl.GetIntegerv(gl::VIEWPORT, viewport.as_mut_ptr());
+            (viewport[2] as u32, viewport[3] as u32)
+        };
+
         Ok(GlutinHeadlessBackend {
             context: context,
+            dimensions: dimensions,
         })
     }
 }
@@ -27,6 +27,7 @@ pub use context::Context;
 
 #[cfg(feature = "glutin")]
 pub mod glutin_backend;
+pub mod recording;
 
 /// Trait for types that can be used as a backend for a glium context.
 ///
@@ -0,0 +1,1018 @@
+/*!
+
+Backend that doesn't need any OpenGL implementation.
+
+The `RecordingBackend` provides its own implementation of the OpenGL functions used by glium.
+These functions don't draw anything. Instead they emulate the creation of objects, keep track
+of the objects that are bound and of the capabilities that are enabled, and record every call
+into a log that can later be inspected.
+
+This allows you to run glium on machines that have no GPU and no display, for example in order
+to check which commands are emitted by a draw call.
+
+```no_run
+# use std::rc::Rc;
+use glium::backend::Context;
+use glium::backend::recording::RecordingBackend;
+
+let backend = Rc::new(RecordingBackend::new(glium::Version(glium::Api::Gl, 3, 3), &[]));
+let context = unsafe { Context::new::<_, ()>(backend.clone(), false) }.unwrap();
+
+// ... use `context` as a facade ...
+
+for call in backend.get_calls() {
+    println!("{}({:?})", call.name, call.args);
+}
+```
+
+Only the functions that glium is likely to call are emulated. Calling a function that is not
+emulated panics with a "function not loaded" message, so make sure that the version and the
+extensions that you advertise match the features that you use.
+
+*/
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::ffi::CString;
+use std::ptr;
+use std::rc::Rc;
+use std::slice;
+
+use gl;
+use gl::types::*;
+use libc;
+
+use SwapBuffersError;
+use backend::Backend;
+use version::Api;
+use version::Version;
+use version::get_supported_glsl_version;
+
+thread_local!(static CURRENT: RefCell<Option<Rc<RefCell<State>>>> = RefCell::new(None));
+
+/// A call to an OpenGL function that has been recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    /// Name of the function, for example `glBindBuffer`.
+    pub name: &'static str,
+
+    /// Values of the parameters that were passed to the function.
+    pub args: Vec<Value>,
+}
+
+/// Value of a parameter passed to an OpenGL function.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Value {
+    /// A signed integer, like a `GLint` or a `GLsizei`.
+    Int(i64),
+    /// An unsigned integer, like a `GLenum`, a `GLuint` or a `GLboolean`.
+    UInt(u64),
+    /// A floating-point number.
+    Float(f64),
+    /// The address of a pointer.
+    Pointer(usize),
+}
+
+/// Backend whose OpenGL functions emulate a context and record the calls.
+///
+/// Wrap it inside a `Rc` if you want to keep access to the backend after passing it to
+/// `Context::new`.
+pub struct RecordingBackend {
+    state: Rc<RefCell<State>>,
+}
+
+impl RecordingBackend {
+    /// Builds a new backend that advertises the given version and extensions.
+    ///
+    /// The extensions are the names of the extensions as they would be returned by the
+    /// implementation, for example `GL_ARB_debug_output`.
+    pub fn new(version: Version, extensions: &[&str]) -> RecordingBackend {
+        let version_string = match version.0 {
+            Api::Gl => format!("{}.{} glium recording backend", version.1, version.2),
+            Api::GlEs => format!("OpenGL ES {}.{} glium recording backend", version.1,
+                                 version.2),
+        };
+
+        let glsl_string = if version >= Version(Api::Gl, 2, 0) ||
+                             version >= Version(Api::GlEs, 2, 0)
+        {
+            let glsl = get_supported_glsl_version(&version);
+            format!("{}.{}", glsl.1, glsl.2 * 10)
+        } else {
+            String::new()
+        };
+
+        let mut integers = HashMap::new();
+        integers.insert(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS, vec![32]);
+        integers.insert(gl::MAX_TEXTURE_SIZE, vec![16384]);
+        integers.insert(gl::MAX_VIEWPORT_DIMS, vec![16384, 16384]);
+        integers.insert(gl::MAX_DRAW_BUFFERS, vec![8]);
+        integers.insert(gl::MAX_CLIP_DISTANCES, vec![8]);
+        integers.insert(gl::MAX_VIEWPORTS, vec![16]);
+        integers.insert(gl::MAX_COLOR_ATTACHMENTS, vec![8]);
+        integers.insert(gl::MAX_VERTEX_ATTRIBS, vec![16]);
+        integers.insert(gl::MAX_PATCH_VERTICES, vec![32]);
+        integers.insert(gl::MAX_SAMPLES, vec![8]);
+        integers.insert(gl::MAX_FRAMEBUFFER_WIDTH, vec![16384]);
+        integers.insert(gl::MAX_FRAMEBUFFER_HEIGHT, vec![16384]);
+        integers.insert(gl::MAX_FRAMEBUFFER_LAYERS, vec![2048]);
+        integers.insert(gl::MAX_FRAMEBUFFER_SAMPLES, vec![8]);
+        integers.insert(gl::MAX_ATOMIC_COUNTER_BUFFER_BINDINGS, vec![8]);
+        integers.insert(gl::MAX_SHADER_STORAGE_BUFFER_BINDINGS, vec![8]);
+        integers.insert(gl::MAX_TRANSFORM_FEEDBACK_BUFFERS, vec![4]);
+        integers.insert(gl::MAX_TRANSFORM_FEEDBACK_SEPARATE_ATTRIBS, vec![4]);
+        integers.insert(gl::MAX_UNIFORM_BUFFER_BINDINGS, vec![36]);
+        integers.insert(gl::MAX_COMPUTE_WORK_GROUP_COUNT, vec![65535]);
+        integers.insert(gl::RESET_NOTIFICATION_STRATEGY, vec![gl::NO_RESET_NOTIFICATION as GLint]);
+        integers.insert(gl::PACK_ALIGNMENT, vec![4]);
+        integers.insert(gl::UNPACK_ALIGNMENT, vec![4]);
+        integers.insert(gl::DEPTH_BITS, vec![24]);
+        integers.insert(gl::STENCIL_BITS, vec![8]);
+
+        let state = State {
+            version: CString::new(version_string).unwrap(),
+            vendor: CString::new("glium").unwrap(),
+            renderer: CString::new("recording backend").unwrap(),
+            glsl_version: CString::new(glsl_string).unwrap(),
+            extensions_string: CString::new(extensions.connect(" ")).unwrap(),
+            extensions: extensions.iter().map(|e| CString::new(*e).unwrap()).collect(),
+            dimensions: (800, 600),
+            next_name: 1,
+            active_texture: 0,
+            integers: integers,
+            enabled: HashSet::new(),
+            bindings: HashMap::new(),
+            buffers: HashMap::new(),
+            debug_callback: None,
+            calls: Vec::new(),
+        };
+
+        RecordingBackend {
+            state: Rc::new(RefCell::new(state)),
+        }
+    }
+
+    /// Changes the value returned by `get_framebuffer_dimensions`. The default is `(800, 600)`.
+    pub fn set_framebuffer_dimensions(&self, dimensions: (u32, u32)) {
+        self.state.borrow_mut().dimensions = dimensions;
+    }
+
+    /// Changes the value returned when calling `glGetIntegerv` with `pname`. Booleans such as
+    /// `GL_STEREO` are read from the same values.
+    ///
+    /// This should be called before building the `Context`, as the capabilities are only
+    /// queried once.
+    pub fn set_integer(&self, pname: u32, value: i32) {
+        self.state.borrow_mut().integers.insert(pname, vec![value]);
+    }
+
+    /// Returns the list of calls that have been recorded since the creation of the backend or
+    /// the last call to `clear_calls`.
+    pub fn get_calls(&self) -> Vec<Call> {
+        self.state.borrow().calls.clone()
+    }
+
+    /// Clears the list of recorded calls.
+    pub fn clear_calls(&self) {
+        self.state.borrow_mut().calls.clear();
+    }
+
+    /// Returns true if `cap` has been enabled with `glEnable`.
+    pub fn is_enabled(&self, cap: u32) -> bool {
+        self.state.borrow().enabled.contains(&cap)
+    }
+
+    /// Returns the name of the object currently bound to `target`.
+    ///
+    /// `index` is the texture unit for textures, the index for indexed buffer bind points
+    /// and for samplers, and must be `0` otherwise. Vertex array objects are bound to
+    /// `GL_VERTEX_ARRAY_BINDING` and programs to `GL_CURRENT_PROGRAM`.
+    pub fn get_binding(&self, target: u32, index: u32) -> u32 {
+        self.state.borrow().bindings.get(&(target, index)).map(|b| *b).unwrap_or(0)
+    }
+
+    /// Sends a message to the callback registered with `glDebugMessageCallback`, as if the
+    /// implementation had emitted it.
+    ///
+    /// Does nothing if no callback is registered or if `GL_DEBUG_OUTPUT` is disabled.
+    pub fn emit_debug_message(&self, source: u32, ty: u32, id: u32, severity: u32,
+                              message: &str)
+    {
+        let callback = {
+            let state = self.state.borrow();
+            if !state.enabled.contains(&gl::DEBUG_OUTPUT) {
+                return;
+            }
+            state.debug_callback
+        };
+
+        if let Some((callback, user_param)) = callback {
+            let message = CString::new(message).unwrap();
+            callback(source, ty, id, severity, message.as_bytes().len() as GLsizei,
+                     message.as_ptr(), user_param as *mut libc::c_void);
+        }
+    }
+}
+
+unsafe impl Backend for RecordingBackend {
+    fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+        Ok(())
+    }
+
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const libc::c_void {
+        get_stub_address(symbol)
+    }
+
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        self.state.borrow().dimensions
+    }
+
+    fn is_current(&self) -> bool {
+        CURRENT.with(|current| {
+            match *current.borrow() {
+                Some(ref state) => &**state as *const RefCell<State> ==
+                                   &*self.state as *const RefCell<State>,
+                None => false,
+            }
+        })
+    }
+
+    unsafe fn make_current(&self) {
+        CURRENT.with(|current| {
+            *current.borrow_mut() = Some(self.state.clone());
+        });
+    }
+}
+
+/// The emulated OpenGL context.
+struct State {
+    version: CString,
+    vendor: CString,
+    renderer: CString,
+    glsl_version: CString,
+    extensions_string: CString,
+    extensions: Vec<CString>,
+    dimensions: (u32, u32),
+
+    /// Name of the next object that will be created.
+    next_name: GLuint,
+
+    /// Index of the texture unit that was set with `glActiveTexture`.
+    active_texture: GLuint,
+
+    /// Values returned by `glGetIntegerv`.
+    integers: HashMap<GLenum, Vec<GLint>>,
+
+    /// Capabilities enabled with `glEnable`.
+    enabled: HashSet<GLenum>,
+
+    /// Objects bound to each `(target, index)`.
+    bindings: HashMap<(GLenum, GLuint), GLuint>,
+
+    /// Content of each buffer object.
+    buffers: HashMap<GLuint, Vec<u8>>,
+
+    /// Function and user parameter passed to `glDebugMessageCallback`.
+    debug_callback: Option<(GLDEBUGPROC, *const libc::c_void)>,
+
+    calls: Vec<Call>,
+}
+
+impl State {
+    fn new_name(&mut self) -> GLuint {
+        let name = self.next_name;
+        self.next_name += 1;
+        name
+    }
+
+    unsafe fn gen_names(&mut self, n: GLsizei, names: *mut GLuint) {
+        for i in 0 .. n as isize {
+            *names.offset(i) = self.new_name();
+        }
+    }
+
+    /// Unbinds objects that are being deleted.
+    unsafe fn delete_names(&mut self, n: GLsizei, names: *const GLuint) {
+        let names = slice::from_raw_parts(names, n as usize);
+
+        for name in names.iter() {
+            self.buffers.remove(name);
+        }
+
+        for (_, bound) in self.bindings.iter_mut() {
+            if names.contains(bound) {
+                *bound = 0;
+            }
+        }
+    }
+
+    fn bind(&mut self, target: GLenum, index: GLuint, name: GLuint) {
+        self.bindings.insert((target, index), name);
+    }
+
+    fn bound_buffer(&mut self, target: GLenum) -> Option<&mut Vec<u8>> {
+        let id = match self.bindings.get(&(target, 0)) {
+            Some(&id) if id != 0 => id,
+            _ => return None,
+        };
+
+        self.buffers.get_mut(&id)
+    }
+
+    fn get_integer(&self, pname: GLenum) -> GLint {
+        if pname == gl::NUM_EXTENSIONS {
+            return self.extensions.len() as GLint;
+        }
+
+        self.integers.get(&pname).map(|v| v[0]).unwrap_or(0)
+    }
+}
+
+/// Trait for the types of the parameters of the emulated functions.
+trait ToValue {
+    fn to_value(self) -> Value;
+}
+
+macro_rules! impl_to_value {
+    ($variant:ident, $conv:ty, $($t:ty),+) => (
+        $(
+            impl ToValue for $t {
+                fn to_value(self) -> Value {
+                    Value::$variant(self as $conv)
+                }
+            }
+        )+
+    )
+}
+
+impl_to_value!(Int, i64, i32, i64, isize);
+impl_to_value!(UInt, u64, u8, u32, u64);
+impl_to_value!(Float, f64, f32, f64);
+
+impl ToValue for GLDEBUGPROC {
+    fn to_value(self) -> Value {
+        Value::Pointer(self as usize)
+    }
+}
+
+impl<T> ToValue for *const T {
+    fn to_value(self) -> Value {
+        Value::Pointer(self as usize)
+    }
+}
+
+impl<T> ToValue for *mut T {
+    fn to_value(self) -> Value {
+        Value::Pointer(self as usize)
+    }
+}
+
+/// Returns the number of bytes written by `glReadPixels`.
+fn pixels_size(state: &State, width: GLsizei, height: GLsizei, format: GLenum,
+               ty: GLenum) -> usize
+{
+    if width <= 0 || height <= 0 {
+        return 0;
+    }
+
+    let components = match format {
+        gl::RED | gl::GREEN | gl::BLUE | gl::ALPHA | gl::RED_INTEGER | gl::DEPTH_COMPONENT |
+        gl::STENCIL_INDEX | gl::DEPTH_STENCIL => 1,
+        gl::RG | gl::RG_INTEGER => 2,
+        gl::RGB | gl::BGR | gl::RGB_INTEGER | gl::BGR_INTEGER => 3,
+        _ => 4,
+    };
+
+    let pixel = match ty {
+        gl::UNSIGNED_BYTE | gl::BYTE => components,
+        gl::UNSIGNED_SHORT | gl::SHORT | gl::HALF_FLOAT => 2 * components,
+        gl::UNSIGNED_BYTE_3_3_2 | gl::UNSIGNED_BYTE_2_3_3_REV => 1,
+        gl::UNSIGNED_SHORT_5_6_5 | gl::UNSIGNED_SHORT_5_6_5_REV | gl::UNSIGNED_SHORT_4_4_4_4 |
+        gl::UNSIGNED_SHORT_4_4_4_4_REV | gl::UNSIGNED_SHORT_5_5_5_1 |
+        gl::UNSIGNED_SHORT_1_5_5_5_REV => 2,
+        gl::UNSIGNED_INT_8_8_8_8 | gl::UNSIGNED_INT_8_8_8_8_REV | gl::UNSIGNED_INT_10_10_10_2 |
+        gl::UNSIGNED_INT_2_10_10_10_REV | gl::UNSIGNED_INT_24_8 |
+        gl::UNSIGNED_INT_10F_11F_11F_REV | gl::UNSIGNED_INT_5_9_9_9_REV => 4,
+        gl::FLOAT_32_UNSIGNED_INT_24_8_REV => 8,
+        _ => 4 * components,
+    };
+
+    let alignment = state.get_integer(gl::PACK_ALIGNMENT) as usize;
+    let row = width as usize * pixel;
+    let aligned_row = (row + alignment - 1) / alignment * alignment;
+
+    aligned_row * (height as usize - 1) + row
+}
+
+/// Defines the emulated functions and `get_stub_address`.
+///
+/// Each function starts with the name that the body uses to access the `State`, followed by
+/// its parameters.
+macro_rules! stubs {
+    ($($name:ident($st:ident $(, $arg:ident: $ty:ty)*) -> $ret:ty $body:block)+) => (
+        $(
+            #[allow(non_snake_case, unused_variables, unused_mut, unused_unsafe)]
+            extern "system" fn $name($($arg: $ty),*) -> $ret {
+                let current_state = CURRENT.with(|current| current.borrow().clone())
+                                           .expect("No recording backend is current");
+                let mut current_state = current_state.borrow_mut();
+                let $st: &mut State = &mut *current_state;
+
+                $st.calls.push(Call {
+                    name: concat!("gl", stringify!($name)),
+                    args: vec![$($arg.to_value()),*],
+                });
+
+                unsafe { $body }
+            }
+        )+
+
+        /// Returns the address of the emulated function named `symbol`, or null if it is not
+        /// emulated.
+        fn get_stub_address(symbol: &str) -> *const libc::c_void {
+            $(
+                if symbol == concat!("gl", stringify!($name)) {
+                    return $name as *const libc::c_void;
+                }
+            )+
+
+            ptr::null()
+        }
+    )
+}
+
+stubs! {
+    // queries
+    GetError(s) -> GLenum {
+        gl::NO_ERROR
+    }
+
+    GetString(s, name: GLenum) -> *const GLubyte {
+        let string = match name {
+            gl::VERSION => &s.version,
+            gl::VENDOR => &s.vendor,
+            gl::RENDERER => &s.renderer,
+            gl::SHADING_LANGUAGE_VERSION => &s.glsl_version,
+            gl::EXTENSIONS => &s.extensions_string,
+            _ => return ptr::null(),
+        };
+
+        string.as_ptr() as *const GLubyte
+    }
+
+    GetStringi(s, name: GLenum, index: GLuint) -> *const GLubyte {
+        match (name, s.extensions.get(index as usize)) {
+            (gl::EXTENSIONS, Some(ext)) => ext.as_ptr() as *const GLubyte,
+            _ => ptr::null(),
+        }
+    }
+
+    GetIntegerv(s, pname: GLenum, data: *mut GLint) -> () {
+        if pname == gl::NUM_EXTENSIONS {
+            *data = s.extensions.len() as GLint;
+            return;
+        }
+
+        match s.integers.get(&pname) {
+            Some(values) => {
+                for (i, value) in values.iter().enumerate() {
+                    *data.offset(i as isize) = *value;
+                }
+            },
+            None => *data = 0,
+        }
+    }
+
+    GetIntegeri_v(s, target: GLenum, index: GLuint, data: *mut GLint) -> () {
+        *data = s.get_integer(target);
+    }
+
+    GetBooleanv(s, pname: GLenum, data: *mut GLboolean) -> () {
+        *data = match pname {
+            gl::SHADER_COMPILER => gl::TRUE,
+            gl::STEREO => if s.get_integer(gl::STEREO) != 0 { gl::TRUE } else { gl::FALSE },
+            p if s.enabled.contains(&p) => gl::TRUE,
+            _ => gl::FALSE,
+        };
+    }
+
+    GetFloatv(s, pname: GLenum, data: *mut GLfloat) -> () {
+        *data = s.get_integer(pname) as GLfloat;
+    }
+
+    GetFramebufferAttachmentParameteriv(s, target: GLenum, attachment: GLenum, pname: GLenum,
+                                        params: *mut GLint) -> ()
+    {
+        *params = match pname {
+            gl::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE => gl::FRAMEBUFFER_DEFAULT as GLint,
+            gl::FRAMEBUFFER_ATTACHMENT_DEPTH_SIZE => s.get_integer(gl::DEPTH_BITS),
+            gl::FRAMEBUFFER_ATTACHMENT_STENCIL_SIZE => s.get_integer(gl::STENCIL_BITS),
+            gl::FRAMEBUFFER_ATTACHMENT_COLOR_ENCODING => gl::LINEAR as GLint,
+            _ => 0,
+        };
+    }
+
+    Finish(s) -> () {}
+    Flush(s) -> () {}
+
+    // debug output
+    DebugMessageCallback(s, callback: GLDEBUGPROC, user_param: *const libc::c_void) -> () {
+        s.debug_callback = Some((callback, user_param));
+    }
+
+    DebugMessageControl(s, source: GLenum, ty: GLenum, severity: GLenum, count: GLsizei,
+                        ids: *const GLuint, enabled: GLboolean) -> () {}
+    PushDebugGroup(s, source: GLenum, id: GLuint, length: GLsizei,
+                   message: *const GLchar) -> () {}
+    PopDebugGroup(s) -> () {}
+    ObjectLabel(s, identifier: GLenum, name: GLuint, length: GLsizei,
+                label: *const GLchar) -> () {}
+
+    // global state
+    Enable(s, cap: GLenum) -> () {
+        s.enabled.insert(cap);
+    }
+
+    Disable(s, cap: GLenum) -> () {
+        s.enabled.remove(&cap);
+    }
+
+    Enablei(s, cap: GLenum, index: GLuint) -> () {}
+    Disablei(s, cap: GLenum, index: GLuint) -> () {}
+
+    Viewport(s, x: GLint, y: GLint, width: GLsizei, height: GLsizei) -> () {
+        s.integers.insert(gl::VIEWPORT, vec![x, y, width, height]);
+    }
+
+    Scissor(s, x: GLint, y: GLint, width: GLsizei, height: GLsizei) -> () {
+        s.integers.insert(gl::SCISSOR_BOX, vec![x, y, width, height]);
+    }
+
+    ViewportArrayv(s, first: GLuint, count: GLsizei, v: *const GLfloat) -> () {}
+    ScissorArrayv(s, first: GLuint, count: GLsizei, v: *const GLint) -> () {}
+
+    ClearColor(s, red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat) -> () {}
+    ClearDepth(s, depth: GLdouble) -> () {}
+    ClearDepthf(s, depth: GLfloat) -> () {}
+    ClearStencil(s, stencil: GLint) -> () {}
+    Clear(s, mask: GLbitfield) -> () {}
+    ClearBufferfv(s, buffer: GLenum, drawbuffer: GLint, value: *const GLfloat) -> () {}
+    ClearBufferiv(s, buffer: GLenum, drawbuffer: GLint, value: *const GLint) -> () {}
+    ClearBufferuiv(s, buffer: GLenum, drawbuffer: GLint, value: *const GLuint) -> () {}
+    ColorMask(s, red: GLboolean, green: GLboolean, blue: GLboolean, alpha: GLboolean) -> () {}
+    ColorMaski(s, index: GLuint, red: GLboolean, green: GLboolean, blue: GLboolean,
+               alpha: GLboolean) -> () {}
+    DepthMask(s, flag: GLboolean) -> () {}
+    DepthFunc(s, func: GLenum) -> () {}
+    DepthRange(s, near: GLdouble, far: GLdouble) -> () {}
+    BlendColor(s, red: GLfloat, green: GLfloat, blue: GLfloat, alpha: GLfloat) -> () {}
+    BlendEquation(s, mode: GLenum) -> () {}
+    BlendEquationSeparate(s, mode_rgb: GLenum, mode_alpha: GLenum) -> () {}
+    BlendFunc(s, sfactor: GLenum, dfactor: GLenum) -> () {}
+    BlendFuncSeparate(s, sfactor_rgb: GLenum, dfactor_rgb: GLenum, sfactor_alpha: GLenum,
+                      dfactor_alpha: GLenum) -> () {}
+    BlendEquationSeparatei(s, buf: GLuint, mode_rgb: GLenum, mode_alpha: GLenum) -> () {}
+    BlendFuncSeparatei(s, buf: GLuint, sfactor_rgb: GLenum, dfactor_rgb: GLenum,
+                       sfactor_alpha: GLenum, dfactor_alpha: GLenum) -> () {}
+    CullFace(s, mode: GLenum) -> () {}
+    FrontFace(s, mode: GLenum) -> () {}
+    PolygonMode(s, face: GLenum, mode: GLenum) -> () {}
+    PolygonOffset(s, factor: GLfloat, units: GLfloat) -> () {}
+    LogicOp(s, opcode: GLenum) -> () {}
+    ProvokingVertex(s, mode: GLenum) -> () {}
+    PrimitiveRestartIndex(s, index: GLuint) -> () {}
+    SampleMaski(s, mask_number: GLuint, mask: GLbitfield) -> () {}
+    MinSampleShading(s, value: GLfloat) -> () {}
+    MinSampleShadingARB(s, value: GLfloat) -> () {}
+    LineWidth(s, width: GLfloat) -> () {}
+    PointSize(s, size: GLfloat) -> () {}
+    StencilFuncSeparate(s, face: GLenum, func: GLenum, reference: GLint, mask: GLuint) -> () {}
+    StencilOpSeparate(s, face: GLenum, sfail: GLenum, dpfail: GLenum, dppass: GLenum) -> () {}
+    StencilMaskSeparate(s, face: GLenum, mask: GLuint) -> () {}
+    Hint(s, target: GLenum, mode: GLenum) -> () {}
+    PatchParameteri(s, pname: GLenum, value: GLint) -> () {}
+    MemoryBarrier(s, barriers: GLbitfield) -> () {}
+
+    PixelStorei(s, pname: GLenum, param: GLint) -> () {
+        s.integers.insert(pname, vec![param]);
+    }
+
+    // buffers
+    GenBuffers(s, n: GLsizei, buffers: *mut GLuint) -> () {
+        s.gen_names(n, buffers);
+    }
+
+    DeleteBuffers(s, n: GLsizei, buffers: *const GLuint) -> () {
+        s.delete_names(n, buffers);
+    }
+
+    BindBuffer(s, target: GLenum, buffer: GLuint) -> () {
+        s.bind(target, 0, buffer);
+        if buffer != 0 {
+            s.buffers.entry(buffer).or_insert(Vec::new());
+        }
+    }
+
+    BindBufferRange(s, target: GLenum, index: GLuint, buffer: GLuint, offset: GLintptr,
+                    size: GLsizeiptr) -> ()
+    {
+        s.bind(target, 0, buffer);
+        s.bind(target, index, buffer);
+    }
+
+    BindBufferBase(s, target: GLenum, index: GLuint, buffer: GLuint) -> () {
+        s.bind(target, 0, buffer);
+        s.bind(target, index, buffer);
+    }
+
+    BufferData(s, target: GLenum, size: GLsizeiptr, data: *const libc::c_void,
+               usage: GLenum) -> ()
+    {
+        if let Some(buffer) = s.bound_buffer(target) {
+            *buffer = if data.is_null() {
+                vec![0; size as usize]
+            } else {
+                slice::from_raw_parts(data as *const u8, size as usize).to_vec()
+            };
+        }
+    }
+
+    BufferSubData(s, target: GLenum, offset: GLintptr, size: GLsizeiptr,
+                  data: *const libc::c_void) -> ()
+    {
+        if let Some(buffer) = s.bound_buffer(target) {
+            if offset as usize + size as usize <= buffer.len() {
+                ptr::copy_nonoverlapping(data as *const u8, buffer.as_mut_ptr().offset(offset),
+                                         size as usize);
+            }
+        }
+    }
+
+    GetBufferSubData(s, target: GLenum, offset: GLintptr, size: GLsizeiptr,
+                     data: *mut libc::c_void) -> ()
+    {
+        if let Some(buffer) = s.bound_buffer(target) {
+            if offset as usize + size as usize <= buffer.len() {
+                ptr::copy_nonoverlapping(buffer.as_ptr().offset(offset), data as *mut u8,
+                                         size as usize);
+            }
+        }
+    }
+
+    GetBufferParameteriv(s, target: GLenum, pname: GLenum, params: *mut GLint) -> () {
+        *params = match (pname, s.bound_buffer(target)) {
+            (gl::BUFFER_SIZE, Some(buffer)) => buffer.len() as GLint,
+            _ => 0,
+        };
+    }
+
+    CopyBufferSubData(s, read_target: GLenum, write_target: GLenum, read_offset: GLintptr,
+                      write_offset: GLintptr, size: GLsizeiptr) -> ()
+    {
+        let data = match s.bound_buffer(read_target) {
+            Some(ref buffer) if read_offset as usize + size as usize <= buffer.len() => {
+                buffer[read_offset as usize .. (read_offset + size) as usize].to_vec()
+            },
+            _ => return,
+        };
+
+        if let Some(buffer) = s.bound_buffer(write_target) {
+            if write_offset as usize + size as usize <= buffer.len() {
+                for (i, byte) in data.into_iter().enumerate() {
+                    buffer[write_offset as usize + i] = byte;
+                }
+            }
+        }
+    }
+
+    MapBufferRange(s, target: GLenum, offset: GLintptr, length: GLsizeiptr,
+                   access: GLbitfield) -> *mut libc::c_void
+    {
+        match s.bound_buffer(target) {
+            Some(ref mut buffer) if offset as usize + length as usize <= buffer.len() => {
+                buffer.as_mut_ptr().offset(offset) as *mut libc::c_void
+            },
+            _ => ptr::null_mut(),
+        }
+    }
+
+    FlushMappedBufferRange(s, target: GLenum, offset: GLintptr, length: GLsizeiptr) -> () {}
+
+    UnmapBuffer(s, target: GLenum) -> GLboolean {
+        gl::TRUE
+    }
+
+    // textures
+    GenTextures(s, n: GLsizei, textures: *mut GLuint) -> () {
+        s.gen_names(n, textures);
+    }
+
+    DeleteTextures(s, n: GLsizei, textures: *const GLuint) -> () {
+        s.delete_names(n, textures);
+    }
+
+    ActiveTexture(s, texture: GLenum) -> () {
+        s.active_texture = texture - gl::TEXTURE0;
+    }
+
+    BindTexture(s, target: GLenum, texture: GLuint) -> () {
+        let unit = s.active_texture;
+        s.bind(target, unit, texture);
+    }
+
+    TexImage1D(s, target: GLenum, level: GLint, internal_format: GLint, width: GLsizei,
+               border: GLint, format: GLenum, ty: GLenum, pixels: *const libc::c_void) -> () {}
+    TexImage2D(s, target: GLenum, level: GLint, internal_format: GLint, width: GLsizei,
+               height: GLsizei, border: GLint, format: GLenum, ty: GLenum,
+               pixels: *const libc::c_void) -> () {}
+    TexImage3D(s, target: GLenum, level: GLint, internal_format: GLint, width: GLsizei,
+               height: GLsizei, depth: GLsizei, border: GLint, format: GLenum, ty: GLenum,
+               pixels: *const libc::c_void) -> () {}
+    TexSubImage1D(s, target: GLenum, level: GLint, xoffset: GLint, width: GLsizei,
+                  format: GLenum, ty: GLenum, pixels: *const libc::c_void) -> () {}
+    TexSubImage2D(s, target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint,
+                  width: GLsizei, height: GLsizei, format: GLenum, ty: GLenum,
+                  pixels: *const libc::c_void) -> () {}
+    TexSubImage3D(s, target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint,
+                  zoffset: GLint, width: GLsizei, height: GLsizei, depth: GLsizei,
+                  format: GLenum, ty: GLenum, pixels: *const libc::c_void) -> () {}
+    CopyTexSubImage1D(s, target: GLenum, level: GLint, xoffset: GLint, x: GLint, y: GLint,
+                      width: GLsizei) -> () {}
+    CopyTexSubImage2D(s, target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint, x: GLint,
+                      y: GLint, width: GLsizei, height: GLsizei) -> () {}
+    CopyTexSubImage3D(s, target: GLenum, level: GLint, xoffset: GLint, yoffset: GLint,
+                      zoffset: GLint, x: GLint, y: GLint, width: GLsizei,
+                      height: GLsizei) -> () {}
+    TexStorage1D(s, target: GLenum, levels: GLsizei, internal_format: GLenum,
+                 width: GLsizei) -> () {}
+    TexStorage2D(s, target: GLenum, levels: GLsizei, internal_format: GLenum, width: GLsizei,
+                 height: GLsizei) -> () {}
+    TexStorage3D(s, target: GLenum, levels: GLsizei, internal_format: GLenum, width: GLsizei,
+                 height: GLsizei, depth: GLsizei) -> () {}
+    TexParameteri(s, target: GLenum, pname: GLenum, param: GLint) -> () {}
+    GenerateMipmap(s, target: GLenum) -> () {}
+
+    GetTexLevelParameteriv(s, target: GLenum, level: GLint, pname: GLenum,
+                           params: *mut GLint) -> ()
+    {
+        *params = 0;
+    }
+
+    // samplers
+    GenSamplers(s, n: GLsizei, samplers: *mut GLuint) -> () {
+        s.gen_names(n, samplers);
+    }
+
+    DeleteSamplers(s, n: GLsizei, samplers: *const GLuint) -> () {
+        s.delete_names(n, samplers);
+    }
+
+    BindSampler(s, unit: GLuint, sampler: GLuint) -> () {
+        s.bind(gl::SAMPLER_BINDING, unit, sampler);
+    }
+
+    SamplerParameteri(s, sampler: GLuint, pname: GLenum, param: GLint) -> () {}
+    SamplerParameterf(s, sampler: GLuint, pname: GLenum, param: GLfloat) -> () {}
+
+    // framebuffers and renderbuffers
+    GenFramebuffers(s, n: GLsizei, framebuffers: *mut GLuint) -> () {
+        s.gen_names(n, framebuffers);
+    }
+
+    DeleteFramebuffers(s, n: GLsizei, framebuffers: *const GLuint) -> () {
+        s.delete_names(n, framebuffers);
+    }
+
+    BindFramebuffer(s, target: GLenum, framebuffer: GLuint) -> () {
+        if target == gl::FRAMEBUFFER {
+            s.bind(gl::DRAW_FRAMEBUFFER, 0, framebuffer);
+            s.bind(gl::READ_FRAMEBUFFER, 0, framebuffer);
+        } else {
+            s.bind(target, 0, framebuffer);
+        }
+    }
+
+    CheckFramebufferStatus(s, target: GLenum) -> GLenum {
+        gl::FRAMEBUFFER_COMPLETE
+    }
+
+    FramebufferTexture(s, target: GLenum, attachment: GLenum, texture: GLuint,
+                       level: GLint) -> () {}
+    FramebufferTexture1D(s, target: GLenum, attachment: GLenum, textarget: GLenum,
+                         texture: GLuint, level: GLint) -> () {}
+    FramebufferTexture2D(s, target: GLenum, attachment: GLenum, textarget: GLenum,
+                         texture: GLuint, level: GLint) -> () {}
+    FramebufferTextureLayer(s, target: GLenum, attachment: GLenum, texture: GLuint,
+                            level: GLint, layer: GLint) -> () {}
+    FramebufferRenderbuffer(s, target: GLenum, attachment: GLenum, renderbuffertarget: GLenum,
+                            renderbuffer: GLuint) -> () {}
+    FramebufferParameteri(s, target: GLenum, pname: GLenum, param: GLint) -> () {}
+    DrawBuffers(s, n: GLsizei, bufs: *const GLenum) -> () {}
+    DrawBuffer(s, buf: GLenum) -> () {}
+    ReadBuffer(s, src: GLenum) -> () {}
+    InvalidateFramebuffer(s, target: GLenum, num_attachments: GLsizei,
+                          attachments: *const GLenum) -> () {}
+    InvalidateSubFramebuffer(s, target: GLenum, num_attachments: GLsizei,
+                             attachments: *const GLenum, x: GLint, y: GLint, width: GLsizei,
+                             height: GLsizei) -> () {}
+    BlitFramebuffer(s, src_x0: GLint, src_y0: GLint, src_x1: GLint, src_y1: GLint,
+                    dst_x0: GLint, dst_y0: GLint, dst_x1: GLint, dst_y1: GLint,
+                    mask: GLbitfield, filter: GLenum) -> () {}
+
+    ReadPixels(s, x: GLint, y: GLint, width: GLsizei, height: GLsizei, format: GLenum,
+               ty: GLenum, pixels: *mut libc::c_void) -> ()
+    {
+        let size = pixels_size(s, width, height, format, ty);
+
+        // the emulated framebuffers are always black
+        if let Some(buffer) = s.bound_buffer(gl::PIXEL_PACK_BUFFER) {
+            let offset = pixels as usize;
+            if offset + size <= buffer.len() {
+                for byte in buffer[offset .. offset + size].iter_mut() {
+                    *byte = 0;
+                }
+            }
+            return;
+        }
+
+        ptr::write_bytes(pixels as *mut u8, 0, size);
+    }
+
+    GenRenderbuffers(s, n: GLsizei, renderbuffers: *mut GLuint) -> () {
+        s.gen_names(n, renderbuffers);
+    }
+
+    DeleteRenderbuffers(s, n: GLsizei, renderbuffers: *const GLuint) -> () {
+        s.delete_names(n, renderbuffers);
+    }
+
+    BindRenderbuffer(s, target: GLenum, renderbuffer: GLuint) -> () {
+        s.bind(target, 0, renderbuffer);
+    }
+
+    RenderbufferStorage(s, target: GLenum, internal_format: GLenum, width: GLsizei,
+                        height: GLsizei) -> () {}
+    RenderbufferStorageMultisample(s, target: GLenum, samples: GLsizei, internal_format: GLenum,
+                                   width: GLsizei, height: GLsizei) -> () {}
+
+    // vertex array objects
+    GenVertexArrays(s, n: GLsizei, arrays: *mut GLuint) -> () {
+        s.gen_names(n, arrays);
+    }
+
+    DeleteVertexArrays(s, n: GLsizei, arrays: *const GLuint) -> () {
+        s.delete_names(n, arrays);
+    }
+
+    BindVertexArray(s, array: GLuint) -> () {
+        s.bind(gl::VERTEX_ARRAY_BINDING, 0, array);
+    }
+
+    EnableVertexAttribArray(s, index: GLuint) -> () {}
+    DisableVertexAttribArray(s, index: GLuint) -> () {}
+    VertexAttribPointer(s, index: GLuint, size: GLint, ty: GLenum, normalized: GLboolean,
+                        stride: GLsizei, pointer: *const libc::c_void) -> () {}
+    VertexAttribIPointer(s, index: GLuint, size: GLint, ty: GLenum, stride: GLsizei,
+                         pointer: *const libc::c_void) -> () {}
+    VertexAttribLPointer(s, index: GLuint, size: GLint, ty: GLenum, stride: GLsizei,
+                         pointer: *const libc::c_void) -> () {}
+    VertexAttribDivisor(s, index: GLuint, divisor: GLuint) -> () {}
+
+    // shaders and programs
+    CreateShader(s, ty: GLenum) -> GLuint {
+        s.new_name()
+    }
+
+    DeleteShader(s, shader: GLuint) -> () {}
+    ShaderSource(s, shader: GLuint, count: GLsizei, string: *const *const GLchar,
+                 length: *const GLint) -> () {}
+    CompileShader(s, shader: GLuint) -> () {}
+
+    GetShaderiv(s, shader: GLuint, pname: GLenum, params: *mut GLint) -> () {
+        *params = match pname {
+            gl::COMPILE_STATUS => gl::TRUE as GLint,
+            _ => 0,
+        };
+    }
+
+    GetShaderInfoLog(s, shader: GLuint, buf_size: GLsizei, length: *mut GLsizei,
+                     info_log: *mut GLchar) -> ()
+    {
+        if !length.is_null() {
+            *length = 0;
+        }
+
+        if buf_size > 0 && !info_log.is_null() {
+            *info_log = 0;
+        }
+    }
+
+    CreateProgram(s) -> GLuint {
+        s.new_name()
+    }
+
+    DeleteProgram(s, program: GLuint) -> () {}
+    AttachShader(s, program: GLuint, shader: GLuint) -> () {}
+    DetachShader(s, program: GLuint, shader: GLuint) -> () {}
+    LinkProgram(s, program: GLuint) -> () {}
+
+    UseProgram(s, program: GLuint) -> () {
+        s.bind(gl::CURRENT_PROGRAM, 0, program);
+    }
+
+    GetProgramiv(s, program: GLuint, pname: GLenum, params: *mut GLint) -> () {
+        *params = match pname {
+            gl::LINK_STATUS | gl::VALIDATE_STATUS => gl::TRUE as GLint,
+            _ => 0,
+        };
+    }
+
+    GetProgramInfoLog(s, program: GLuint, buf_size: GLsizei, length: *mut GLsizei,
+                      info_log: *mut GLchar) -> ()
+    {
+        if !length.is_null() {
+            *length = 0;
+        }
+
+        if buf_size > 0 && !info_log.is_null() {
+            *info_log = 0;
+        }
+    }
+
+    GetUniformLocation(s, program: GLuint, name: *const GLchar) -> GLint {
+        -1
+    }
+
+    GetAttribLocation(s, program: GLuint, name: *const GLchar) -> GLint {
+        -1
+    }
+
+    GetFragDataLocation(s, program: GLuint, name: *const GLchar) -> GLint {
+        -1
+    }
+
+    GetFragDataIndex(s, program: GLuint, name: *const GLchar) -> GLint {
+        -1
+    }
+
+    UniformBlockBinding(s, program: GLuint, index: GLuint, binding: GLuint) -> () {}
+    TransformFeedbackVaryings(s, program: GLuint, count: GLsizei,
+                              varyings: *const *const GLchar, buffer_mode: GLenum) -> () {}
+
+    // queries
+    GenQueries(s, n: GLsizei, ids: *mut GLuint) -> () {
+        s.gen_names(n, ids);
+    }
+
+    DeleteQueries(s, n: GLsizei, ids: *const GLuint) -> () {
+        s.delete_names(n, ids);
+    }
+
+    BeginQuery(s, target: GLenum, id: GLuint) -> () {}
+    EndQuery(s, target: GLenum) -> () {}
+    QueryCounter(s, id: GLuint, target: GLenum) -> () {}
+    BeginConditionalRender(s, id: GLuint, mode: GLenum) -> () {}
+    EndConditionalRender(s) -> () {}
+
+    GetQueryObjectiv(s, id: GLuint, pname: GLenum, params: *mut GLint) -> () {
+        *params = if pname == gl::QUERY_RESULT_AVAILABLE { gl::TRUE as GLint } else { 0 };
+    }
+
+    GetQueryObjectuiv(s, id: GLuint, pname: GLenum, params: *mut GLuint) -> () {
+        *params = if pname == gl::QUERY_RESULT_AVAILABLE { gl::TRUE as GLuint } else { 0 };
+    }
+
+    GetQueryObjectui64v(s, id: GLuint, pname: GLenum, params: *mut GLuint64) -> () {
+        *params = if pname == gl::QUERY_RESULT_AVAILABLE { gl::TRUE as GLuint64 } else { 0 };
+    }
+
+    // sync objects
+    FenceSync(s, condition: GLenum, flags: GLbitfield) -> GLsync {
+        s.new_name() as usize as GLsync
+    }
+
+    ClientWaitSync(s, sync: GLsync, flags: GLbitfield, timeout: GLuint64) -> GLenum {
+        gl::ALREADY_SIGNALED
+    }
+
+    WaitSync(s, sync: GLsync, flags: GLbitfield, timeout: GLuint64) -> () {}
+    DeleteSync(s, sync: GLsync) -> () {}
+
+    // draw commands
+    DrawArrays(s, mode: GLenum, first: GLint, count: GLsizei) -> () {}
+    DrawArraysInstanced(s, mode: GLenum, first: GLint, count: GLsizei,
+                        instance_count: GLsizei) -> () {}
+    DrawElements(s, mode: GLenum, count: GLsizei, ty: GLenum,
+                 indices: *const libc::c_void) -> () {}
+    DrawElementsInstanced(s, mode: GLenum, count: GLsizei, ty: GLenum,
+                          indices: *const libc::c_void, instance_count: GLsizei) -> () {}
+    DrawElementsBaseVertex(s, mode: GLenum, count: GLsizei, ty: GLenum,
+                           indices: *const libc::c_void, base_vertex: GLint) -> () {}
+    DrawElementsInstancedBaseVertex(s, mode: GLenum, count: GLsizei, ty: GLenum,
+                                    indices: *const libc::c_void, instance_count: GLsizei,
+                                    base_vertex: GLint) -> () {}
+    MultiDrawArraysIndirect(s, mode: GLenum, indirect: *const libc::c_void, draw_count: GLsizei,
+                            stride: GLsizei) -> () {}
+    MultiDrawElementsIndirect(s, mode: GLenum, ty: GLenum, indirect: *const libc::c_void,
+                              draw_count: GLsizei, stride: GLsizei) -> () {}
+    DispatchCompute(s, num_groups_x: GLuint, num_groups_y: GLuint, num_groups_z: GLuint) -> () {}
+}
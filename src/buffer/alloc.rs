@@ -1,4 +1,5 @@
 use backend::Facade;
+use context;
 use context::CommandContext;
 use context::Context;
 use version::Version;
@@ -7,7 +8,7 @@ use ContextExt;
 use gl;
 use libc;
 use std::{fmt, mem, ptr};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::ops::{Deref, DerefMut, Range};
 use GlObject;
@@ -59,6 +60,9 @@ pub struct Buffer {
 
     /// ID of the draw call where the buffer was last written as an SSBO.
     latest_shader_write: Cell<u64>,
+
+    /// Label attached with `set_label`.
+    label: RefCell<Option<String>>,
 }
 
 impl Buffer {
@@ -86,6 +90,7 @@ impl Buffer {
             creation_mode: mode,
             mapped: Cell::new(false),
             latest_shader_write: Cell::new(0),
+            label: RefCell::new(None),
         })
     }
 
@@ -109,6 +114,7 @@ impl Buffer {
             creation_mode: mode,
             mapped: Cell::new(false),
             latest_shader_write: Cell::new(0),
+            label: RefCell::new(None),
         })
     }
 
@@ -117,6 +123,18 @@ impl Buffer {
         &self.context
     }
 
+    /// Attaches a label to the buffer.
+    pub fn set_label(&self, label: &str) {
+        let mut ctxt = self.context.make_current();
+        let _ = context::set_object_label(&mut ctxt, gl::BUFFER, self.id, label);
+        *self.label.borrow_mut() = Some(label.to_string());
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.label.borrow().clone()
+    }
+
     /// Returns the total size in bytes of this buffer.
     pub fn get_size(&self) -> usize {
         self.size
@@ -729,7 +747,11 @@ impl Buffer {
 
 impl fmt::Debug for Buffer {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "Buffer #{} (size: {} bytes)", self.id, self.size)
+        match *self.label.borrow() {
+            Some(ref label) => write!(fmt, "Buffer #{} (size: {} bytes, label: {:?})", self.id,
+                                      self.size, label),
+            None => write!(fmt, "Buffer #{} (size: {} bytes)", self.id, self.size),
+        }
     }
 }
 
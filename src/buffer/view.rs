@@ -76,6 +76,19 @@ impl<T: ?Sized> BufferView<T> where T: Content {
         self.alloc.as_ref().unwrap().get_context()
     }
 
+    /// Attaches a label to the buffer.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the buffer with `{:?}`.
+    pub fn set_label(&self, label: &str) {
+        self.alloc.as_ref().unwrap().set_label(label)
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.alloc.as_ref().unwrap().get_label()
+    }
+
     /// Returns the size in bytes of this buffer.
     pub fn get_size(&self) -> usize {
         self.alloc.as_ref().unwrap().get_size()
@@ -620,6 +633,19 @@ impl BufferViewAny {
         self.alloc.get_context()
     }
 
+    /// Attaches a label to the buffer.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the buffer with `{:?}`.
+    pub fn set_label(&self, label: &str) {
+        self.alloc.set_label(label)
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.alloc.get_label()
+    }
+
     /// Returns the number of bytes in this subbuffer.
     pub fn get_size(&self) -> usize {
         self.size
@@ -31,6 +31,9 @@ pub struct Capabilities {
     /// Number of bits in the default framebuffer's stencil buffer
     pub stencil_bits: Option<u16>,
 
+    /// Number of samples of the default framebuffer, or `None` if it is not multisampled.
+    pub samples: Option<u16>,
+
     /// Maximum number of textures that can be bound to a program.
     ///
     /// `glActiveTexture` must be between `GL_TEXTURE0` and `GL_TEXTURE0` + this value - 1.
@@ -44,9 +47,37 @@ pub struct Capabilities {
     /// Maximum width and height of `glViewport`.
     pub max_viewport_dims: (gl::types::GLint, gl::types::GLint),
 
+    /// Maximum number of viewports and scissor boxes that can be passed at once with
+    /// `glViewportArrayv` and `glScissorArrayv`. `1` if viewport arrays are not supported.
+    pub max_viewports: gl::types::GLint,
+
     /// Maximum number of elements that can be passed with `glDrawBuffers`.
     pub max_draw_buffers: gl::types::GLint,
 
+    /// Maximum number of `gl_ClipDistance` that can be written by shaders. `0` if clip
+    /// distances are not supported.
+    pub max_clip_distances: gl::types::GLint,
+
+    /// Maximum number of samples of a multisample render buffer. `None` if multisample render
+    /// buffers are not supported.
+    pub max_samples: Option<gl::types::GLint>,
+
+    /// Maximum width of a framebuffer without attachments. `None` if framebuffers without
+    /// attachments are not supported.
+    pub max_framebuffer_width: Option<gl::types::GLint>,
+
+    /// Maximum height of a framebuffer without attachments. `None` if framebuffers without
+    /// attachments are not supported.
+    pub max_framebuffer_height: Option<gl::types::GLint>,
+
+    /// Maximum number of layers of a framebuffer without attachments. `None` if framebuffers
+    /// without attachments are not supported, or if they can't be layered (OpenGL ES).
+    pub max_framebuffer_layers: Option<gl::types::GLint>,
+
+    /// Maximum number of samples of a framebuffer without attachments. `None` if framebuffers
+    /// without attachments are not supported.
+    pub max_framebuffer_samples: Option<gl::types::GLint>,
+
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
 
@@ -214,6 +245,16 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        samples: {
+            let mut value = 0;
+            gl.GetIntegerv(gl::SAMPLES, &mut value);
+
+            match value {
+                0 => None,
+                v => Some(v as u16),
+            }
+        },
+
         max_combined_texture_image_units: {
             let mut val = 2;
             gl.GetIntegerv(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS, &mut val);
@@ -249,6 +290,89 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_viewports: {
+            if version >= &Version(Api::Gl, 4, 1) || extensions.gl_arb_viewport_array {
+                let mut val = 1;
+                gl.GetIntegerv(gl::MAX_VIEWPORTS, &mut val);
+                val
+            } else {
+                1
+            }
+        },
+
+        max_clip_distances: {
+            if version.0 == Api::Gl {
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_CLIP_DISTANCES, &mut val);
+                val
+            } else {
+                0
+            }
+        },
+
+        max_samples: if version >= &Version(Api::Gl, 3, 0) ||
+                        version >= &Version(Api::GlEs, 3, 0)
+        {
+            Some({
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_SAMPLES, &mut val);
+                val
+            })
+
+        } else if extensions.gl_ext_framebuffer_multisample {
+            Some({
+                let mut val = mem::uninitialized();
+                gl.GetIntegerv(gl::MAX_SAMPLES_EXT, &mut val);
+                val
+            })
+
+        } else {
+            None
+        },
+
+        max_framebuffer_width: if version >= &Version(Api::Gl, 4, 3) ||
+                                  version >= &Version(Api::GlEs, 3, 1) ||
+                                  extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_WIDTH, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
+        max_framebuffer_height: if version >= &Version(Api::Gl, 4, 3) ||
+                                   version >= &Version(Api::GlEs, 3, 1) ||
+                                   extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_HEIGHT, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
+        max_framebuffer_layers: if version >= &Version(Api::Gl, 4, 3) ||
+                                   extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_LAYERS, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
+        max_framebuffer_samples: if version >= &Version(Api::Gl, 4, 3) ||
+                                    version >= &Version(Api::GlEs, 3, 1) ||
+                                    extensions.gl_arb_framebuffer_no_attachments
+        {
+            let mut val = mem::uninitialized();
+            gl.GetIntegerv(gl::MAX_FRAMEBUFFER_SAMPLES, &mut val);
+            Some(val)
+        } else {
+            None
+        },
+
         max_patch_vertices: if version >= &Version(Api::Gl, 4, 0) ||
             extensions.gl_arb_tessellation_shader
         {
@@ -12,6 +12,8 @@ pub struct ExtensionsList {
     pub gl_apple_vertex_array_object: bool,
     /// GL_ARB_bindless_texture
     pub gl_arb_bindless_texture: bool,
+    /// GL_ARB_blend_func_extended
+    pub gl_arb_blend_func_extended: bool,
     /// GL_ARB_buffer_storage
     pub gl_arb_buffer_storage: bool,
     /// GL_ARB_compute_shader
@@ -20,10 +22,14 @@ pub struct ExtensionsList {
     pub gl_arb_copy_buffer: bool,
     /// GL_ARB_debug_output
     pub gl_arb_debug_output: bool,
+    /// GL_ARB_depth_clamp
+    pub gl_arb_depth_clamp: bool,
     /// GL_ARB_depth_texture
     pub gl_arb_depth_texture: bool,
     /// GL_ARB_direct_state_access
     pub gl_arb_direct_state_access: bool,
+    /// GL_ARB_draw_buffers_blend
+    pub gl_arb_draw_buffers_blend: bool,
     /// GL_ARB_compatibility
     pub gl_arb_compatibility: bool,
     /// GL_ARB_ES2_compatibility
@@ -34,6 +40,8 @@ pub struct ExtensionsList {
     pub gl_arb_es3_1_compatibility: bool,
     /// GL_ARB_fragment_shader
     pub gl_arb_fragment_shader: bool,
+    /// GL_ARB_framebuffer_no_attachments
+    pub gl_arb_framebuffer_no_attachments: bool,
     /// GL_ARB_framebuffer_sRGB
     pub gl_arb_framebuffer_srgb: bool,
     /// GL_ARB_geometry_shader4
@@ -60,6 +68,10 @@ pub struct ExtensionsList {
     pub gl_arb_pixel_buffer_object: bool,
     /// GL_ARB_program_interface_query
     pub gl_arb_program_interface_query: bool,
+    /// GL_ARB_provoking_vertex
+    pub gl_arb_provoking_vertex: bool,
+    /// GL_ARB_sample_shading
+    pub gl_arb_sample_shading: bool,
     /// GL_ARB_sampler_objects
     pub gl_arb_sampler_objects: bool,
     /// GL_ARB_shader_image_load_store
@@ -74,6 +86,8 @@ pub struct ExtensionsList {
     pub gl_arb_tessellation_shader: bool,
     /// GL_ARB_texture_compression_bptc
     pub gl_arb_texture_compression_bptc: bool,
+    /// GL_ARB_texture_cube_map_array
+    pub gl_arb_texture_cube_map_array: bool,
     /// GL_ARB_texture_float
     pub gl_arb_texture_float: bool,
     /// GL_ARB_texture_multisample
@@ -106,6 +120,8 @@ pub struct ExtensionsList {
     pub gl_arb_vertex_type_10f_11f_11f_rev: bool,
     /// GL_ARB_vertex_type_2_10_10_10_rev
     pub gl_arb_vertex_type_2_10_10_10_rev: bool,
+    /// GL_ARB_viewport_array
+    pub gl_arb_viewport_array: bool,
     /// GL_ARM_rgba8
     pub gl_arm_rgba8: bool,
     /// GL_ATI_meminfo
@@ -116,10 +132,16 @@ pub struct ExtensionsList {
     pub gl_ext_debug_marker: bool,
     /// GL_EXT_direct_state_access
     pub gl_ext_direct_state_access: bool,
+    /// GL_EXT_discard_framebuffer
+    pub gl_ext_discard_framebuffer: bool,
     /// GL_EXT_disjoint_timer_query
     pub gl_ext_disjoint_timer_query: bool,
+    /// GL_EXT_draw_buffers2
+    pub gl_ext_draw_buffers2: bool,
     /// GL_EXT_framebuffer_blit
     pub gl_ext_framebuffer_blit: bool,
+    /// GL_EXT_framebuffer_multisample
+    pub gl_ext_framebuffer_multisample: bool,
     /// GL_EXT_framebuffer_object
     pub gl_ext_framebuffer_object: bool,
     /// GL_EXT_framebuffer_sRGB
@@ -200,17 +222,21 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_apple_sync: false,
         gl_apple_vertex_array_object: false,
         gl_arb_bindless_texture: false,
+        gl_arb_blend_func_extended: false,
         gl_arb_buffer_storage: false,
         gl_arb_copy_buffer: false,
         gl_arb_compute_shader: false,
         gl_arb_debug_output: false,
+        gl_arb_depth_clamp: false,
         gl_arb_depth_texture: false,
         gl_arb_direct_state_access: false,
+        gl_arb_draw_buffers_blend: false,
         gl_arb_compatibility: false,
         gl_arb_es2_compatibility: false,
         gl_arb_es3_compatibility: false,
         gl_arb_es3_1_compatibility: false,
         gl_arb_fragment_shader: false,
+        gl_arb_framebuffer_no_attachments: false,
         gl_arb_framebuffer_srgb: false,
         gl_arb_geometry_shader4: false,
         gl_arb_get_programy_binary: false,
@@ -222,8 +248,10 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_multi_draw_indirect: false,
         gl_arb_pixel_buffer_object: false,
         gl_arb_program_interface_query: false,
+        gl_arb_provoking_vertex: false,
         gl_arb_robustness: false,
         gl_arb_robust_buffer_access_behavior: false,
+        gl_arb_sample_shading: false,
         gl_arb_sampler_objects: false,
         gl_arb_shader_image_load_store: false,
         gl_arb_shader_objects: false,
@@ -231,6 +259,7 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_sync: false,
         gl_arb_tessellation_shader: false,
         gl_arb_texture_compression_bptc: false,
+        gl_arb_texture_cube_map_array: false,
         gl_arb_texture_float: false,
         gl_arb_texture_multisample: false,
         gl_arb_texture_non_power_of_two: false,
@@ -246,13 +275,17 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
         gl_arb_vertex_shader: false,
         gl_arb_vertex_type_10f_11f_11f_rev: false,
         gl_arb_vertex_type_2_10_10_10_rev: false,
+        gl_arb_viewport_array: false,
         gl_arm_rgba8: false,
         gl_ati_meminfo: false,
         gl_ext_buffer_storage: false,
         gl_ext_debug_marker: false,
         gl_ext_direct_state_access: false,
+        gl_ext_discard_framebuffer: false,
         gl_ext_disjoint_timer_query: false,
+        gl_ext_draw_buffers2: false,
         gl_ext_framebuffer_blit: false,
+        gl_ext_framebuffer_multisample: false,
         gl_ext_framebuffer_object: false,
         gl_ext_framebuffer_srgb: false,
         gl_ext_geometry_shader4: false,
@@ -291,17 +324,21 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_APPLE_sync" => extensions.gl_apple_sync = true,
             "GL_APPLE_vertex_array_object" => extensions.gl_apple_vertex_array_object = true,
             "GL_ARB_bindless_texture" => extensions.gl_arb_bindless_texture = true,
+            "GL_ARB_blend_func_extended" => extensions.gl_arb_blend_func_extended = true,
             "GL_ARB_buffer_storage" => extensions.gl_arb_buffer_storage = true,
             "GL_ARB_compute_shader" => extensions.gl_arb_compute_shader = true,
             "GL_ARB_copy_buffer" => extensions.gl_arb_copy_buffer = true,
             "GL_ARB_debug_output" => extensions.gl_arb_debug_output = true,
+            "GL_ARB_depth_clamp" => extensions.gl_arb_depth_clamp = true,
             "GL_ARB_depth_texture" => extensions.gl_arb_depth_texture = true,
             "GL_ARB_direct_state_access" => extensions.gl_arb_direct_state_access = true,
+            "GL_ARB_draw_buffers_blend" => extensions.gl_arb_draw_buffers_blend = true,
             "GL_ARB_compatibility" => extensions.gl_arb_compatibility = true,
             "GL_ARB_ES2_compatibility" => extensions.gl_arb_es2_compatibility = true,
             "GL_ARB_ES3_compatibility" => extensions.gl_arb_es3_compatibility = true,
             "GL_ARB_ES3_1_compatibility" => extensions.gl_arb_es3_1_compatibility = true,
             "GL_ARB_fragment_shader" => extensions.gl_arb_fragment_shader = true,
+            "GL_ARB_framebuffer_no_attachments" => extensions.gl_arb_framebuffer_no_attachments = true,
             "GL_ARB_framebuffer_sRGB" => extensions.gl_arb_framebuffer_srgb = true,
             "GL_ARB_geometry_shader4" => extensions.gl_arb_geometry_shader4 = true,
             "GL_ARB_get_program_binary" => extensions.gl_arb_get_programy_binary = true,
@@ -311,10 +348,12 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_occlusion_query2" => extensions.gl_arb_occlusion_query2 = true,
             "GL_ARB_pixel_buffer_object" => extensions.gl_arb_pixel_buffer_object = true,
             "GL_ARB_program_interface_query" => extensions.gl_arb_program_interface_query = true,
+            "GL_ARB_provoking_vertex" => extensions.gl_arb_provoking_vertex = true,
             "GL_ARB_map_buffer_range" => extensions.gl_arb_map_buffer_range = true,
             "GL_ARB_multi_draw_indirect" => extensions.gl_arb_multi_draw_indirect = true,
             "GL_ARB_robustness" => extensions.gl_arb_robustness = true,
             "GL_ARB_robust_buffer_access_behavior" => extensions.gl_arb_robust_buffer_access_behavior = true,
+            "GL_ARB_sample_shading" => extensions.gl_arb_sample_shading = true,
             "GL_ARB_sampler_objects" => extensions.gl_arb_sampler_objects = true,
             "GL_ARB_shader_image_load_store" => extensions.gl_arb_shader_image_load_store = true,
             "GL_ARB_shader_objects" => extensions.gl_arb_shader_objects = true,
@@ -322,6 +361,7 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_sync" => extensions.gl_arb_sync = true,
             "GL_ARB_tessellation_shader" => extensions.gl_arb_tessellation_shader = true,
             "GL_ARB_texture_compression_bptc" => extensions.gl_arb_texture_compression_bptc = true,
+            "GL_ARB_texture_cube_map_array" => extensions.gl_arb_texture_cube_map_array = true,
             "GL_ARB_texture_float" => extensions.gl_arb_texture_float = true,
             "GL_ARB_texture_multisample" => extensions.gl_arb_texture_multisample = true,
             "GL_ARB_texture_non_power_of_two" => extensions.gl_arb_texture_non_power_of_two = true,
@@ -337,13 +377,17 @@ pub unsafe fn get_extensions(gl: &gl::Gl, version: &Version) -> ExtensionsList {
             "GL_ARB_vertex_shader" => extensions.gl_arb_vertex_shader = true,
             "GL_ARB_vertex_type_10f_11f_11f_rev" => extensions.gl_arb_vertex_type_10f_11f_11f_rev = true,
             "GL_ARB_vertex_type_2_10_10_10_rev" => extensions.gl_arb_vertex_type_2_10_10_10_rev = true,
+            "GL_ARB_viewport_array" => extensions.gl_arb_viewport_array = true,
             "GL_ARM_rgba8" => extensions.gl_arm_rgba8 = true,
             "GL_ATI_meminfo" => extensions.gl_ati_meminfo = true,
             "GL_EXT_buffer_storage" => extensions.gl_ext_buffer_storage = true,
             "GL_EXT_debug_marker" => extensions.gl_ext_debug_marker = true,
             "GL_EXT_direct_state_access" => extensions.gl_ext_direct_state_access = true,
+            "GL_EXT_discard_framebuffer" => extensions.gl_ext_discard_framebuffer = true,
             "GL_EXT_disjoint_timer_query" => extensions.gl_ext_disjoint_timer_query = true,
+            "GL_EXT_draw_buffers2" => extensions.gl_ext_draw_buffers2 = true,
             "GL_EXT_framebuffer_blit" => extensions.gl_ext_framebuffer_blit = true,
+            "GL_EXT_framebuffer_multisample" => extensions.gl_ext_framebuffer_multisample = true,
             "GL_EXT_framebuffer_object" => extensions.gl_ext_framebuffer_object = true,
             "GL_EXT_framebuffer_sRGB" => extensions.gl_ext_framebuffer_srgb = true,
             "GL_EXT_geometry_shader4" => extensions.gl_ext_geometry_shader4 = true,
@@ -18,6 +18,7 @@ use SwapBuffersError;
 use CapabilitiesSource;
 use ContextExt;
 use backend::Backend;
+use debug;
 use version;
 use version::Api;
 use version::Version;
@@ -31,7 +32,7 @@ use vertex_array_object;
 
 pub use self::capabilities::Capabilities;
 pub use self::extensions::ExtensionsList;
-pub use self::state::GlState;
+pub use self::state::{GlState, IndexedBlendingState};
 
 mod capabilities;
 mod extensions;
@@ -70,6 +71,10 @@ pub struct Context {
     /// like compiling/linking shaders.
     report_debug_output_errors: Cell<bool>,
 
+    /// Function called when the backend emits a debug message. If `None`, glium panics when
+    /// it receives an error with a high or medium severity.
+    debug_callback: RefCell<Option<debug::DebugCallback>>,
+
     /// We maintain a cache of FBOs.
     /// The `Option` is here in order to destroy the container. It must be filled at all time
     /// is a normal situation.
@@ -193,6 +198,7 @@ impl Context {
             extensions: extensions,
             capabilities: capabilities,
             report_debug_output_errors: report_debug_output_errors,
+            debug_callback: RefCell::new(None),
             backend: RefCell::new(Box::new(backend)),
             check_current_context: check_current_context,
             framebuffer_objects: Some(framebuffer_objects),
@@ -501,6 +507,159 @@ impl Context {
             Ok(())
         }
     }
+
+    /// Sets the function that is called when the backend emits a debug message.
+    ///
+    /// By default, glium panics in debug builds when it receives an error with a high or
+    /// medium severity. Setting a callback replaces this behavior. The callback receives every
+    /// message that is not disabled with `set_debug_messages_enabled`, in both debug and
+    /// release builds.
+    ///
+    /// Returns `Err` if the backend doesn't support debug output.
+    pub fn set_debug_callback(&self, callback: debug::DebugCallback) -> Result<(), ()> {
+        try!(register_debug_callback(self));
+        *self.debug_callback.borrow_mut() = Some(callback);
+        Ok(())
+    }
+
+    /// Removes the callback set with `set_debug_callback` and restores the default behavior.
+    pub fn remove_debug_callback(&self) {
+        *self.debug_callback.borrow_mut() = None;
+    }
+
+    /// Enables or disables the debug messages that match a source, a type and a severity.
+    ///
+    /// Passing `None` matches every source, type or severity. For example calling
+    /// `set_debug_messages_enabled(None, Some(MessageType::Performance), None, false)` disables
+    /// all performance warnings.
+    ///
+    /// Returns `Err` if the backend doesn't support debug output.
+    pub fn set_debug_messages_enabled(&self, source: Option<debug::Source>,
+                                      ty: Option<debug::MessageType>,
+                                      severity: Option<debug::Severity>, enabled: bool)
+                                      -> Result<(), ()>
+    {
+        let ctxt = self.make_current();
+
+        let source = source.map(|s| s as gl::types::GLenum).unwrap_or(gl::DONT_CARE);
+        let ty = ty.map(|t| t as gl::types::GLenum).unwrap_or(gl::DONT_CARE);
+        let severity = severity.map(|s| s as gl::types::GLenum).unwrap_or(gl::DONT_CARE);
+        let enabled = if enabled { gl::TRUE } else { gl::FALSE };
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+            {
+                ctxt.gl.DebugMessageControl(source, ty, severity, 0, ptr::null(), enabled);
+                Ok(())
+
+            } else if ctxt.version >= &Version(Api::GlEs, 2, 0) &&
+                ctxt.extensions.gl_khr_debug
+            {
+                ctxt.gl.DebugMessageControlKHR(source, ty, severity, 0, ptr::null(), enabled);
+                Ok(())
+
+            } else if ctxt.extensions.gl_arb_debug_output {
+                ctxt.gl.DebugMessageControlARB(source, ty, severity, 0, ptr::null(), enabled);
+                Ok(())
+
+            } else {
+                Err(())
+            }
+        }
+    }
+
+    /// Pushes a debug group onto the debug groups stack.
+    ///
+    /// Debug groups are shown by debugging tools like apitrace or RenderDoc, and make it easier
+    /// to identify the commands that belong to a part of your application. The group is popped
+    /// when the returned object is destroyed.
+    ///
+    /// Returns `Err` if the backend doesn't support this functionnality. You can choose whether
+    /// to call `.unwrap()` if you want to make sure that it works, or `.ok()` if you don't care.
+    pub fn push_debug_group(&self, name: &str) -> Result<DebugGroup, ()> {
+        let ctxt = self.make_current();
+        let name = name.as_bytes();
+
+        if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+            (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+        {
+            unsafe { ctxt.gl.PushDebugGroup(gl::DEBUG_SOURCE_APPLICATION, 0,
+                                            name.len() as gl::types::GLsizei,
+                                            name.as_ptr() as *const _) };
+            Ok(DebugGroup { context: self, method: DebugGroupMethod::Core })
+
+        } else if ctxt.version >= &Version(Api::GlEs, 2, 0) && ctxt.extensions.gl_khr_debug {
+            unsafe { ctxt.gl.PushDebugGroupKHR(gl::DEBUG_SOURCE_APPLICATION, 0,
+                                               name.len() as gl::types::GLsizei,
+                                               name.as_ptr() as *const _) };
+            Ok(DebugGroup { context: self, method: DebugGroupMethod::Khr })
+
+        } else if ctxt.extensions.gl_ext_debug_marker {
+            unsafe { ctxt.gl.PushGroupMarkerEXT(name.len() as gl::types::GLsizei,
+                                                name.as_ptr() as *const _) };
+            Ok(DebugGroup { context: self, method: DebugGroupMethod::Ext })
+
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// A debug group pushed with `Context::push_debug_group`. The group is popped when this object
+/// is destroyed.
+pub struct DebugGroup<'a> {
+    context: &'a Context,
+    method: DebugGroupMethod,
+}
+
+/// Function that was used to push a debug group.
+enum DebugGroupMethod {
+    /// `glPushDebugGroup`
+    Core,
+    /// `glPushDebugGroupKHR`
+    Khr,
+    /// `glPushGroupMarkerEXT`
+    Ext,
+}
+
+impl<'a> Drop for DebugGroup<'a> {
+    fn drop(&mut self) {
+        let ctxt = self.context.make_current();
+
+        unsafe {
+            match self.method {
+                DebugGroupMethod::Core => ctxt.gl.PopDebugGroup(),
+                DebugGroupMethod::Khr => ctxt.gl.PopDebugGroupKHR(),
+                DebugGroupMethod::Ext => ctxt.gl.PopGroupMarkerEXT(),
+            }
+        }
+    }
+}
+
+/// Attaches a label to an OpenGL object with `glObjectLabel`.
+///
+/// Returns `Err` if the backend doesn't support labels.
+pub fn set_object_label(ctxt: &mut CommandContext, identifier: gl::types::GLenum,
+                        name: gl::types::GLuint, label: &str) -> Result<(), ()>
+{
+    let label = label.as_bytes();
+
+    if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+        (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+    {
+        unsafe { ctxt.gl.ObjectLabel(identifier, name, label.len() as gl::types::GLsizei,
+                                     label.as_ptr() as *const _) };
+        Ok(())
+
+    } else if ctxt.version >= &Version(Api::GlEs, 2, 0) && ctxt.extensions.gl_khr_debug {
+        unsafe { ctxt.gl.ObjectLabelKHR(identifier, name, label.len() as gl::types::GLsizei,
+                                        label.as_ptr() as *const _) };
+        Ok(())
+
+    } else {
+        Err(())
+    }
 }
 
 impl ContextExt for Context {
@@ -669,36 +828,19 @@ fn init_debug_callback(context: &Rc<Context>) {
         return;
     }
 
-    // this is the C callback
-    extern "system" fn callback_wrapper(source: gl::types::GLenum, ty: gl::types::GLenum,
-                                        id: gl::types::GLuint, severity: gl::types::GLenum,
-                                        _length: gl::types::GLsizei,
-                                        message: *const gl::types::GLchar,
-                                        user_param: *mut libc::c_void)
-    {
-        let user_param = user_param as *const Context;
-        let user_param: &Context = unsafe { mem::transmute(user_param) };
-
-        if (severity == gl::DEBUG_SEVERITY_HIGH || severity == gl::DEBUG_SEVERITY_MEDIUM) &&
-           (ty == gl::DEBUG_TYPE_ERROR || ty == gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR ||
-            ty == gl::DEBUG_TYPE_PORTABILITY || ty == gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR)
-        {
-            if user_param.report_debug_output_errors.get() {
-                // reporting
-                let message = unsafe {
-                    String::from_utf8(CStr::from_ptr(message).to_bytes().to_vec()).unwrap()
-                };
-
-                panic!("Debug message with high or medium severity: `{}`.\n\
-                        Please report this error: https://github.com/tomaka/glium/issues",
-                        message);
-            }
-        }
+    if register_debug_callback(context).is_ok() {
+        // some messages, like the ones with a low severity, are disabled by default
+        let _ = context.set_debug_messages_enabled(None, None, None, true);
     }
+}
 
+/// Registers `debug_callback_wrapper` as the debug output callback of the backend.
+///
+/// Returns `Err` if the backend doesn't support debug output.
+fn register_debug_callback(context: &Context) -> Result<(), ()> {
     struct ContextRawPtr(*const Context);
     unsafe impl Send for ContextRawPtr {}
-    let context_raw_ptr = ContextRawPtr(&**context);
+    let context_raw_ptr = ContextRawPtr(context);
 
     unsafe {
         let mut ctxt = context.make_current();
@@ -714,10 +856,8 @@ fn init_debug_callback(context: &Rc<Context>) {
             if ctxt.version >= &Version(Api::Gl, 4, 5) ||
                 (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
             {
-                ctxt.gl.DebugMessageCallback(callback_wrapper, context_raw_ptr.0
-                                                                 as *const libc::c_void);
-                ctxt.gl.DebugMessageControl(gl::DONT_CARE, gl::DONT_CARE, gl::DONT_CARE, 0,
-                                            ptr::null(), gl::TRUE);
+                ctxt.gl.DebugMessageCallback(debug_callback_wrapper, context_raw_ptr.0
+                                                                       as *const libc::c_void);
 
                 if ctxt.state.enabled_debug_output != Some(true) {
                     ctxt.gl.Enable(gl::DEBUG_OUTPUT);
@@ -727,10 +867,8 @@ fn init_debug_callback(context: &Rc<Context>) {
             } else if ctxt.version >= &Version(Api::GlEs, 2, 0) &&
                 ctxt.extensions.gl_khr_debug
             {
-                ctxt.gl.DebugMessageCallbackKHR(callback_wrapper, context_raw_ptr.0
-                                                                 as *const libc::c_void);
-                ctxt.gl.DebugMessageControlKHR(gl::DONT_CARE, gl::DONT_CARE, gl::DONT_CARE, 0,
-                                               ptr::null(), gl::TRUE);
+                ctxt.gl.DebugMessageCallbackKHR(debug_callback_wrapper, context_raw_ptr.0
+                                                                       as *const libc::c_void);
 
                 if ctxt.state.enabled_debug_output != Some(true) {
                     ctxt.gl.Enable(gl::DEBUG_OUTPUT);
@@ -738,13 +876,92 @@ fn init_debug_callback(context: &Rc<Context>) {
                 }
 
             } else {
-                ctxt.gl.DebugMessageCallbackARB(callback_wrapper, context_raw_ptr.0
-                                                                    as *const libc::c_void);
-                ctxt.gl.DebugMessageControlARB(gl::DONT_CARE, gl::DONT_CARE, gl::DONT_CARE,
-                                               0, ptr::null(), gl::TRUE);
-
+                ctxt.gl.DebugMessageCallbackARB(debug_callback_wrapper, context_raw_ptr.0
+                                                                          as *const libc::c_void);
                 ctxt.state.enabled_debug_output = Some(true);
             }
+
+            Ok(())
+
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// The callback that is registered with `glDebugMessageCallback`. Forwards the messages to the
+/// callback of the context, or panics on errors if there is none.
+extern "system" fn debug_callback_wrapper(source: gl::types::GLenum, ty: gl::types::GLenum,
+                                          id: gl::types::GLuint, severity: gl::types::GLenum,
+                                          _length: gl::types::GLsizei,
+                                          message: *const gl::types::GLchar,
+                                          user_param: *mut libc::c_void)
+{
+    let user_param = user_param as *const Context;
+    let user_param: &Context = unsafe { mem::transmute(user_param) };
+
+    let message = unsafe {
+        String::from_utf8_lossy(CStr::from_ptr(message).to_bytes()).into_owned()
+    };
+
+    // the callback is removed from the context while it is running, so that a message
+    // emitted from within the callback doesn't borrow it twice
+    let callback = user_param.debug_callback.borrow_mut().take();
+
+    if let Some(mut callback) = callback {
+        (*callback)(glenum_to_debug_source(source), glenum_to_debug_message_type(ty),
+                    glenum_to_debug_severity(severity), id, &message);
+
+        let mut current = user_param.debug_callback.borrow_mut();
+        if current.is_none() {
+            *current = Some(callback);
+        }
+
+        return;
+    }
+
+    if (severity == gl::DEBUG_SEVERITY_HIGH || severity == gl::DEBUG_SEVERITY_MEDIUM) &&
+       (ty == gl::DEBUG_TYPE_ERROR || ty == gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR ||
+        ty == gl::DEBUG_TYPE_PORTABILITY || ty == gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR)
+    {
+        if user_param.report_debug_output_errors.get() {
+            panic!("Debug message with high or medium severity: `{}`.\n\
+                    Please report this error: https://github.com/tomaka/glium/issues",
+                    message);
         }
     }
 }
+
+fn glenum_to_debug_source(value: gl::types::GLenum) -> debug::Source {
+    match value {
+        gl::DEBUG_SOURCE_API => debug::Source::Api,
+        gl::DEBUG_SOURCE_WINDOW_SYSTEM => debug::Source::WindowSystem,
+        gl::DEBUG_SOURCE_SHADER_COMPILER => debug::Source::ShaderCompiler,
+        gl::DEBUG_SOURCE_THIRD_PARTY => debug::Source::ThirdParty,
+        gl::DEBUG_SOURCE_APPLICATION => debug::Source::Application,
+        _ => debug::Source::OtherSource,
+    }
+}
+
+fn glenum_to_debug_message_type(value: gl::types::GLenum) -> debug::MessageType {
+    match value {
+        gl::DEBUG_TYPE_ERROR => debug::MessageType::Error,
+        gl::DEBUG_TYPE_DEPRECATED_BEHAVIOR => debug::MessageType::DeprecatedBehavior,
+        gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR => debug::MessageType::UndefinedBehavior,
+        gl::DEBUG_TYPE_PORTABILITY => debug::MessageType::Portability,
+        gl::DEBUG_TYPE_PERFORMANCE => debug::MessageType::Performance,
+        gl::DEBUG_TYPE_MARKER => debug::MessageType::Marker,
+        gl::DEBUG_TYPE_PUSH_GROUP => debug::MessageType::PushGroup,
+        gl::DEBUG_TYPE_POP_GROUP => debug::MessageType::PopGroup,
+        _ => debug::MessageType::Other,
+    }
+}
+
+fn glenum_to_debug_severity(value: gl::types::GLenum) -> debug::Severity {
+    match value {
+        gl::DEBUG_SEVERITY_HIGH => debug::Severity::High,
+        gl::DEBUG_SEVERITY_MEDIUM => debug::Severity::Medium,
+        gl::DEBUG_SEVERITY_LOW => debug::Severity::Low,
+        _ => debug::Severity::Notification,
+    }
+}
@@ -18,12 +18,21 @@ pub struct GlState {
     /// Whether GL_CULL_FACE is enabled
     pub enabled_cull_face: bool,
 
+    /// Bitmask of the GL_CLIP_DISTANCEi that are enabled
+    pub enabled_clip_distances: u32,
+
     /// Whether GL_DEBUG_OUTPUT is enabled. None means "unknown".
     pub enabled_debug_output: Option<bool>,
 
     /// Whether GL_DEBUG_OUTPUT_SYNCHRONOUS is enabled
     pub enabled_debug_output_synchronous: bool,
 
+    /// Whether GL_COLOR_LOGIC_OP is enabled
+    pub enabled_color_logic_op: bool,
+
+    /// Whether GL_DEPTH_CLAMP is enabled
+    pub enabled_depth_clamp: bool,
+
     /// Whether GL_DEPTH_TEST is enabled
     pub enabled_depth_test: bool,
 
@@ -39,6 +48,18 @@ pub struct GlState {
     /// Whether GL_POLYGON_OFFSET_FILL is enabled
     pub enabled_polygon_offset_fill: bool,
 
+    /// Whether GL_POLYGON_OFFSET_LINE is enabled
+    pub enabled_polygon_offset_line: bool,
+
+    /// Whether GL_POLYGON_OFFSET_POINT is enabled
+    pub enabled_polygon_offset_point: bool,
+
+    /// Whether GL_PRIMITIVE_RESTART is enabled
+    pub enabled_primitive_restart: bool,
+
+    /// Whether GL_PRIMITIVE_RESTART_FIXED_INDEX is enabled
+    pub enabled_primitive_restart_fixed_index: bool,
+
     /// Whether GL_RASTERIZER_DISCARD is enabled
     pub enabled_rasterizer_discard: bool,
 
@@ -48,6 +69,12 @@ pub struct GlState {
     /// Whether GL_SAMPLE_COVERAGE is enabled
     pub enabled_sample_coverage: bool,
 
+    /// Whether GL_SAMPLE_MASK is enabled
+    pub enabled_sample_mask: bool,
+
+    /// Whether GL_SAMPLE_SHADING is enabled
+    pub enabled_sample_shading: bool,
+
     /// Whether GL_SCISSOR_TEST is enabled
     pub enabled_scissor_test: bool,
 
@@ -83,6 +110,11 @@ pub struct GlState {
     pub color_mask: (gl::types::GLboolean, gl::types::GLboolean,
                      gl::types::GLboolean, gl::types::GLboolean),
 
+    /// The color mask of each draw buffer that has been modified with `glColorMaski` since the
+    /// latest call to `glColorMask`. `None` means that the draw buffer uses `color_mask`.
+    pub indexed_color_mask: SmallVec<[Option<(gl::types::GLboolean, gl::types::GLboolean,
+                                              gl::types::GLboolean, gl::types::GLboolean)>; 8]>,
+
     /// The latest buffer bound to `GL_ARRAY_BUFFER`.
     pub array_buffer_binding: gl::types::GLuint,
 
@@ -141,14 +173,49 @@ pub struct GlState {
     /// `None` means "unknown".
     pub default_framebuffer_read: Option<gl::types::GLenum>,
 
+    /// The latest values passed to `glDrawBuffer` with the default framebuffer.
+    /// `None` means "unknown".
+    ///
+    /// Starts as `GL_BACK` so that single-buffered contexts never receive a `glDrawBuffer`
+    /// call unless stereo rendering is used.
+    pub default_framebuffer_draw: Option<gl::types::GLenum>,
+
     /// The latest render buffer bound with `glBindRenderbuffer`.
     pub renderbuffer: gl::types::GLuint,
 
-    /// The latest values passed to `glBlendEquation`.
-    pub blend_equation: gl::types::GLenum,
+    /// The latest values passed to `glBlendEquationSeparate`, for the color and alpha
+    /// components.
+    pub blend_equation: (gl::types::GLenum, gl::types::GLenum),
 
-    /// The latest values passed to `glBlendFunc`.
-    pub blend_func: (gl::types::GLenum, gl::types::GLenum),
+    /// The latest values passed to `glBlendFuncSeparate`.
+    pub blend_func: (gl::types::GLenum, gl::types::GLenum, gl::types::GLenum, gl::types::GLenum),
+
+    /// The latest values passed to `glBlendColor`.
+    pub blend_color: (gl::types::GLclampf, gl::types::GLclampf,
+                      gl::types::GLclampf, gl::types::GLclampf),
+
+    /// The blending state of each draw buffer that has been modified with `glEnablei`,
+    /// `glBlendEquationSeparatei` or `glBlendFuncSeparatei`. `None` means that the draw buffer
+    /// uses `enabled_blend`, `blend_equation` and `blend_func`.
+    pub indexed_blending: SmallVec<[Option<IndexedBlendingState>; 8]>,
+
+    /// The latest value passed to `glPrimitiveRestartIndex`.
+    pub primitive_restart_index: gl::types::GLuint,
+
+    /// The latest values passed to `glPolygonOffset`.
+    pub polygon_offset: (gl::types::GLfloat, gl::types::GLfloat),
+
+    /// The latest value passed to `glSampleMaski` for the first mask word.
+    pub sample_mask: gl::types::GLbitfield,
+
+    /// The latest value passed to `glMinSampleShading`.
+    pub min_sample_shading: gl::types::GLfloat,
+
+    /// The latest value passed to `glProvokingVertex`.
+    pub provoking_vertex: gl::types::GLenum,
+
+    /// The latest value passed to `glLogicOp`.
+    pub logic_op: gl::types::GLenum,
 
     /// The latest value passed to `glDepthFunc`.
     pub depth_func: gl::types::GLenum,
@@ -326,6 +393,20 @@ pub struct IndexedBufferState {
     pub size: gl::types::GLsizeiptr,
 }
 
+/// Blending state of a draw buffer (`glEnablei`, `glBlendEquationSeparatei` and
+/// `glBlendFuncSeparatei`).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct IndexedBlendingState {
+    /// True if `GL_BLEND` is enabled.
+    pub enabled: bool,
+
+    /// Blending equations for the color and alpha components.
+    pub equation: (gl::types::GLenum, gl::types::GLenum),
+
+    /// Source and destination factors for the color and alpha components.
+    pub func: (gl::types::GLenum, gl::types::GLenum, gl::types::GLenum, gl::types::GLenum),
+}
+
 /// Builds the `GlState` corresponding to a newly-created OpenGL context.
 impl Default for GlState {
     fn default() -> GlState {
@@ -340,16 +421,25 @@ impl Default for GlState {
 
             enabled_blend: false,
             enabled_cull_face: false,
+            enabled_clip_distances: 0,
             enabled_debug_output: None,
             enabled_debug_output_synchronous: false,
+            enabled_color_logic_op: false,
+            enabled_depth_clamp: false,
             enabled_depth_test: false,
             enabled_dither: false,
             enabled_framebuffer_srgb: false,
             enabled_multisample: true,
             enabled_polygon_offset_fill: false,
+            enabled_polygon_offset_line: false,
+            enabled_polygon_offset_point: false,
+            enabled_primitive_restart: false,
+            enabled_primitive_restart_fixed_index: false,
             enabled_rasterizer_discard: false,
             enabled_sample_alpha_to_coverage: false,
             enabled_sample_coverage: false,
+            enabled_sample_mask: false,
+            enabled_sample_shading: false,
             enabled_scissor_test: false,
             enabled_stencil_test: false,
             enabled_line_smooth: false,
@@ -362,6 +452,7 @@ impl Default for GlState {
             clear_depth: 1.0,
             clear_stencil: 0,
             color_mask: (1, 1, 1, 1),
+            indexed_color_mask: SmallVec::new(),
             array_buffer_binding: 0,
             pixel_pack_buffer_binding: 0,
             pixel_unpack_buffer_binding: 0,
@@ -381,6 +472,7 @@ impl Default for GlState {
             read_framebuffer: 0,
             draw_framebuffer: 0,
             default_framebuffer_read: None,
+            default_framebuffer_draw: Some(gl::BACK),
             renderbuffer: 0,
             depth_func: gl::LESS,
             depth_mask: true,
@@ -391,8 +483,16 @@ impl Default for GlState {
             stencil_mask_back: 0xffffffff,
             stencil_op_front: (gl::KEEP, gl::KEEP, gl::KEEP),
             stencil_op_back: (gl::KEEP, gl::KEEP, gl::KEEP),
-            blend_equation: gl::FUNC_ADD,
-            blend_func: (gl::ONE, gl::ZERO),
+            blend_equation: (gl::FUNC_ADD, gl::FUNC_ADD),
+            blend_func: (gl::ONE, gl::ZERO, gl::ONE, gl::ZERO),
+            blend_color: (0.0, 0.0, 0.0, 0.0),
+            indexed_blending: SmallVec::new(),
+            polygon_offset: (0.0, 0.0),
+            primitive_restart_index: 0,
+            logic_op: gl::COPY,
+            sample_mask: 0xffffffff,
+            min_sample_shading: 0.0,
+            provoking_vertex: gl::LAST_VERTEX_CONVENTION,
             viewport: None,
             scissor: None,
             line_width: 1.0,
@@ -12,6 +12,8 @@ use gl;
 use std::rc::Rc;
 use std::mem;
 
+pub use context::DebugGroup;
+
 /// Severity of a debug message.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -32,7 +34,7 @@ pub enum Severity {
 }
 
 /// Source of a debug message.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum Source {
     /// Calls to the OpenGL API.
@@ -57,7 +59,7 @@ pub enum Source {
 }
 
 /// Type of a debug message.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum MessageType {
     /// An error, typically from the API
@@ -80,6 +82,15 @@ pub enum MessageType {
     Other = gl::DEBUG_TYPE_OTHER,
 }
 
+/// Function that is called when the backend emits a debug message.
+///
+/// The parameters are the source, the type and the severity of the message, followed by the
+/// identifier of the message and its content.
+///
+/// The function is called synchronously from within the OpenGL call that triggered the message.
+/// Therefore it must not call any glium function.
+pub type DebugCallback = Box<FnMut(Source, MessageType, Severity, u32, &str)>;
+
 /// Allows you to obtain the timestamp inside the OpenGL commands queue.
 ///
 /// When you call functions in glium, they are not instantly executed. Instead they are
@@ -78,8 +78,10 @@ use context::Context;
 use version::Version;
 use version::Api;
 
+use CapabilitiesSource;
 use ContextExt;
 use DrawError;
+use fbo::ValidatedAttachments;
 use Rect;
 use ToGlEnum;
 use vertex::TransformFeedbackSession;
@@ -168,6 +170,20 @@ pub enum BlendingFunction {
     },
 }
 
+impl BlendingFunction {
+    /// Returns true if this function uses dual-source blending.
+    fn is_dual_source(&self) -> bool {
+        match *self {
+            BlendingFunction::Addition { source, destination } |
+            BlendingFunction::Subtraction { source, destination } |
+            BlendingFunction::ReverseSubtraction { source, destination } => {
+                source.is_dual_source() || destination.is_dual_source()
+            },
+            _ => false,
+        }
+    }
+}
+
 /// Indicates which value to multiply each component with.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LinearBlendingFactor {
@@ -209,6 +225,58 @@ pub enum LinearBlendingFactor {
     /// Multiply the source or destination component by `1.0` minus the alpha value of the
     /// destination.
     OneMinusDestinationAlpha,
+
+    /// Multiply the source or destination component by the corresponding value of the
+    /// `constant_color` of the draw parameters.
+    ConstantColor,
+
+    /// Equivalent to `1 - ConstantColor`.
+    OneMinusConstantColor,
+
+    /// Multiply the source or destination component by the alpha value of the
+    /// `constant_color` of the draw parameters.
+    ConstantAlpha,
+
+    /// Equivalent to `1 - ConstantAlpha`.
+    OneMinusConstantAlpha,
+
+    /// Multiply the red, green and blue components by the minimum between the alpha value
+    /// of the source and `1.0` minus the alpha value of the destination. The alpha component
+    /// is multiplied by `1.0`.
+    ///
+    /// Older backends only accept this factor for the source.
+    SourceAlphaSaturate,
+
+    /// Multiply the source or destination component by its corresponding value in the
+    /// second output of the fragment shader.
+    ///
+    /// This is called dual-source blending. The second output is the fragment output whose
+    /// index is 1, for example `layout(location = 0, index = 1) out vec4 foo;`. Dual-source
+    /// blending can only be used when drawing to a single draw buffer.
+    SourceOneColor,
+
+    /// Equivalent to `1 - SourceOneColor`.
+    OneMinusSourceOneColor,
+
+    /// Multiply the source or destination component by the alpha value of the second output
+    /// of the fragment shader. See `SourceOneColor`.
+    SourceOneAlpha,
+
+    /// Equivalent to `1 - SourceOneAlpha`.
+    OneMinusSourceOneAlpha,
+}
+
+impl LinearBlendingFactor {
+    /// Returns true if this factor uses the second output of the fragment shader.
+    fn is_dual_source(&self) -> bool {
+        match *self {
+            LinearBlendingFactor::SourceOneColor => true,
+            LinearBlendingFactor::OneMinusSourceOneColor => true,
+            LinearBlendingFactor::SourceOneAlpha => true,
+            LinearBlendingFactor::OneMinusSourceOneAlpha => true,
+            _ => false,
+        }
+    }
 }
 
 impl ToGlEnum for LinearBlendingFactor {
@@ -224,10 +292,53 @@ impl ToGlEnum for LinearBlendingFactor {
             LinearBlendingFactor::OneMinusSourceAlpha => gl::ONE_MINUS_SRC_ALPHA,
             LinearBlendingFactor::DestinationAlpha => gl::DST_ALPHA,
             LinearBlendingFactor::OneMinusDestinationAlpha => gl::ONE_MINUS_DST_ALPHA,
+            LinearBlendingFactor::ConstantColor => gl::CONSTANT_COLOR,
+            LinearBlendingFactor::OneMinusConstantColor => gl::ONE_MINUS_CONSTANT_COLOR,
+            LinearBlendingFactor::ConstantAlpha => gl::CONSTANT_ALPHA,
+            LinearBlendingFactor::OneMinusConstantAlpha => gl::ONE_MINUS_CONSTANT_ALPHA,
+            LinearBlendingFactor::SourceAlphaSaturate => gl::SRC_ALPHA_SATURATE,
+            LinearBlendingFactor::SourceOneColor => gl::SRC1_COLOR,
+            LinearBlendingFactor::OneMinusSourceOneColor => gl::ONE_MINUS_SRC1_COLOR,
+            LinearBlendingFactor::SourceOneAlpha => gl::SRC1_ALPHA,
+            LinearBlendingFactor::OneMinusSourceOneAlpha => gl::ONE_MINUS_SRC1_ALPHA,
         }
     }
 }
 
+/// Identifies one of the draw buffers of the framebuffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawBuffer<'a> {
+    /// The draw buffer that the fragment output with this name is written to.
+    ///
+    /// Drawing returns `DrawError::FragmentOutputNotFound` if the program doesn't have an
+    /// output with this name.
+    Output(&'a str),
+
+    /// The draw buffer with this index. When drawing on a `MultiOutputFrameBuffer`, this is
+    /// the location of the fragment output that is written to it.
+    Index(u32),
+}
+
+/// Blending and color mask of a single draw buffer.
+///
+/// See the `draw_buffers` member of `DrawParameters`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawBufferParameters<'a> {
+    /// The draw buffer that these parameters apply to.
+    pub draw_buffer: DrawBuffer<'a>,
+
+    /// The function that the GPU will use to merge the existing pixel with the pixel that is
+    /// being written.
+    pub blending_function: BlendingFunction,
+
+    /// If set, the function to use for the alpha component instead of `blending_function`.
+    pub alpha_blending_function: Option<BlendingFunction>,
+
+    /// The red, green, blue and alpha components to write. See the `color_mask` member
+    /// of `DrawParameters`.
+    pub color_mask: (bool, bool, bool, bool),
+}
+
 /// Describes how triangles should be filtered before the fragment processing. Backface culling
 /// is purely an optimization. If you don't know what this does, just use `CullingDisabled`.
 ///
@@ -505,6 +616,136 @@ impl ToGlEnum for PolygonMode {
     }
 }
 
+/// Specifies a depth offset to apply to polygons.
+///
+/// The offset that is added to the depth value of each fragment is equal to
+/// `factor * slope + units * r`, where `slope` is the maximal depth slope of the polygon and `r`
+/// is the smallest value that is guaranteed to produce a resolvable difference in the depth
+/// buffer. This is usually used to avoid z-fighting when drawing decals, or shadow acne when
+/// rendering shadow maps.
+///
+/// The default value doesn't apply any offset.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PolygonOffset {
+    /// Scale factor of the depth slope of the polygon.
+    pub factor: f32,
+
+    /// Multiplied by the smallest resolvable difference of the depth buffer.
+    pub units: f32,
+
+    /// Whether the offset applies to polygons drawn with `PolygonMode::Point`.
+    ///
+    /// Ignored on OpenGL ES, which doesn't support polygon modes.
+    pub point: bool,
+
+    /// Whether the offset applies to polygons drawn with `PolygonMode::Line`.
+    ///
+    /// Ignored on OpenGL ES, which doesn't support polygon modes.
+    pub line: bool,
+
+    /// Whether the offset applies to polygons drawn with `PolygonMode::Fill`.
+    pub fill: bool,
+}
+
+impl Default for PolygonOffset {
+    fn default() -> PolygonOffset {
+        PolygonOffset {
+            factor: 0.0,
+            units: 0.0,
+            point: false,
+            line: false,
+            fill: false,
+        }
+    }
+}
+
+/// Specifies which vertex of each primitive provides the values of the `flat` outputs of the
+/// vertex or geometry shader.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProvokingVertex {
+    /// The first vertex of each primitive is used. This is the convention of Direct3D.
+    First,
+
+    /// The last vertex of each primitive is used. This is the default convention of OpenGL.
+    Last,
+}
+
+impl ToGlEnum for ProvokingVertex {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            ProvokingVertex::First => gl::FIRST_VERTEX_CONVENTION,
+            ProvokingVertex::Last => gl::LAST_VERTEX_CONVENTION,
+        }
+    }
+}
+
+/// Specifies how the color of the fragment and the color in the framebuffer are combined.
+///
+/// Contrary to blending, logic operations work on the bits of the colors. They only apply to
+/// normalized integer and integral attachments, and are ignored for floating-point and
+/// sRGB attachments.
+///
+/// In the descriptions below, `s` is the color of the fragment and `d` is the color in
+/// the framebuffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogicOp {
+    /// `0`
+    Clear,
+    /// `s & d`
+    And,
+    /// `s & !d`
+    AndReverse,
+    /// `s`
+    Copy,
+    /// `!s & d`
+    AndInverted,
+    /// `d`
+    Noop,
+    /// `s ^ d`
+    Xor,
+    /// `s | d`
+    Or,
+    /// `!(s | d)`
+    Nor,
+    /// `!(s ^ d)`
+    Equivalent,
+    /// `!d`
+    Invert,
+    /// `s | !d`
+    OrReverse,
+    /// `!s`
+    CopyInverted,
+    /// `!s | d`
+    OrInverted,
+    /// `!(s & d)`
+    Nand,
+    /// All the bits set to `1`.
+    Set,
+}
+
+impl ToGlEnum for LogicOp {
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            LogicOp::Clear => gl::CLEAR,
+            LogicOp::And => gl::AND,
+            LogicOp::AndReverse => gl::AND_REVERSE,
+            LogicOp::Copy => gl::COPY,
+            LogicOp::AndInverted => gl::AND_INVERTED,
+            LogicOp::Noop => gl::NOOP,
+            LogicOp::Xor => gl::XOR,
+            LogicOp::Or => gl::OR,
+            LogicOp::Nor => gl::NOR,
+            LogicOp::Equivalent => gl::EQUIV,
+            LogicOp::Invert => gl::INVERT,
+            LogicOp::OrReverse => gl::OR_REVERSE,
+            LogicOp::CopyInverted => gl::COPY_INVERTED,
+            LogicOp::OrInverted => gl::OR_INVERTED,
+            LogicOp::Nand => gl::NAND,
+            LogicOp::Set => gl::SET,
+        }
+    }
+}
+
 /// Specifies a hint for the smoothing.
 ///
 /// Note that this is just a hint and the driver may disregard it.
@@ -575,6 +816,16 @@ pub struct DrawParameters<'a> {
     /// It is possible for the "near" value to be greater than the "far" value.
     pub depth_range: (f32, f32),
 
+    /// If `true`, the primitives are not clipped against the near and far planes and their
+    /// depth values are clamped to the depth range instead. The default value is `false`.
+    ///
+    /// This is useful for example for shadow volumes, or to render objects that are very far
+    /// away.
+    ///
+    /// If `true`, drawing returns `DepthClampNotSupported` if the backend doesn't support
+    /// OpenGL 3.2 or `GL_ARB_depth_clamp`.
+    pub depth_clamp: bool,
+
     /// A comparaison against the existing value in the stencil buffer.
     ///
     /// Only relevant for faces that are clockwise on the target surface. Other faces, points and
@@ -683,18 +934,57 @@ pub struct DrawParameters<'a> {
     /// being written.
     ///
     /// `None` means "don't care" (usually when you know that the alpha is always 1).
+    ///
+    /// Unless `alpha_blending_function` is set, this function applies to all four components.
     pub blending_function: Option<BlendingFunction>,
 
+    /// If set, the function that the GPU will use to merge the alpha component instead of
+    /// `blending_function`, which then only applies to the red, green and blue components.
+    ///
+    /// This is ignored if `blending_function` is `None`. The default value is `None`.
+    ///
+    /// For example premultiplied-alpha compositing usually uses
+    /// `Addition { source: One, destination: OneMinusSourceAlpha }` for the color components
+    /// and a different function for the alpha component.
+    pub alpha_blending_function: Option<BlendingFunction>,
+
+    /// The color used by the `ConstantColor`, `OneMinusConstantColor`, `ConstantAlpha` and
+    /// `OneMinusConstantAlpha` blending factors.
+    ///
+    /// The parameters are in order: red, green, blue, alpha. The default value
+    /// is `(0.0, 0.0, 0.0, 0.0)`.
+    pub constant_color: (f32, f32, f32, f32),
+
     /// Allows you to disable some color components.
     ///
-    /// This affects all attachments to the framebuffer. It's at the same level as the
-    /// blending function.
+    /// This affects all attachments to the framebuffer, except those that are in
+    /// `draw_buffers`. It's at the same level as the blending function.
     ///
     /// The parameters are in order: red, green, blue, alpha. `true` means that the given
     /// component will be written, `false` means that it will be ignored. The default value
     /// is `(true, true, true, true)`.
     pub color_mask: (bool, bool, bool, bool),
 
+    /// Blending functions and color masks of individual draw buffers.
+    ///
+    /// The draw buffers that are in this list use these values instead of `blending_function`,
+    /// `alpha_blending_function` and `color_mask`. This allows you for example to blend one
+    /// attachment of a `MultiOutputFrameBuffer` while overwriting the others. The default value
+    /// is an empty list.
+    ///
+    /// If the list is not empty, drawing returns `DrawBufferParametersNotSupported` if the
+    /// backend doesn't support this feature. See `is_draw_buffer_parameters_supported`.
+    pub draw_buffers: &'a [DrawBufferParameters<'a>],
+
+    /// If set, the logic operation to use to combine the color of the fragment with the color
+    /// in the framebuffer. The default value is `None`.
+    ///
+    /// When a logic operation is used, blending is disabled for all draw buffers.
+    ///
+    /// If set, drawing returns `LogicOperationNotSupported` on OpenGL ES, which doesn't support
+    /// logic operations.
+    pub logic_operation: Option<LogicOp>,
+
     /// Width in pixels of the lines to draw when drawing lines.
     ///
     /// `None` means "don't care". Use this when you don't draw lines.
@@ -718,6 +1008,18 @@ pub struct DrawParameters<'a> {
     /// See the documentation of `PolygonMode` for more infos.
     pub polygon_mode: PolygonMode,
 
+    /// Which vertex of each primitive provides the values of the `flat` outputs. The default
+    /// value is `Last`.
+    ///
+    /// If `First`, drawing returns `ProvokingVertexNotSupported` if the backend doesn't support
+    /// OpenGL 3.2 or `GL_ARB_provoking_vertex`.
+    pub provoking_vertex: ProvokingVertex,
+
+    /// The depth offset to apply to polygons. The default value doesn't apply any offset.
+    ///
+    /// See the documentation of `PolygonOffset` for more infos.
+    pub polygon_offset: PolygonOffset,
+
     /// Whether multisample antialiasing (MSAA) should be used. Default value is `true`.
     ///
     /// Note that you will need to set the appropriate option when creating the window.
@@ -725,6 +1027,30 @@ pub struct DrawParameters<'a> {
     /// creating the window.
     pub multisampling: bool,
 
+    /// If `true`, the alpha value of the first output of the fragment shader is used to
+    /// determine which samples of the pixel are covered. The default value is `false`.
+    ///
+    /// This has no effect if the surface is not multisampled or if `multisampling` is `false`.
+    pub alpha_to_coverage: bool,
+
+    /// If set, only the samples whose bit is set in this mask are written. The default value
+    /// is `None`.
+    ///
+    /// Drawing returns `SampleMaskNotSupported` if the backend doesn't support this feature
+    /// (see `is_sample_mask_supported`), and `SampleMaskOutOfBounds` if a bit greater than or
+    /// equal to the number of samples of the surface is set. A surface that is not
+    /// multisampled is considered to have one sample.
+    pub sample_mask: Option<u32>,
+
+    /// If set, the fragment shader is executed for at least this fraction of the samples of
+    /// each pixel instead of once per pixel. The default value is `None`.
+    ///
+    /// `1.0` means that the fragment shader is executed for each sample. Drawing returns
+    /// `InvalidMinSampleShading` if the value is not between `0.0` and `1.0`, and
+    /// `SampleShadingNotSupported` if the backend doesn't support this feature (see
+    /// `is_sample_shading_supported`).
+    pub min_sample_shading: Option<f32>,
+
     /// Whether dithering is activated. Default value is `true`.
     ///
     /// Dithering will smoothen the transition between colors in your color buffer.
@@ -747,6 +1073,27 @@ pub struct DrawParameters<'a> {
     /// not the scissor box.
     pub scissor: Option<Rect>,
 
+    /// List of viewports to use when drawing. The default value is an empty list.
+    ///
+    /// If the list is not empty, `viewport` is ignored and each primitive is mapped to the
+    /// viewport whose index is the value of `gl_ViewportIndex` written by the geometry shader,
+    /// or to the first viewport if the shader doesn't write it. This allows you to render to
+    /// multiple views of a surface with a single draw command.
+    ///
+    /// Drawing returns `ViewportArrayNotSupported` if the backend doesn't support this feature
+    /// (see `is_viewport_array_supported`), `TooManyViewports` if the list contains more
+    /// than `Capabilities::max_viewports` elements, and `ViewportTooLarge` if one of the
+    /// viewports is larger than `Capabilities::max_viewport_dims`.
+    pub viewports: &'a [Rect],
+
+    /// List of scissor boxes to use when drawing, one for each element of `viewports`. The
+    /// default value is an empty list.
+    ///
+    /// If the list is not empty, `scissor` is ignored and the scissor box whose index is the
+    /// value of `gl_ViewportIndex` is used. Drawing returns `ScissorArrayLengthMismatch` if
+    /// the list doesn't have the same length as `viewports`.
+    pub scissors: &'a [Rect],
+
     /// If `false`, the pipeline will stop after the primitives generation stage. The default
     /// value is `true`.
     ///
@@ -781,6 +1128,27 @@ pub struct DrawParameters<'a> {
     /// If set, then the generated primitives will be written back to a buffer.
     pub transform_feedback: Option<&'a TransformFeedbackSession<'a>>,
 
+    /// Specifies which clip distances are enabled. The default value is `0`.
+    ///
+    /// If the bit `i` is set, then the primitives are clipped where the value of
+    /// `gl_ClipDistance[i]` written by the vertex, tessellation or geometry shader is
+    /// negative. This can be used to implement user clip planes.
+    ///
+    /// Drawing returns `ClipPlaneIndexOutOfBounds` if a bit greater than or equal to
+    /// `Capabilities::max_clip_distances` is set.
+    pub clip_planes_bitmask: u32,
+
+    /// If `true`, the maximum value of the index type (`255` for `u8`, `65535` for `u16` and
+    /// `4294967295` for `u32`) starts a new primitive when it is encountered in the indices.
+    /// The default value is `false`.
+    ///
+    /// This allows you to draw multiple triangle strips, triangle fans or line strips with a
+    /// single index buffer. It has no effect if you don't use indices.
+    ///
+    /// If `true`, drawing returns `PrimitiveRestartNotSupported` if the backend doesn't support
+    /// this feature. See `is_primitive_restart_index_supported`.
+    pub primitive_restart_index: bool,
+
     /// If set, then the generated primitives will be smoothed.
     /// 
     /// Note that blending needs to be enabled for this to work.
@@ -829,6 +1197,7 @@ impl<'a> Default for DrawParameters<'a> {
             depth_test: DepthTest::Overwrite,
             depth_write: false,
             depth_range: (0.0, 1.0),
+            depth_clamp: false,
             stencil_test_clockwise: StencilTest::AlwaysPass,
             stencil_reference_value_clockwise: 0,
             stencil_write_mask_clockwise: 0xffffffff,
@@ -842,15 +1211,26 @@ impl<'a> Default for DrawParameters<'a> {
             stencil_pass_depth_fail_operation_counter_clockwise: StencilOperation::Keep,
             stencil_depth_pass_operation_counter_clockwise: StencilOperation::Keep,
             blending_function: Some(BlendingFunction::AlwaysReplace),
+            alpha_blending_function: None,
+            constant_color: (0.0, 0.0, 0.0, 0.0),
             color_mask: (true, true, true, true),
+            draw_buffers: &[],
+            logic_operation: None,
             line_width: None,
             point_size: None,
             backface_culling: BackfaceCullingMode::CullingDisabled,
             polygon_mode: PolygonMode::Fill,
+            provoking_vertex: ProvokingVertex::Last,
+            polygon_offset: Default::default(),
             multisampling: true,
+            alpha_to_coverage: false,
+            sample_mask: None,
+            min_sample_shading: None,
             dithering: true,
             viewport: None,
             scissor: None,
+            viewports: &[],
+            scissors: &[],
             draw_primitives: true,
             samples_passed_query: None,
             time_elapsed_query: None,
@@ -858,6 +1238,8 @@ impl<'a> Default for DrawParameters<'a> {
             transform_feedback_primitives_written_query: None,
             condition: None,
             transform_feedback: None,
+            clip_planes_bitmask: 0,
+            primitive_restart_index: false,
             smooth: None,
         }
     }
@@ -900,6 +1282,15 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets that depth values must be clamped instead of clipping the primitives against the
+    /// near and far planes.
+    ///
+    /// Depth clamping is disabled by default.
+    pub fn with_depth_clamp(mut self) -> DrawParametersBuilder<'a> {
+        self.params.depth_clamp = true;
+        self
+    }
+
     /// Sets the function that the GPU will use to merge the existing pixel with the pixel that is
     /// being written.
     pub fn with_blending_function(mut self, blending: BlendingFunction)
@@ -909,6 +1300,43 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets the function that the GPU will use for the alpha component, while the function
+    /// passed to `with_blending_function` is only used for the red, green and blue components.
+    pub fn with_alpha_blending_function(mut self, blending: BlendingFunction)
+                                        -> DrawParametersBuilder<'a>
+    {
+        self.params.alpha_blending_function = Some(blending);
+        self
+    }
+
+    /// Sets the logic operation to use instead of blending.
+    ///
+    /// By default, no logic operation is used.
+    pub fn with_logic_operation(mut self, operation: LogicOp) -> DrawParametersBuilder<'a> {
+        self.params.logic_operation = Some(operation);
+        self
+    }
+
+    /// Sets the blending functions and color masks of individual draw buffers.
+    ///
+    /// By default, all draw buffers use the same values.
+    pub fn with_draw_buffer_parameters(mut self, parameters: &'a [DrawBufferParameters<'a>])
+                                       -> DrawParametersBuilder<'a>
+    {
+        self.params.draw_buffers = parameters;
+        self
+    }
+
+    /// Sets the color used by the constant blending factors.
+    ///
+    /// By default, the constant color is `(0.0, 0.0, 0.0, 0.0)`.
+    pub fn with_constant_color(mut self, color: (f32, f32, f32, f32))
+                               -> DrawParametersBuilder<'a>
+    {
+        self.params.constant_color = color;
+        self
+    }
+
     /// Sets whether to cull faces, and which ones.
     pub fn with_backface_culling(mut self, culling: BackfaceCullingMode)
                                  -> DrawParametersBuilder<'a>
@@ -917,6 +1345,38 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets which vertex of each primitive provides the values of the `flat` outputs.
+    ///
+    /// By default, the last vertex is used.
+    pub fn with_provoking_vertex(mut self, vertex: ProvokingVertex) -> DrawParametersBuilder<'a> {
+        self.params.provoking_vertex = vertex;
+        self
+    }
+
+    /// Sets the depth offset to apply to polygons.
+    ///
+    /// By default, no offset is applied.
+    pub fn with_polygon_offset(mut self, offset: PolygonOffset) -> DrawParametersBuilder<'a> {
+        self.params.polygon_offset = offset;
+        self
+    }
+
+    /// Sets which clip distances are enabled.
+    ///
+    /// By default, no clip distance is enabled.
+    pub fn with_clip_planes_bitmask(mut self, bitmask: u32) -> DrawParametersBuilder<'a> {
+        self.params.clip_planes_bitmask = bitmask;
+        self
+    }
+
+    /// Sets that the maximum value of the index type must start a new primitive.
+    ///
+    /// Primitive restart is disabled by default.
+    pub fn with_primitive_restart_index(mut self) -> DrawParametersBuilder<'a> {
+        self.params.primitive_restart_index = true;
+        self
+    }
+
     /// Sets the viewport to use.
     ///
     /// By default, the whole surface is used.
@@ -933,6 +1393,23 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets the list of viewports to use. The geometry shader chooses the viewport of each
+    /// primitive by writing to `gl_ViewportIndex`.
+    ///
+    /// By default, the single viewport of `with_viewport` is used.
+    pub fn with_viewports(mut self, viewports: &'a [Rect]) -> DrawParametersBuilder<'a> {
+        self.params.viewports = viewports;
+        self
+    }
+
+    /// Sets the list of scissor boxes to use, one for each viewport.
+    ///
+    /// By default, the single scissor box of `with_scissor` is used.
+    pub fn with_scissors(mut self, scissors: &'a [Rect]) -> DrawParametersBuilder<'a> {
+        self.params.scissors = scissors;
+        self
+    }
+
     /// Sets that the output of the rasterized must be discarded.
     ///
     /// It is not discarded by default.
@@ -959,6 +1436,32 @@ impl<'a> DrawParametersBuilder<'a> {
         self
     }
 
+    /// Sets that the alpha value of the fragment shader's output must be used to determine
+    /// the coverage of the samples.
+    ///
+    /// Alpha-to-coverage is disabled by default.
+    pub fn with_alpha_to_coverage(mut self) -> DrawParametersBuilder<'a> {
+        self.params.alpha_to_coverage = true;
+        self
+    }
+
+    /// Sets the mask of the samples that can be written.
+    ///
+    /// By default, all the samples can be written.
+    pub fn with_sample_mask(mut self, mask: u32) -> DrawParametersBuilder<'a> {
+        self.params.sample_mask = Some(mask);
+        self
+    }
+
+    /// Sets the minimum fraction of the samples of each pixel for which the fragment shader
+    /// must be executed.
+    ///
+    /// By default, the fragment shader is executed once per pixel.
+    pub fn with_min_sample_shading(mut self, value: f32) -> DrawParametersBuilder<'a> {
+        self.params.min_sample_shading = Some(value);
+        self
+    }
+
     /// Sets that dithering must not be used.
     ///
     /// Dithering is active by default.
@@ -1031,8 +1534,51 @@ impl<'a> DerefMut for DrawParametersBuilder<'a> {
     }
 }
 
+/// Returns true if the backend supports the `draw_buffers` member of `DrawParameters`.
+pub fn is_draw_buffer_parameters_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    (context.get_extensions().gl_arb_draw_buffers_blend &&
+     (context.get_version() >= &Version(Api::Gl, 3, 0) ||
+      context.get_extensions().gl_ext_draw_buffers2))
+}
+
+/// Returns true if the backend supports the `primitive_restart_index` member of
+/// `DrawParameters`.
+pub fn is_primitive_restart_index_supported<C>(context: &C) -> bool
+                                               where C: CapabilitiesSource
+{
+    context.get_version() >= &Version(Api::Gl, 3, 1) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 0) ||
+    context.get_extensions().gl_arb_es3_compatibility
+}
+
+/// Returns true if the backend supports the `sample_mask` member of `DrawParameters`.
+pub fn is_sample_mask_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 3, 2) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 1) ||
+    context.get_extensions().gl_arb_texture_multisample
+}
+
+/// Returns true if the backend supports the `min_sample_shading` member of `DrawParameters`.
+pub fn is_sample_shading_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_extensions().gl_arb_sample_shading
+}
+
+/// Returns true if the backend supports the `viewports` and `scissors` members of
+/// `DrawParameters`.
+pub fn is_viewport_array_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 4, 1) ||
+    context.get_extensions().gl_arb_viewport_array
+}
+
 /// Checks parameters and panics if something is wrong.
-pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawError> {
+///
+/// `framebuffer` is the framebuffer that is going to be drawn on, or `None` for the
+/// default framebuffer.
+pub fn validate(context: &Context, params: &DrawParameters,
+                framebuffer: Option<&ValidatedAttachments>) -> Result<(), DrawError>
+{
     if params.depth_range.0 < 0.0 || params.depth_range.0 > 1.0 ||
        params.depth_range.1 < 0.0 || params.depth_range.1 > 1.0
     {
@@ -1045,5 +1591,116 @@ pub fn validate(context: &Context, params: &DrawParameters) -> Result<(), DrawEr
         return Err(DrawError::TransformFeedbackNotSupported);
     }
 
+    if params.depth_clamp && !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !context.get_extensions().gl_arb_depth_clamp
+    {
+        return Err(DrawError::DepthClampNotSupported);
+    }
+
+    if params.logic_operation.is_some() && context.get_version().0 == Api::GlEs {
+        return Err(DrawError::LogicOperationNotSupported);
+    }
+
+    if params.provoking_vertex == ProvokingVertex::First &&
+       !(context.get_version() >= &Version(Api::Gl, 3, 2)) &&
+       !context.get_extensions().gl_arb_provoking_vertex
+    {
+        return Err(DrawError::ProvokingVertexNotSupported);
+    }
+
+    let max_clip_distances = context.capabilities().max_clip_distances as u32;
+    if max_clip_distances < 32 && (params.clip_planes_bitmask >> max_clip_distances) != 0 {
+        return Err(DrawError::ClipPlaneIndexOutOfBounds);
+    }
+
+    if params.primitive_restart_index && !is_primitive_restart_index_supported(context) {
+        return Err(DrawError::PrimitiveRestartNotSupported);
+    }
+
+    if !params.draw_buffers.is_empty() {
+        if !is_draw_buffer_parameters_supported(context) {
+            return Err(DrawError::DrawBufferParametersNotSupported);
+        }
+
+        for draw_buffer in params.draw_buffers.iter() {
+            if let DrawBuffer::Index(index) = draw_buffer.draw_buffer {
+                if index >= context.capabilities().max_draw_buffers as u32 {
+                    return Err(DrawError::DrawBufferIndexOutOfRange);
+                }
+            }
+        }
+    }
+
+    let dual_source = match params.blending_function {
+        Some(f) => f.is_dual_source() ||
+                   params.alpha_blending_function.map_or(false, |f| f.is_dual_source()),
+        None => false,
+    } || params.draw_buffers.iter().any(|b| {
+        b.blending_function.is_dual_source() ||
+        b.alpha_blending_function.map_or(false, |f| f.is_dual_source())
+    });
+
+    if dual_source {
+        if !(context.get_version() >= &Version(Api::Gl, 3, 3)) &&
+           !context.get_extensions().gl_arb_blend_func_extended
+        {
+            return Err(DrawError::DualSourceBlendingNotSupported);
+        }
+
+        // the default framebuffer only has one draw buffer
+        let draw_buffers = framebuffer.map(|f| f.get_color_attachments_count()).unwrap_or(1);
+        if draw_buffers > 1 {
+            return Err(DrawError::DualSourceBlendingMultipleDrawBuffers);
+        }
+    }
+
+    if let Some(mask) = params.sample_mask {
+        if !is_sample_mask_supported(context) {
+            return Err(DrawError::SampleMaskNotSupported);
+        }
+
+        // a surface that isn't multisampled behaves as if it had one sample
+        let samples = match framebuffer {
+            Some(framebuffer) => framebuffer.get_samples(),
+            None => context.capabilities().samples.map(|s| s as u32),
+        }.unwrap_or(1);
+
+        if samples < 32 && (mask >> samples) != 0 {
+            return Err(DrawError::SampleMaskOutOfBounds);
+        }
+    }
+
+    if !params.viewports.is_empty() || !params.scissors.is_empty() {
+        if !is_viewport_array_supported(context) {
+            return Err(DrawError::ViewportArrayNotSupported);
+        }
+
+        let max_viewports = context.capabilities().max_viewports as usize;
+        if params.viewports.len() > max_viewports || params.scissors.len() > max_viewports {
+            return Err(DrawError::TooManyViewports);
+        }
+
+        if !params.scissors.is_empty() && params.scissors.len() != params.viewports.len() {
+            return Err(DrawError::ScissorArrayLengthMismatch);
+        }
+
+        let max_dims = context.capabilities().max_viewport_dims;
+        for viewport in params.viewports.iter() {
+            if viewport.width > max_dims.0 as u32 || viewport.height > max_dims.1 as u32 {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+    }
+
+    if let Some(value) = params.min_sample_shading {
+        if !(value >= 0.0 && value <= 1.0) {
+            return Err(DrawError::InvalidMinSampleShading);
+        }
+
+        if !is_sample_shading_supported(context) {
+            return Err(DrawError::SampleShadingNotSupported);
+        }
+    }
+
     Ok(())
 }
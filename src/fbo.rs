@@ -57,14 +57,18 @@ use std::marker::PhantomData;
 
 use smallvec::SmallVec;
 
+use CapabilitiesSource;
 use GlObject;
+use RenderBufferExt;
 use TextureExt;
 
 use texture::TextureAny;
 use texture::Dimensions;
+use texture::{InternalFormat, InternalFormatType};
 use framebuffer::RenderBufferAny;
 
 use gl;
+use context;
 use context::CommandContext;
 use version::Version;
 use version::Api;
@@ -110,7 +114,26 @@ pub enum FramebufferDepthStencilAttachments<'a> {
 
 /// Represents the attachments to use for an OpenGL framebuffer.
 #[derive(Clone)]
-pub struct FramebufferAttachments<'a> {
+pub enum FramebufferAttachments<'a> {
+    /// The framebuffer has at least one attachment.
+    Regular(FramebufferSpecificAttachments<'a>),
+
+    /// The framebuffer doesn't have any attachment. Its dimensions are specified manually.
+    Empty {
+        /// Width of the framebuffer.
+        width: u32,
+        /// Height of the framebuffer.
+        height: u32,
+        /// Number of layers of the framebuffer. `None` if it is not layered.
+        layers: Option<u32>,
+        /// Number of samples of the framebuffer. `None` if it is not multisampled.
+        samples: Option<u32>,
+    },
+}
+
+/// Describes the attachments of a framebuffer that has at least one attachment.
+#[derive(Clone)]
+pub struct FramebufferSpecificAttachments<'a> {
     /// List of color attachments. The first parameter of the tuple is the index, and the
     /// second element is the attachment.
     pub colors: SmallVec<[(u32, Attachment<'a>); 5]>,
@@ -122,18 +145,126 @@ pub struct FramebufferAttachments<'a> {
 impl<'a> FramebufferAttachments<'a> {
     /// After building a `FramebufferAttachments` struct, you must use this function
     /// to "compile" the attachments and make sure that they are valid together.
-    pub fn validate(self) -> Result<ValidatedAttachments<'a>, ValidationError> {
+    pub fn validate<C>(self, context: &C) -> Result<ValidatedAttachments<'a>, ValidationError>
+                       where C: CapabilitiesSource
+    {
+        match self {
+            FramebufferAttachments::Regular(attachments) => {
+                FramebufferAttachments::validate_regular(attachments, context)
+            },
+            FramebufferAttachments::Empty { width, height, layers, samples } => {
+                FramebufferAttachments::validate_empty(context, width, height, layers, samples)
+            },
+        }
+    }
+
+    fn validate_empty<C>(context: &C, width: u32, height: u32, layers: Option<u32>,
+                         samples: Option<u32>)
+                         -> Result<ValidatedAttachments<'a>, ValidationError>
+                         where C: CapabilitiesSource
+    {
+        let version = context.get_version();
+        let extensions = context.get_extensions();
+        let capabilities = context.get_capabilities();
+
+        if !(version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 1) ||
+             extensions.gl_arb_framebuffer_no_attachments)
+        {
+            return Err(ValidationError::EmptyFramebufferObjectsNotSupported);
+        }
+
+        let max_width = capabilities.max_framebuffer_width.unwrap_or(0) as u32;
+        let max_height = capabilities.max_framebuffer_height.unwrap_or(0) as u32;
+
+        if width == 0 || height == 0 || width > max_width || height > max_height {
+            return Err(ValidationError::EmptyFramebufferUnsupportedDimensions);
+        }
+
+        if let Some(layers) = layers {
+            let max_layers = capabilities.max_framebuffer_layers.unwrap_or(0) as u32;
+            if layers == 0 || layers > max_layers {
+                return Err(ValidationError::EmptyFramebufferUnsupportedLayers);
+            }
+        }
+
+        if let Some(samples) = samples {
+            let max_samples = capabilities.max_framebuffer_samples.unwrap_or(0) as u32;
+            if samples == 0 || samples > max_samples {
+                return Err(ValidationError::EmptyFramebufferUnsupportedSamples);
+            }
+        }
+
+        Ok(ValidatedAttachments {
+            raw: RawAttachments {
+                color: Vec::new(),
+                depth: None,
+                stencil: None,
+                depth_stencil: None,
+                default_width: Some(width),
+                default_height: Some(height),
+                default_layers: layers,
+                default_samples: samples,
+            },
+            marker: PhantomData,
+            dimensions: (width, height),
+            samples: samples,
+            depth_buffer_bits: None,
+            stencil_buffer_bits: None,
+            depth_buffer_format: None,
+            stencil_buffer_format: None,
+        })
+    }
+
+    fn validate_regular<C>(attachments: FramebufferSpecificAttachments<'a>, context: &C)
+                           -> Result<ValidatedAttachments<'a>, ValidationError>
+                           where C: CapabilitiesSource
+    {
+        // the formats of the depth and stencil buffers, which must match when blitting
+        let (depth_format, stencil_format) = match attachments.depth_stencil {
+            FramebufferDepthStencilAttachments::None => (None, None),
+            FramebufferDepthStencilAttachments::DepthAttachment(ref a) => {
+                (Some(get_depth_stencil_format(a, false, true)), None)
+            },
+            FramebufferDepthStencilAttachments::StencilAttachment(ref a) => {
+                (None, Some(get_depth_stencil_format(a, false, false)))
+            },
+            FramebufferDepthStencilAttachments::DepthAndStencilAttachments(ref d, ref s) => {
+                (Some(get_depth_stencil_format(d, false, true)),
+                 Some(get_depth_stencil_format(s, false, false)))
+            },
+            FramebufferDepthStencilAttachments::DepthStencilAttachment(ref a) => {
+                (Some(get_depth_stencil_format(a, true, true)),
+                 Some(get_depth_stencil_format(a, true, false)))
+            },
+        };
+
         // turning the attachments into raw attachments
-        let (raw_attachments, dimensions, depth_bits, stencil_bits) = {
+        let (raw_attachments, dimensions, samples, depth_bits, stencil_bits) = {
             fn handle_attachment(a: &Attachment, dim: &mut Option<(u32, u32)>,
-                                 num_bits: Option<&mut Option<u16>>)
+                                 samples: &mut Vec<u32>,
+                                 depth_bits: Option<&mut Option<u16>>,
+                                 stencil_bits: Option<&mut Option<u16>>)
                                  -> RawAttachment
             {
                 match a {
                     &Attachment::Texture { ref texture, level, layer } => {
-                        if let Some(num_bits) = num_bits {
-                            *num_bits = Some(texture.get_internal_format()
-                                               .map(|f| f.get_total_bits()).ok().unwrap_or(24) as u16);     // TODO: how to handle this?
+                        samples.push(match texture.get_texture_type() {
+                            Dimensions::Texture2dMultisample { samples, .. } => samples,
+                            Dimensions::Texture2dMultisampleArray { samples, .. } => samples,
+                            _ => 0
+                        });
+
+                        match (depth_bits, stencil_bits) {
+                            (Some(depth_bits), Some(stencil_bits)) => {
+                                // FIXME: depth-stencil textures are always D24S8 for the moment
+                                *depth_bits = Some(24);
+                                *stencil_bits = Some(8);
+                            },
+                            (Some(num_bits), None) | (None, Some(num_bits)) => {
+                                *num_bits = Some(texture.get_internal_format()
+                                                   .map(|f| f.get_total_bits()).ok().unwrap_or(24) as u16);     // TODO: how to handle this?
+                            },
+                            (None, None) => ()
                         }
 
                         match dim {
@@ -149,6 +280,8 @@ impl<'a> FramebufferAttachments<'a> {
                             (l, Dimensions::Texture2dArray { .. }) => l,
                             (l, Dimensions::Texture2dMultisampleArray { .. }) => l,
                             (l, Dimensions::Texture3d { .. }) => l,
+                            (l, Dimensions::Cubemap { .. }) => l,
+                            (l, Dimensions::CubemapArray { .. }) => l,
                             (Some(l), _) if l == 0 => None,
                             (Some(l), _) => panic!(),
                             (None, _) => None,
@@ -162,8 +295,14 @@ impl<'a> FramebufferAttachments<'a> {
                         }
                     },
                     &Attachment::RenderBuffer(ref buffer) => {
-                        if let Some(num_bits) = num_bits {
-                            *num_bits = Some(24);    // FIXME: totally random
+                        samples.push(buffer.get_samples().unwrap_or(0));
+
+                        let (depth, stencil) = buffer.get_depth_stencil_bits();
+                        if let Some(depth_bits) = depth_bits {
+                            *depth_bits = Some(depth);
+                        }
+                        if let Some(stencil_bits) = stencil_bits {
+                            *stencil_bits = Some(stencil);
                         }
 
                         match dim {
@@ -180,41 +319,47 @@ impl<'a> FramebufferAttachments<'a> {
                 }
             }
 
-            // TODO: check number of samples
-            // TODO: check layering
-
             // the dimensions of the framebuffer object
             let mut dimensions = None;
+            // number of samples of each attachment
+            let mut samples = Vec::new();
             // number of depth bits
             let mut depth_bits = None;
             // number of stencil bits
             let mut stencil_bits = None;
 
             let mut raw_attachments = RawAttachments {
-                color: Vec::with_capacity(self.colors.len()),
+                color: Vec::with_capacity(attachments.colors.len()),
                 depth: None,
                 stencil: None,
                 depth_stencil: None,
+                default_width: None,
+                default_height: None,
+                default_layers: None,
+                default_samples: None,
             };
 
-            for &(index, ref a) in self.colors.iter() {
-                raw_attachments.color.push((index, handle_attachment(a, &mut dimensions, None)));
+            for &(index, ref a) in attachments.colors.iter() {
+                raw_attachments.color.push((index, handle_attachment(a, &mut dimensions, &mut samples, None, None)));
             }
 
-            match self.depth_stencil {
+            match attachments.depth_stencil {
                 FramebufferDepthStencilAttachments::None => (),
                 FramebufferDepthStencilAttachments::DepthAttachment(ref a) => {
-                    raw_attachments.depth = Some(handle_attachment(a, &mut dimensions, Some(&mut depth_bits)));
+                    raw_attachments.depth = Some(handle_attachment(a, &mut dimensions, &mut samples, Some(&mut depth_bits), None));
                 },
                 FramebufferDepthStencilAttachments::StencilAttachment(ref a) => {
-                    raw_attachments.stencil = Some(handle_attachment(a, &mut dimensions, Some(&mut stencil_bits)));
+                    raw_attachments.stencil = Some(handle_attachment(a, &mut dimensions, &mut samples, None, Some(&mut stencil_bits)));
                 },
                 FramebufferDepthStencilAttachments::DepthAndStencilAttachments(ref d, ref s) => {
-                    raw_attachments.depth = Some(handle_attachment(d, &mut dimensions, Some(&mut depth_bits)));
-                    raw_attachments.stencil = Some(handle_attachment(s, &mut dimensions, Some(&mut stencil_bits)));
+                    raw_attachments.depth = Some(handle_attachment(d, &mut dimensions, &mut samples, Some(&mut depth_bits), None));
+                    raw_attachments.stencil = Some(handle_attachment(s, &mut dimensions, &mut samples, None, Some(&mut stencil_bits)));
                 },
                 FramebufferDepthStencilAttachments::DepthStencilAttachment(ref a) => {
-                    raw_attachments.depth_stencil = Some(handle_attachment(a, &mut dimensions, None));      // FIXME: bit counts
+                    raw_attachments.depth_stencil = Some(handle_attachment(a, &mut dimensions,
+                                                                           &mut samples,
+                                                                           Some(&mut depth_bits),
+                                                                           Some(&mut stencil_bits)));
                 },
             }
 
@@ -223,15 +368,58 @@ impl<'a> FramebufferAttachments<'a> {
                 None => return Err(ValidationError::EmptyFramebufferObjectsNotSupported)
             };
 
-            (raw_attachments, dimensions, depth_bits, stencil_bits)
+            // all the attachments must have the same number of samples
+            if samples.iter().any(|&s| s != samples[0]) {
+                return Err(ValidationError::SamplesCountMismatch);
+            }
+
+            let samples = match samples[0] {
+                0 => None,
+                s => Some(s),
+            };
+
+            (raw_attachments, dimensions, samples, depth_bits, stencil_bits)
         };
 
+        // if one attachment is layered, then all attachments must be layered
+        let layered = {
+            let all = raw_attachments.color.iter().map(|&(_, ref a)| a)
+                                     .chain(raw_attachments.depth.iter())
+                                     .chain(raw_attachments.stencil.iter())
+                                     .chain(raw_attachments.depth_stencil.iter())
+                                     .collect::<Vec<_>>();
+
+            if all.iter().any(|a| a.is_layered()) {
+                if !all.iter().all(|a| a.is_layered()) {
+                    return Err(ValidationError::LayeringMismatch);
+                }
+
+                true
+            } else {
+                false
+            }
+        };
+
+        if layered {
+            let version = context.get_version();
+            let extensions = context.get_extensions();
+
+            if !(version >= &Version(Api::Gl, 3, 2) || extensions.gl_arb_direct_state_access ||
+                 (extensions.gl_ext_direct_state_access && extensions.gl_ext_geometry_shader4))
+            {
+                return Err(ValidationError::LayeredRenderingNotSupported);
+            }
+        }
+
         Ok(ValidatedAttachments {
             raw: raw_attachments,
             marker: PhantomData,
             dimensions: dimensions,
+            samples: samples,
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            depth_buffer_format: depth_format,
+            stencil_buffer_format: stencil_format,
         })
     }
 }
@@ -241,8 +429,11 @@ impl<'a> FramebufferAttachments<'a> {
 pub struct ValidatedAttachments<'a> {
     raw: RawAttachments,
     dimensions: (u32, u32),
+    samples: Option<u32>,
     depth_buffer_bits: Option<u16>,
     stencil_buffer_bits: Option<u16>,
+    depth_buffer_format: Option<gl::types::GLenum>,
+    stencil_buffer_format: Option<gl::types::GLenum>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -252,6 +443,16 @@ impl<'a> ValidatedAttachments<'a> {
         self.dimensions
     }
 
+    /// Returns the number of color attachments, which is also the number of draw buffers.
+    pub fn get_color_attachments_count(&self) -> usize {
+        self.raw.color.len()
+    }
+
+    /// Returns the number of samples of the attachments, or `None` if they are not multisampled.
+    pub fn get_samples(&self) -> Option<u32> {
+        self.samples
+    }
+
     /// Returns the number of bits of precision of the depth buffer, or `None` if there is no
     /// depth buffer. Also works for depth-stencil buffers.
     pub fn get_depth_buffer_bits(&self) -> Option<u16> {
@@ -263,12 +464,121 @@ impl<'a> ValidatedAttachments<'a> {
     pub fn get_stencil_buffer_bits(&self) -> Option<u16> {
         self.stencil_buffer_bits
     }
+
+    /// Returns the sized internal format of the depth buffer (eg. `GL_DEPTH_COMPONENT24`), or
+    /// `None` if there is no depth buffer. Also works for depth-stencil buffers.
+    pub fn get_depth_buffer_format(&self) -> Option<gl::types::GLenum> {
+        self.depth_buffer_format
+    }
+
+    /// Returns the sized internal format of the stencil buffer (eg. `GL_STENCIL_INDEX8`), or
+    /// `None` if there is no stencil buffer. Also works for depth-stencil buffers.
+    pub fn get_stencil_buffer_format(&self) -> Option<gl::types::GLenum> {
+        self.stencil_buffer_format
+    }
+
+    /// Returns the attachment points (`GL_COLOR_ATTACHMENTi`, `GL_DEPTH_ATTACHMENT` and
+    /// `GL_STENCIL_ATTACHMENT`) of the requested attachments, for example to pass them to
+    /// `glInvalidateFramebuffer`.
+    ///
+    /// Depth-stencil attachments are returned as separate depth and stencil attachment points.
+    pub fn get_attachment_points(&self, color: bool, depth: bool, stencil: bool)
+                                 -> Vec<gl::types::GLenum>
+    {
+        let mut points = Vec::new();
+
+        if color {
+            for &(index, _) in self.raw.color.iter() {
+                points.push(gl::COLOR_ATTACHMENT0 + index);
+            }
+        }
+
+        if depth && (self.raw.depth.is_some() || self.raw.depth_stencil.is_some()) {
+            points.push(gl::DEPTH_ATTACHMENT);
+        }
+
+        if stencil && (self.raw.stencil.is_some() || self.raw.depth_stencil.is_some()) {
+            points.push(gl::STENCIL_ATTACHMENT);
+        }
+
+        points
+    }
+}
+
+/// Returns the sized internal format of the depth buffer of an attachment if `depth` is true,
+/// or of its stencil buffer otherwise.
+///
+/// For depth-stencil attachments, only the depth or the stencil part of the format is returned
+/// (eg. `GL_DEPTH_COMPONENT24` for `GL_DEPTH24_STENCIL8`).
+fn get_depth_stencil_format(attachment: &Attachment, depth_stencil: bool, depth: bool)
+                            -> gl::types::GLenum
+{
+    let format = match attachment {
+        &Attachment::RenderBuffer(buffer) => buffer.get_internal_format(),
+
+        // FIXME: depth-stencil textures are always D24S8 for the moment
+        &Attachment::Texture { .. } if depth_stencil => gl::DEPTH24_STENCIL8,
+
+        &Attachment::Texture { texture, .. } => {
+            match texture.get_internal_format() {
+                Ok(InternalFormat::OneComponent { ty1, bits1 }) => {
+                    get_sized_depth_stencil_format(depth, bits1 as u16,
+                                                   ty1 == InternalFormatType::Float)
+                },
+                _ => get_sized_depth_stencil_format(depth, if depth { 24 } else { 8 }, false),
+            }
+        },
+    };
+
+    match (format, depth) {
+        (gl::DEPTH24_STENCIL8, true) => gl::DEPTH_COMPONENT24,
+        (gl::DEPTH32F_STENCIL8, true) => gl::DEPTH_COMPONENT32F,
+        (gl::DEPTH24_STENCIL8, false) | (gl::DEPTH32F_STENCIL8, false) => gl::STENCIL_INDEX8,
+        (format, _) => format,
+    }
+}
+
+/// Returns the sized internal format of a depth buffer if `depth` is true, or of a stencil
+/// buffer otherwise, given its number of bits.
+pub fn get_sized_depth_stencil_format(depth: bool, bits: u16, float: bool)
+                                      -> gl::types::GLenum
+{
+    match (depth, bits, float) {
+        (true, 16, _) => gl::DEPTH_COMPONENT16,
+        (true, 24, _) => gl::DEPTH_COMPONENT24,
+        (true, _, true) => gl::DEPTH_COMPONENT32F,
+        (true, _, false) => gl::DEPTH_COMPONENT32,
+        (false, 1, _) => gl::STENCIL_INDEX1,
+        (false, 4, _) => gl::STENCIL_INDEX4,
+        (false, 8, _) => gl::STENCIL_INDEX8,
+        (false, _, _) => gl::STENCIL_INDEX16,
+    }
 }
 
 /// An error that can happen while validating attachments.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ValidationError {
+    /// The framebuffer doesn't have any attachment, and the backend doesn't support
+    /// framebuffers without attachments.
     EmptyFramebufferObjectsNotSupported,
+
+    /// The width or height of a framebuffer without attachments is zero or is too large.
+    EmptyFramebufferUnsupportedDimensions,
+
+    /// The number of layers of a framebuffer without attachments is zero or is too large.
+    EmptyFramebufferUnsupportedLayers,
+
+    /// The number of samples of a framebuffer without attachments is zero or is too large.
+    EmptyFramebufferUnsupportedSamples,
+
+    /// Some attachments are layered and some others are not.
+    LayeringMismatch,
+
+    /// The backend doesn't support layered attachments.
+    LayeredRenderingNotSupported,
+
+    /// The attachments don't all have the same number of samples.
+    SamplesCountMismatch,
 }
 
 /// Data structure stored in the hashmap.
@@ -278,6 +588,11 @@ struct RawAttachments {
     depth: Option<RawAttachment>,
     stencil: Option<RawAttachment>,
     depth_stencil: Option<RawAttachment>,
+    // parameters of framebuffers without attachments
+    default_width: Option<u32>,
+    default_height: Option<u32>,
+    default_layers: Option<u32>,
+    default_samples: Option<u32>,
 }
 
 /// Single attachment.
@@ -299,6 +614,20 @@ enum RawAttachment {
     RenderBuffer(gl::types::GLuint),
 }
 
+impl RawAttachment {
+    /// Returns true if all the layers of the texture are attached.
+    fn is_layered(&self) -> bool {
+        match self {
+            &RawAttachment::Texture { bind_point, layer: None, .. } => match bind_point {
+                gl::TEXTURE_1D_ARRAY | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_2D_MULTISAMPLE_ARRAY |
+                gl::TEXTURE_3D | gl::TEXTURE_CUBE_MAP | gl::TEXTURE_CUBE_MAP_ARRAY => true,
+                _ => false
+            },
+            _ => false
+        }
+    }
+}
+
 /// Manages all the framebuffer objects.
 ///
 /// `cleanup` **must** be called when destroying the container, otherwise `Drop` will panic.
@@ -415,7 +744,37 @@ impl FramebuffersContainer {
                                                 read_buffer: gl::types::GLenum)
     {
         unsafe { bind_framebuffer(ctxt, 0, false, true) };
-        unsafe { ctxt.gl.ReadBuffer(read_buffer) };     // TODO: cache
+
+        if ctxt.state.default_framebuffer_read != Some(read_buffer) {
+            unsafe { ctxt.gl.ReadBuffer(read_buffer) };
+            ctxt.state.default_framebuffer_read = Some(read_buffer);
+        }
+    }
+
+    /// Binds the default framebuffer to `GL_DRAW_FRAMEBUFFER` or `GL_FRAMEBUFFER` and selects
+    /// which of its buffers are the target of drawing operations.
+    ///
+    /// `draw_buffer` is usually `GL_BACK`, or `GL_BACK_LEFT` or `GL_BACK_RIGHT` for stereo
+    /// contexts.
+    pub fn bind_default_framebuffer_for_drawing(ctxt: &mut CommandContext,
+                                                draw_buffer: gl::types::GLenum)
+    {
+        unsafe { bind_framebuffer(ctxt, 0, true, false) };
+
+        if ctxt.state.default_framebuffer_draw == Some(draw_buffer) {
+            return;
+        }
+
+        if ctxt.version >= &Version(Api::Gl, 1, 0) {
+            unsafe { ctxt.gl.DrawBuffer(draw_buffer) };
+        } else if ctxt.version >= &Version(Api::GlEs, 3, 0) {
+            unsafe { ctxt.gl.DrawBuffers(1, &draw_buffer) };
+        } else {
+            // OpenGL ES 2 can only draw to `GL_BACK`
+            assert_eq!(draw_buffer, gl::BACK);
+        }
+
+        ctxt.state.default_framebuffer_draw = Some(draw_buffer);
     }
 
     /// Binds a framebuffer to `GL_READ_FRAMEBUFFER` or `GL_FRAMEBUFFER` so that it becomes the
@@ -435,16 +794,40 @@ impl FramebuffersContainer {
             }
         }*/
 
-        let attachments = FramebufferAttachments {
+        let attachments = FramebufferAttachments::Regular(FramebufferSpecificAttachments {
             colors: { let mut v = SmallVec::new(); v.push((0, attachment.clone())); v },
             depth_stencil: FramebufferDepthStencilAttachments::None,
-        }.validate().unwrap();
+        }).validate(&*ctxt).unwrap();
 
         let framebuffer = FramebuffersContainer::get_framebuffer_for_drawing(ctxt, Some(&attachments));
         bind_framebuffer(ctxt, framebuffer, false, true);
         ctxt.gl.ReadBuffer(gl::COLOR_ATTACHMENT0);     // TODO: cache
     }
 
+    /// Attaches a label to the framebuffer object that corresponds to the attachments,
+    /// creating it if necessary.
+    ///
+    /// The label belongs to the framebuffer object and is destroyed with it when one of the
+    /// attachments is destroyed. Does nothing if the object already has this label.
+    pub fn set_label(ctxt: &mut CommandContext, attachments: &ValidatedAttachments,
+                     label: &str)
+    {
+        let id = FramebuffersContainer::get_framebuffer(ctxt, attachments);
+
+        {
+            let framebuffers = ctxt.framebuffer_objects.framebuffers.borrow();
+            let fbo = framebuffers.get(&attachments.raw).unwrap();
+            if fbo.label.as_ref().map(|l| &l[..]) == Some(label) {
+                return;
+            }
+        }
+
+        let _ = context::set_object_label(ctxt, gl::FRAMEBUFFER, id, label);
+
+        let mut framebuffers = ctxt.framebuffer_objects.framebuffers.borrow_mut();
+        framebuffers.get_mut(&attachments.raw).unwrap().label = Some(label.to_string());
+    }
+
     ///
     /// # Unsafety
     ///
@@ -478,6 +861,8 @@ impl Drop for FramebuffersContainer {
 struct FrameBufferObject {
     id: gl::types::GLuint,
     current_read_buffer: gl::types::GLenum,
+    /// The latest label passed to `glObjectLabel`.
+    label: Option<String>,
 }
 
 impl FrameBufferObject {
@@ -525,6 +910,19 @@ impl FrameBufferObject {
             unsafe { attach(&mut ctxt, gl::DEPTH_STENCIL_ATTACHMENT, id, depth_stencil) };
         }
 
+        if let Some(width) = attachments.default_width {
+            unsafe { set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_WIDTH, width) };
+        }
+        if let Some(height) = attachments.default_height {
+            unsafe { set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_HEIGHT, height) };
+        }
+        if let Some(layers) = attachments.default_layers {
+            unsafe { set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_LAYERS, layers) };
+        }
+        if let Some(samples) = attachments.default_samples {
+            unsafe { set_parameter(&mut ctxt, id, gl::FRAMEBUFFER_DEFAULT_SAMPLES, samples) };
+        }
+
         if ctxt.version >= &Version(Api::Gl, 4, 5) ||
            ctxt.extensions.gl_arb_direct_state_access
         {
@@ -553,6 +951,7 @@ impl FrameBufferObject {
         FrameBufferObject {
             id: id,
             current_read_buffer: gl::BACK,
+            label: None,
         }
     }
 
@@ -626,6 +1025,29 @@ pub unsafe fn bind_framebuffer(ctxt: &mut CommandContext, fbo_id: gl::types::GLu
     }
 }
 
+/// Sets one of the default parameters of a framebuffer object without attachments.
+///
+/// # Safety
+///
+/// The id of the FBO must be valid.
+unsafe fn set_parameter(ctxt: &mut CommandContext, id: gl::types::GLuint,
+                        pname: gl::types::GLenum, value: u32)
+{
+    if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+        ctxt.gl.NamedFramebufferParameteri(id, pname, value as gl::types::GLint);
+
+    } else if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+              ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+              ctxt.extensions.gl_arb_framebuffer_no_attachments
+    {
+        bind_framebuffer(ctxt, id, true, false);
+        ctxt.gl.FramebufferParameteri(gl::DRAW_FRAMEBUFFER, pname, value as gl::types::GLint);
+
+    } else {
+        unreachable!();
+    }
+}
+
 /// Attaches something to a framebuffer object.
 ///
 /// # Safety
@@ -7,7 +7,7 @@ shaders that write to `gl_FragColor`.
 ```no_run
 # let display: glium::Display = unsafe { ::std::mem::uninitialized() };
 # let texture: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
-let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
 // framebuffer.draw(...);    // draws over `texture`
 ```
 
@@ -19,7 +19,7 @@ a `MultiOutputFrameBuffer`.
 # let texture1: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
 # let texture2: glium::texture::Texture2d = unsafe { ::std::mem::uninitialized() };
 let output = &[ ("output1", &texture1), ("output2", &texture2) ];
-let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, output);
+let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, output).unwrap();
 // framebuffer.draw(...);
 
 // example shader:
@@ -33,13 +33,15 @@ let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, outp
 //     }
 ```
 
-**Note**: depth-stencil attachments are not yet implemented.
+Finally, an `EmptyFrameBuffer` doesn't have any attachment at all. Drawing on it only has side
+effects such as writing to images, buffers or atomic counters from the shaders.
 
 */
+use std::fmt;
 use std::rc::Rc;
+use std::cell::RefCell;
 use smallvec::SmallVec;
 
-use texture::Texture2d;
 use texture::TextureAnyMipmap;
 use TextureExt;
 
@@ -49,18 +51,26 @@ use context::Context;
 use FboAttachments;
 use Rect;
 use BlitTarget;
+use {BlitMask, BlitError};
+use InvalidateMask;
 use ContextExt;
-use ToGlEnum;
+use CapabilitiesSource;
 use ops;
 use uniforms;
+use uniforms::UniformType;
 
-use {Program, Surface};
+use image_format::{TextureFormatRequest, TextureFormat};
+
+use {Program, Surface, SurfaceReadSource};
 use DrawError;
 
 use {fbo, gl};
+use version::{Api, Version};
 
 pub use self::render_buffer::{RenderBuffer, RenderBufferAny, DepthRenderBuffer};
 pub use self::render_buffer::{StencilRenderBuffer, DepthStencilRenderBuffer};
+pub use self::render_buffer::CreationError as RenderBufferCreationError;
+pub use fbo::ValidationError;
 
 mod render_buffer;
 
@@ -68,13 +78,18 @@ mod render_buffer;
 pub struct SimpleFrameBuffer<'a> {
     context: Rc<Context>,
     attachments: fbo::ValidatedAttachments<'a>,
+    label: RefCell<Option<String>>,
 }
 
 impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment and no depth
     /// nor stencil buffer.
-    pub fn new<F, C>(facade: &F, color: &'a C) -> SimpleFrameBuffer<'a>
-                  where C: ToColorAttachment, F: Facade
+    ///
+    /// Returns an error if the attachments can't be used together, for example if some of
+    /// them are layered and some others are not.
+    pub fn new<F, C>(facade: &F, color: &'a C)
+                     -> Result<SimpleFrameBuffer<'a>, ValidationError>
+                     where C: ToColorAttachment, F: Facade
     {
         SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(), None, None, None)
     }
@@ -82,7 +97,7 @@ impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth
     /// buffer, but no stencil buffer.
     pub fn with_depth_buffer<F, C, D>(facade: &F, color: &'a C, depth: &'a D)
-                                      -> SimpleFrameBuffer<'a>
+                                      -> Result<SimpleFrameBuffer<'a>, ValidationError>
                                       where C: ToColorAttachment, D: ToDepthAttachment, F: Facade
     {
         SimpleFrameBuffer::new_impl(facade, color.to_color_attachment(),
@@ -92,7 +107,9 @@ impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment, a depth
     /// buffer, and a stencil buffer.
     pub fn with_depth_and_stencil_buffer<F, C, D, S>(facade: &F, color: &'a C, depth: &'a D,
-                                                     stencil: &'a S) -> SimpleFrameBuffer<'a>
+                                                     stencil: &'a S)
+                                                     -> Result<SimpleFrameBuffer<'a>,
+                                                               ValidationError>
                                                      where C: ToColorAttachment,
                                                            D: ToDepthAttachment,
                                                            S: ToStencilAttachment, F: Facade
@@ -105,7 +122,7 @@ impl<'a> SimpleFrameBuffer<'a> {
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a stencil
     /// buffer, but no depth buffer.
     pub fn with_stencil_buffer<F, C, S>(facade: &F, color: &'a C, stencil: &'a S)
-                                        -> SimpleFrameBuffer<'a>
+                                        -> Result<SimpleFrameBuffer<'a>, ValidationError>
                                         where C: ToColorAttachment, S: ToStencilAttachment,
                                               F: Facade
     {
@@ -115,7 +132,7 @@ impl<'a> SimpleFrameBuffer<'a> {
 
     /// Creates a `SimpleFrameBuffer` with a single color attachment and a depth-stencil buffer.
     pub fn with_depth_stencil_buffer<F, C, D>(facade: &F, color: &'a C, depthstencil: &'a D)
-                                              -> SimpleFrameBuffer<'a>
+                                              -> Result<SimpleFrameBuffer<'a>, ValidationError>
                                               where C: ToColorAttachment,
                                                     D: ToDepthStencilAttachment, F: Facade
     {
@@ -127,56 +144,52 @@ impl<'a> SimpleFrameBuffer<'a> {
     fn new_impl<F>(facade: &F, color: ColorAttachment<'a>, depth: Option<DepthAttachment<'a>>,
                    stencil: Option<StencilAttachment<'a>>,
                    depthstencil: Option<DepthStencilAttachment<'a>>)
-                   -> SimpleFrameBuffer<'a> where F: Facade
+                   -> Result<SimpleFrameBuffer<'a>, ValidationError> where F: Facade
     {
-        let color = match color {
-            ColorAttachment::Texture(tex) => fbo::Attachment::Texture {
-                texture: tex.get_texture(), layer: Some(tex.get_layer()), level: tex.get_level()
-            },
-            ColorAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        };
-
-        let depth = depth.map(|depth| match depth {
-            DepthAttachment::Texture(tex) => fbo::Attachment::Texture {
-                texture: tex.get_texture(), layer: Some(tex.get_layer()), level: tex.get_level()
-            },
-            DepthAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        });
-
-        let stencil = stencil.map(|stencil|  match stencil {
-            StencilAttachment::Texture(tex) => fbo::Attachment::Texture {
-                texture: tex.get_texture(), layer: Some(tex.get_layer()), level: tex.get_level()
-            },
-            StencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        });
-
-        let depthstencil = depthstencil.map(|depthstencil| match depthstencil {
-            DepthStencilAttachment::Texture(tex) => fbo::Attachment::Texture {
-                texture: tex.get_texture(), layer: Some(tex.get_layer()), level: tex.get_level()
-            },
-            DepthStencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        });
-
-        let attachments = fbo::FramebufferAttachments {
-            colors: { let mut v = SmallVec::new(); v.push((0, color)); v },
-            depth_stencil: if let (Some(depth), Some(stencil)) = (depth, stencil) {
-                fbo::FramebufferDepthStencilAttachments::DepthAndStencilAttachments(depth, stencil)
-            } else if let Some(depth) = depth {
-                fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
-            } else if let Some(stencil) = stencil {
-                fbo::FramebufferDepthStencilAttachments::StencilAttachment(stencil)
-            } else if let Some(depthstencil) = depthstencil {
-                fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(depthstencil)
-            } else {
-                fbo::FramebufferDepthStencilAttachments::None
+        let attachments = fbo::FramebufferAttachments::Regular(
+            fbo::FramebufferSpecificAttachments {
+                colors: { let mut v = SmallVec::new(); v.push((0, color.to_fbo_attachment())); v },
+                depth_stencil: depth_stencil_attachments(depth, stencil, depthstencil),
             }
-        };
+        );
 
-        let attachments = attachments.validate().unwrap();
+        let attachments = try!(attachments.validate(&**facade.get_context()));
 
-        SimpleFrameBuffer {
+        Ok(SimpleFrameBuffer {
             context: facade.get_context().clone(),
             attachments: attachments,
+            label: RefCell::new(None),
+        })
+    }
+
+    /// Attaches a label to the framebuffer.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the framebuffer with `{:?}`.
+    ///
+    /// Framebuffer objects are shared between all the framebuffers that have the same
+    /// attachments. Therefore the label seen by debugging tools is the last one that was set
+    /// on a framebuffer with these attachments.
+    pub fn set_label(&self, label: &str) {
+        let mut ctxt = self.context.make_current();
+        fbo::FramebuffersContainer::set_label(&mut ctxt, &self.attachments, label);
+        *self.label.borrow_mut() = Some(label.to_string());
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.label.borrow().clone()
+    }
+}
+
+impl<'a> fmt::Debug for SimpleFrameBuffer<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let (width, height) = self.get_dimensions();
+
+        match *self.label.borrow() {
+            Some(ref label) => write!(fmt, "SimpleFrameBuffer (dimensions: {}x{}, label: {:?})",
+                                      width, height, label),
+            None => write!(fmt, "SimpleFrameBuffer (dimensions: {}x{})", width, height),
         }
     }
 }
@@ -188,6 +201,12 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         ops::clear(&self.context, Some(&self.attachments), rect, color, depth, stencil);
     }
 
+    fn invalidate(&mut self, mask: InvalidateMask, rect: Option<&Rect>) {
+        let dimensions = self.get_dimensions();
+        ops::invalidate(&self.context, Some(&self.attachments), dimensions, mask,
+                        rect);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.attachments.get_dimensions()
     }
@@ -228,33 +247,51 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
-    fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+    fn get_read_source(&self) -> Option<SurfaceReadSource> {
+        Some(SurfaceReadSource {
+            context: &self.context,
+            source: ops::Source::Framebuffer(&self.attachments, 0),
+        })
+    }
+
+    fn copy_to_texture(&self, source_rect: &Rect, target: &::texture::TextureAnyMipmap,
+                       offset: (u32, u32)) -> Result<(), ()>
+    {
+        let source = ops::Source::Framebuffer(&self.attachments, 0);
+        ops::copy_to_texture(&self.context, source, source_rect, target, offset)
+    }
+
+    fn blit_buffers<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError> where S: Surface
     {
-        target.blit_from_simple_framebuffer(self, source_rect, target_rect, filter)
+        target.blit_from_simple_framebuffer(self, source_rect, target_rect, filter, mask)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError>
     {
         ops::blit(&self.context, None, self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 
     fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
-                                    filter: uniforms::MagnifySamplerFilter)
+                                    filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                    -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 
     fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
-                                         filter: uniforms::MagnifySamplerFilter)
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                         -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 }
 
@@ -264,125 +301,248 @@ impl<'a> FboAttachments for SimpleFrameBuffer<'a> {
     }
 }
 
-/// This struct is useless for the moment.
+/// A framebuffer with multiple color attachments, each of them corresponding to a fragment
+/// output of the program.
+///
+/// Color attachments can be anything that implements `ToColorAttachment`: render buffers,
+/// floating-point, sRGB, integral and unsigned textures, or a single mipmap level or layer
+/// of a texture. To mix different types of attachments, call `to_color_attachment` on each
+/// of them and pass references to the resulting `ColorAttachment`s.
+///
+/// When drawing, each attachment is bound to the fragment output that has the same name. The
+/// type of the output must match the attachment: `int` outputs for integral attachments,
+/// `uint` outputs for unsigned attachments, and `float` outputs for all the others.
 pub struct MultiOutputFrameBuffer<'a> {
     context: Rc<Context>,
     example_attachments: fbo::ValidatedAttachments<'a>,
     color_attachments: Vec<(String, fbo::Attachment<'a>)>,
-    depth_attachment: Option<fbo::Attachment<'a>>,
-    stencil_attachment: Option<fbo::Attachment<'a>>,
+    depth_stencil_attachments: fbo::FramebufferDepthStencilAttachments<'a>,
+    label: RefCell<Option<String>>,
 }
 
 impl<'a> MultiOutputFrameBuffer<'a> {
     /// Creates a new `MultiOutputFrameBuffer`.
     ///
-    /// # Panic
+    /// Returns an error if the attachments can't be used together, for example if some of
+    /// them are layered and some others are not.
+    pub fn new<F, C>(facade: &F, color_attachments: &[(&str, &'a C)])
+                     -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                     where C: ToColorAttachment, F: Facade
+    {
+        MultiOutputFrameBuffer::new_impl(facade, color_attachments, None, None, None)
+    }
+
+    /// Creates a `MultiOutputFrameBuffer` with a depth buffer.
     ///
-    /// Panics if all attachments don't have the same dimensions.
-    pub fn new<F>(facade: &F, color_attachments: &[(&str, &'a Texture2d)])
-                  -> MultiOutputFrameBuffer<'a> where F: Facade
+    /// Returns an error if the attachments can't be used together, for example if some of
+    /// them are layered and some others are not.
+    pub fn with_depth_buffer<F, C, D>(facade: &F, color_attachments: &[(&str, &'a C)],
+                                      depth: &'a D)
+                                      -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                                      where C: ToColorAttachment, D: ToDepthAttachment,
+                                            F: Facade
     {
         MultiOutputFrameBuffer::new_impl(facade, color_attachments,
-                                         None::<&DepthRenderBuffer>,
-                                         None::<&StencilRenderBuffer>)
+                                         Some(depth.to_depth_attachment()), None, None)
     }
 
-    /// Creates a `MultiOutputFrameBuffer` with a depth buffer.
+    /// Creates a `MultiOutputFrameBuffer` with a depth buffer and a stencil buffer.
     ///
-    /// # Panic
+    /// Returns an error if the attachments can't be used together, for example if some of
+    /// them are layered and some others are not.
+    pub fn with_depth_and_stencil_buffer<F, C, D, S>(facade: &F,
+                                                     color_attachments: &[(&str, &'a C)],
+                                                     depth: &'a D, stencil: &'a S)
+                                                     -> Result<MultiOutputFrameBuffer<'a>,
+                                                               ValidationError>
+                                                     where C: ToColorAttachment,
+                                                           D: ToDepthAttachment,
+                                                           S: ToStencilAttachment, F: Facade
+    {
+        MultiOutputFrameBuffer::new_impl(facade, color_attachments,
+                                         Some(depth.to_depth_attachment()),
+                                         Some(stencil.to_stencil_attachment()), None)
+    }
+
+    /// Creates a `MultiOutputFrameBuffer` with a stencil buffer, but no depth buffer.
     ///
-    /// Panics if all attachments don't have the same dimensions.
-    pub fn with_depth_buffer<F, D>(facade: &F, color_attachments: &[(&str, &'a Texture2d)],
-                                   depth: &'a D) -> MultiOutputFrameBuffer<'a>
-                                   where D: ToDepthAttachment, F: Facade
+    /// Returns an error if the attachments can't be used together, for example if some of
+    /// them are layered and some others are not.
+    pub fn with_stencil_buffer<F, C, S>(facade: &F, color_attachments: &[(&str, &'a C)],
+                                        stencil: &'a S)
+                                        -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                                        where C: ToColorAttachment, S: ToStencilAttachment,
+                                              F: Facade
     {
-        MultiOutputFrameBuffer::new_impl(facade, color_attachments, Some(depth),
-                                         None::<&StencilRenderBuffer>)
+        MultiOutputFrameBuffer::new_impl(facade, color_attachments, None,
+                                         Some(stencil.to_stencil_attachment()), None)
     }
 
-    fn new_impl<F, D, S>(facade: &F, color: &[(&str, &'a Texture2d)],
-                         depth: Option<&'a D>, stencil: Option<&'a S>)
-                         -> MultiOutputFrameBuffer<'a> where D: ToDepthAttachment, F: Facade
+    /// Creates a `MultiOutputFrameBuffer` with a depth-stencil buffer.
+    ///
+    /// Returns an error if the attachments can't be used together, for example if some of
+    /// them are layered and some others are not.
+    pub fn with_depth_stencil_buffer<F, C, D>(facade: &F, color_attachments: &[(&str, &'a C)],
+                                              depthstencil: &'a D)
+                                              -> Result<MultiOutputFrameBuffer<'a>,
+                                                        ValidationError>
+                                              where C: ToColorAttachment,
+                                                    D: ToDepthStencilAttachment, F: Facade
+    {
+        MultiOutputFrameBuffer::new_impl(facade, color_attachments, None, None,
+                                         Some(depthstencil.to_depth_stencil_attachment()))
+    }
+
+    fn new_impl<F, C>(facade: &F, color: &[(&str, &'a C)], depth: Option<DepthAttachment<'a>>,
+                      stencil: Option<StencilAttachment<'a>>,
+                      depthstencil: Option<DepthStencilAttachment<'a>>)
+                      -> Result<MultiOutputFrameBuffer<'a>, ValidationError>
+                      where C: ToColorAttachment, F: Facade
     {
-        let color = color.iter().map(|&(name, tex)| {
-            (name.to_string(), fbo::Attachment::Texture {
-                texture: tex, layer: None, level: 0
-            })
+        let color = color.iter().map(|&(name, attachment)| {
+            (name.to_string(), attachment.to_color_attachment().to_fbo_attachment())
         }).collect::<Vec<_>>();
 
         let example_color = {
             let mut v = SmallVec::new();
-            for e in color.iter().enumerate().map(|(index, &(_, tex))| { (index as u32, tex) }) {
+            for e in color.iter().enumerate().map(|(index, &(_, a))| { (index as u32, a) }) {
                 v.push(e);
             }
             v
         };
 
-        let depth = depth.map(|depth| match depth.to_depth_attachment() {
-            DepthAttachment::Texture(tex) => fbo::Attachment::Texture {
-                texture: tex.get_texture(), layer: Some(tex.get_layer()), level: tex.get_level()
-            },
-            DepthAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        });
+        let depth_stencil = depth_stencil_attachments(depth, stencil, depthstencil);
 
-        let stencil = None;/*stencil.map(|stencil|  match color {
-            StencilAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
-                texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
-            },
-            StencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        });*/       // TODO: 
-
-        let depthstencil = None;/*depthstencil.map(|depthstencil| match color {
-            DepthStencilAttachment::Texture(tex) => fbo::Attachment::TextureLayer {
-                texture: tex.get_texture(), layer: tex.get_layer(), level: tex.get_level()
-            },
-            DepthStencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
-        });*/       // TODO: 
-
-        let example_attachments = fbo::FramebufferAttachments {
-            colors: example_color,
-            depth_stencil: if let (Some(depth), Some(stencil)) = (depth, stencil) {
-                fbo::FramebufferDepthStencilAttachments::DepthAndStencilAttachments(depth, stencil)
-            } else if let Some(depth) = depth {
-                fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
-            } else if let Some(stencil) = stencil {
-                fbo::FramebufferDepthStencilAttachments::StencilAttachment(stencil)
-            } else if let Some(depthstencil) = depthstencil {
-                fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(depthstencil)
-            } else {
-                fbo::FramebufferDepthStencilAttachments::None
+        let example_attachments = fbo::FramebufferAttachments::Regular(
+            fbo::FramebufferSpecificAttachments {
+                colors: example_color,
+                depth_stencil: depth_stencil,
             }
-        }.validate().unwrap();
+        );
+
+        let example_attachments = try!(example_attachments.validate(&**facade.get_context()));
 
-        MultiOutputFrameBuffer {
+        Ok(MultiOutputFrameBuffer {
             context: facade.get_context().clone(),
             example_attachments: example_attachments,
             color_attachments: color,
-            depth_attachment: depth,
-            stencil_attachment: stencil,
+            depth_stencil_attachments: depth_stencil,
+            label: RefCell::new(None),
+        })
+    }
+
+    /// Attaches a label to the framebuffer.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the framebuffer with `{:?}`.
+    ///
+    /// The label is attached to the framebuffer object whose attachments are in the order
+    /// passed to the constructor. The framebuffer objects used when drawing depend on the
+    /// program and aren't labelled. Framebuffer objects are shared between all the
+    /// framebuffers that have the same attachments, therefore the label seen by debugging
+    /// tools is the last one that was set on a framebuffer with these attachments.
+    pub fn set_label(&self, label: &str) {
+        let mut ctxt = self.context.make_current();
+        fbo::FramebuffersContainer::set_label(&mut ctxt, &self.example_attachments, label);
+        *self.label.borrow_mut() = Some(label.to_string());
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.label.borrow().clone()
+    }
+
+    /// Clears the color attachment that corresponds to the fragment output `name`.
+    ///
+    /// Contrary to `Surface::clear`, each attachment can be cleared with a different value,
+    /// and integral attachments can be cleared as well. If `rect` is `Some`, only the given
+    /// rectangle is cleared.
+    ///
+    /// Returns an error if the variant of `value` doesn't match the format of the attachment,
+    /// or if the backend doesn't support OpenGL 3.0 or OpenGL ES 3.0.
+    ///
+    /// # Panic
+    ///
+    /// Panics if there is no attachment named `name`.
+    pub fn clear_attachment(&mut self, name: &str, rect: Option<&Rect>, value: ClearColor)
+                            -> Result<(), ClearAttachmentError>
+    {
+        let (index, attachment) = match self.color_attachments.iter()
+                                            .position(|&(ref n, _)| n == name)
+        {
+            Some(i) => (i, self.color_attachments[i].1),
+            None => panic!("The framebuffer has no attachment named `{}`", name)
+        };
+
+        let compatible = match (value, get_attachment_kind(&attachment)) {
+            (ClearColor::Float(..), AttachmentKind::Float) => true,
+            (ClearColor::Integral(..), AttachmentKind::Integral) => true,
+            (ClearColor::Unsigned(..), AttachmentKind::Unsigned) => true,
+            _ => false,
+        };
+
+        if !compatible {
+            return Err(ClearAttachmentError::FormatMismatch);
         }
+
+        ops::clear_color_attachment(&self.context, Some(&self.example_attachments), rect,
+                                    index as u32, value)
+            .map_err(|_| ClearAttachmentError::NotSupported)
+    }
+
+    /// Reads the content of a rectangle of the color attachment that corresponds to the
+    /// fragment output `name`.
+    ///
+    /// `Surface::read_pixels` reads the first attachment. Returns `Err` if the backend can't
+    /// read pixels of this type.
+    ///
+    /// # Panic
+    ///
+    /// Panics if there is no attachment named `name`.
+    pub fn read_attachment_pixels<T, S>(&self, name: &str, rect: &Rect) -> Result<S, ()>
+                                        where T: ::texture::PixelValue,
+                                              S: ::texture::Texture2dDataSink<T>
+    {
+        let index = match self.color_attachments.iter().position(|&(ref n, _)| n == name) {
+            Some(i) => i,
+            None => panic!("The framebuffer has no attachment named `{}`", name)
+        };
+
+        let source = SurfaceReadSource {
+            context: &self.context,
+            source: ops::Source::Framebuffer(&self.example_attachments, index as u32),
+        };
+
+        let mut data = Vec::with_capacity(0);
+        try!(ops::read_surface(Some(source), ops::Aspect::Color, rect, &mut data));
+        Ok(S::from_raw(::std::borrow::Cow::Owned(data), rect.width, rect.height))
     }
 
-    fn build_attachments(&self, program: &Program) -> fbo::ValidatedAttachments {
+    fn build_attachments(&self, program: &Program)
+                         -> Result<fbo::ValidatedAttachments, DrawError>
+    {
         let mut colors = SmallVec::new();
 
         for &(ref name, attachment) in self.color_attachments.iter() {
             let location = match program.get_frag_data_location(&name) {
                 Some(l) => l,
-                None => panic!("The fragment output `{}` was not found in the program", name)
+                None => return Err(DrawError::FragmentOutputNotFound { name: name.clone() })
             };
 
+            // the type can only be checked if the backend supports introspecting the outputs
+            if let Some(ty) = program.get_frag_data_type(&name) {
+                if !is_output_compatible(ty, &attachment) {
+                    return Err(DrawError::FragmentOutputTypeMismatch { name: name.clone() });
+                }
+            }
+
             colors.push((location, attachment));
         }
 
-        fbo::FramebufferAttachments {
+        Ok(fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
             colors: colors,
-            depth_stencil: if let Some(depth) = self.depth_attachment {
-                fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
-            } else {        // FIXME: other cases
-                fbo::FramebufferDepthStencilAttachments::None
-            },
-        }.validate().unwrap()
+            depth_stencil: self.depth_stencil_attachments,
+        }).validate(&*self.context).unwrap())
     }
 }
 
@@ -394,6 +554,12 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
                    color, depth, stencil);
     }
 
+    fn invalidate(&mut self, mask: InvalidateMask, rect: Option<&Rect>) {
+        let dimensions = self.get_dimensions();
+        ops::invalidate(&self.context, Some(&self.example_attachments), dimensions, mask,
+                        rect);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.example_attachments.get_dimensions()
     }
@@ -430,51 +596,274 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
             }
         }
 
-        ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
+        let attachments = try!(self.build_attachments(program));
+
+        ops::draw(&self.context, Some(&attachments), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
-    fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+    fn get_read_source(&self) -> Option<SurfaceReadSource> {
+        Some(SurfaceReadSource {
+            context: &self.context,
+            source: ops::Source::Framebuffer(&self.example_attachments, 0),
+        })
+    }
+
+    fn copy_to_texture(&self, source_rect: &Rect, target: &::texture::TextureAnyMipmap,
+                       offset: (u32, u32)) -> Result<(), ()>
+    {
+        let source = ops::Source::Framebuffer(&self.example_attachments, 0);
+        ops::copy_to_texture(&self.context, source, source_rect, target, offset)
+    }
+
+    fn blit_buffers<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError> where S: Surface
     {
-        target.blit_from_multioutput_framebuffer(self, source_rect, target_rect, filter)
+        target.blit_from_multioutput_framebuffer(self, source_rect, target_rect, filter, mask)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError>
     {
         ops::blit(&self.context, None, self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 
     fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
-                                    filter: uniforms::MagnifySamplerFilter)
+                                    filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                    -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 
     fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
-                                         filter: uniforms::MagnifySamplerFilter)
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                         -> Result<(), BlitError>
     {
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
+    }
+}
+
+impl<'a> fmt::Debug for MultiOutputFrameBuffer<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let (width, height) = self.get_dimensions();
+
+        match *self.label.borrow() {
+            Some(ref label) => write!(fmt, "MultiOutputFrameBuffer (dimensions: {}x{}, \
+                                            label: {:?})", width, height, label),
+            None => write!(fmt, "MultiOutputFrameBuffer (dimensions: {}x{})", width, height),
+        }
     }
 }
 
 impl<'a> FboAttachments for MultiOutputFrameBuffer<'a> {
     fn get_attachments(&self) -> Option<&fbo::ValidatedAttachments> {
-        unimplemented!();
+        Some(&self.example_attachments)
+    }
+}
+
+/// A framebuffer without any attachment.
+///
+/// The fragment shader is still invoked when drawing, but its outputs are discarded. This is
+/// useful when the shaders write to images, buffers or atomic counters.
+///
+/// Requires OpenGL 4.3, OpenGL ES 3.1 or `GL_ARB_framebuffer_no_attachments`.
+pub struct EmptyFrameBuffer {
+    context: Rc<Context>,
+    attachments: fbo::ValidatedAttachments<'static>,
+}
+
+impl EmptyFrameBuffer {
+    /// Returns true if empty framebuffers are supported by the backend.
+    pub fn is_supported<C>(context: &C) -> bool where C: CapabilitiesSource {
+        context.get_version() >= &Version(Api::Gl, 4, 3) ||
+            context.get_version() >= &Version(Api::GlEs, 3, 1) ||
+            context.get_extensions().gl_arb_framebuffer_no_attachments
+    }
+
+    /// Creates a `EmptyFrameBuffer`.
+    ///
+    /// If `layers` is `Some`, the framebuffer is layered and the layer to draw to is selected
+    /// with `gl_Layer`. If `samples` is `Some`, the framebuffer is multisampled.
+    ///
+    /// Returns an error if empty framebuffers are not supported, or if the dimensions, number
+    /// of layers or number of samples are zero or exceed the limits of the backend.
+    pub fn new<F>(facade: &F, width: u32, height: u32, layers: Option<u32>,
+                  samples: Option<u32>) -> Result<EmptyFrameBuffer, ValidationError>
+                  where F: Facade
+    {
+        let attachments = fbo::FramebufferAttachments::Empty {
+            width: width,
+            height: height,
+            layers: layers,
+            samples: samples,
+        };
+
+        let attachments = try!(attachments.validate(&**facade.get_context()));
+
+        Ok(EmptyFrameBuffer {
+            context: facade.get_context().clone(),
+            attachments: attachments,
+        })
+    }
+}
+
+impl fmt::Debug for EmptyFrameBuffer {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let (width, height) = self.get_dimensions();
+        write!(fmt, "EmptyFrameBuffer (dimensions: {}x{})", width, height)
+    }
+}
+
+impl Surface for EmptyFrameBuffer {
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, depth, stencil);
+    }
+
+    fn invalidate(&mut self, mask: InvalidateMask, rect: Option<&Rect>) {
+        let dimensions = self.get_dimensions();
+        ops::invalidate(&self.context, Some(&self.attachments), dimensions, mask,
+                        rect);
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.attachments.get_dimensions()
+    }
+
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        None
+    }
+
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        None
+    }
+
+    fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &::Program,
+        uniforms: &U, draw_parameters: &::DrawParameters) -> Result<(), DrawError>
+        where I: Into<::index::IndicesSource<'b>>, U: ::uniforms::Uniforms,
+        V: ::vertex::MultiVerticesSource<'v>
+    {
+        if draw_parameters.depth_test.requires_depth_buffer() || draw_parameters.depth_write {
+            return Err(DrawError::NoDepthBuffer);
+        }
+
+        if let Some(viewport) = draw_parameters.viewport {
+            if viewport.width > self.context.capabilities().max_viewport_dims.0
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+            if viewport.height > self.context.capabilities().max_viewport_dims.1
+                    as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+        }
+
+        ops::draw(&self.context, Some(&self.attachments), vb,
+                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    }
+
+    fn copy_to_texture(&self, _: &Rect, _: &::texture::TextureAnyMipmap, _: (u32, u32))
+                       -> Result<(), ()>
+    {
+        Err(())
+    }
+
+    fn blit_buffers<S>(&self, _: &Rect, _: &S, _: &BlitTarget,
+                       _: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError> where S: Surface
+    {
+        // there is no color, depth or stencil buffer to copy
+        if mask.color {
+            return Err(BlitError::NoColorBuffer);
+        }
+
+        if mask.depth {
+            return Err(BlitError::NoDepthBuffer);
+        }
+
+        if mask.stencil {
+            return Err(BlitError::NoStencilBuffer);
+        }
+
+        Ok(())
+    }
+
+    fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError>
+    {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  mask, source_rect, target_rect, filter)
+    }
+
+    fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer,
+                                    source_rect: &Rect, target_rect: &BlitTarget,
+                                    filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                    -> Result<(), BlitError>
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask, source_rect, target_rect, filter)
+    }
+
+    fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer,
+                                         source_rect: &Rect, target_rect: &BlitTarget,
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                         -> Result<(), BlitError>
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  mask, source_rect, target_rect, filter)
+    }
+}
+
+impl FboAttachments for EmptyFrameBuffer {
+    fn get_attachments(&self) -> Option<&fbo::ValidatedAttachments> {
+        Some(&self.attachments)
     }
 }
 
+/// Value to fill a color attachment with when clearing it.
+///
+/// Floating-point and normalized attachments must be cleared with `Float`, signed integral
+/// attachments with `Integral` and unsigned integral attachments with `Unsigned`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ClearColor {
+    /// Clears with floating-point values.
+    Float(f32, f32, f32, f32),
+    /// Clears with signed integers.
+    Integral(i32, i32, i32, i32),
+    /// Clears with unsigned integers.
+    Unsigned(u32, u32, u32, u32),
+}
+
+/// Error that can happen when calling `MultiOutputFrameBuffer::clear_attachment`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClearAttachmentError {
+    /// The backend doesn't support clearing individual attachments. This requires OpenGL 3.0
+    /// or OpenGL ES 3.0.
+    NotSupported,
+
+    /// The variant of the `ClearColor` doesn't match the format of the attachment.
+    FormatMismatch,
+}
+
 /// Describes an attachment for a color buffer.
 #[derive(Copy, Clone)]
 pub enum ColorAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a mipmap level of an array or 3D texture. The layer to draw to is
+    /// selected with `gl_Layer` in the geometry shader.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a RenderBuffer),
 }
@@ -490,6 +879,9 @@ pub trait ToColorAttachment {
 pub enum DepthAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a mipmap level of an array or 3D texture. The layer to draw to is
+    /// selected with `gl_Layer` in the geometry shader.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a DepthRenderBuffer),
 }
@@ -505,6 +897,9 @@ pub trait ToDepthAttachment {
 pub enum StencilAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a mipmap level of an array or 3D texture. The layer to draw to is
+    /// selected with `gl_Layer` in the geometry shader.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a StencilRenderBuffer),
 }
@@ -520,6 +915,9 @@ pub trait ToStencilAttachment {
 pub enum DepthStencilAttachment<'a> {
     /// A texture.
     Texture(TextureAnyMipmap<'a>),
+    /// All the layers of a mipmap level of an array or 3D texture. The layer to draw to is
+    /// selected with `gl_Layer` in the geometry shader.
+    LayeredTexture(TextureAnyMipmap<'a>),
     /// A render buffer.
     RenderBuffer(&'a DepthStencilRenderBuffer),
 }
@@ -529,3 +927,124 @@ pub trait ToDepthStencilAttachment {
     /// Builds the `DepthStencilAttachment`.
     fn to_depth_stencil_attachment(&self) -> DepthStencilAttachment;
 }
+
+impl<'a> ToColorAttachment for ColorAttachment<'a> {
+    fn to_color_attachment(&self) -> ColorAttachment {
+        *self
+    }
+}
+
+impl<'a> ColorAttachment<'a> {
+    fn to_fbo_attachment(self) -> fbo::Attachment<'a> {
+        match self {
+            ColorAttachment::Texture(tex) => texture_to_fbo_attachment(tex, false),
+            ColorAttachment::LayeredTexture(tex) => texture_to_fbo_attachment(tex, true),
+            ColorAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
+        }
+    }
+}
+
+impl<'a> DepthAttachment<'a> {
+    fn to_fbo_attachment(self) -> fbo::Attachment<'a> {
+        match self {
+            DepthAttachment::Texture(tex) => texture_to_fbo_attachment(tex, false),
+            DepthAttachment::LayeredTexture(tex) => texture_to_fbo_attachment(tex, true),
+            DepthAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
+        }
+    }
+}
+
+impl<'a> StencilAttachment<'a> {
+    fn to_fbo_attachment(self) -> fbo::Attachment<'a> {
+        match self {
+            StencilAttachment::Texture(tex) => texture_to_fbo_attachment(tex, false),
+            StencilAttachment::LayeredTexture(tex) => texture_to_fbo_attachment(tex, true),
+            StencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
+        }
+    }
+}
+
+impl<'a> DepthStencilAttachment<'a> {
+    fn to_fbo_attachment(self) -> fbo::Attachment<'a> {
+        match self {
+            DepthStencilAttachment::Texture(tex) => texture_to_fbo_attachment(tex, false),
+            DepthStencilAttachment::LayeredTexture(tex) => texture_to_fbo_attachment(tex, true),
+            DepthStencilAttachment::RenderBuffer(buffer) => fbo::Attachment::RenderBuffer(buffer),
+        }
+    }
+}
+
+/// Turns a mipmap into an attachment. If `layered` is true, all the layers of the mipmap
+/// level are attached.
+fn texture_to_fbo_attachment(tex: TextureAnyMipmap, layered: bool) -> fbo::Attachment {
+    fbo::Attachment::Texture {
+        texture: tex.get_texture(),
+        layer: if layered { None } else { Some(tex.get_layer()) },
+        level: tex.get_level(),
+    }
+}
+
+/// Builds the depth and stencil attachments of a framebuffer.
+fn depth_stencil_attachments<'a>(depth: Option<DepthAttachment<'a>>,
+                                 stencil: Option<StencilAttachment<'a>>,
+                                 depthstencil: Option<DepthStencilAttachment<'a>>)
+                                 -> fbo::FramebufferDepthStencilAttachments<'a>
+{
+    let depth = depth.map(|d| d.to_fbo_attachment());
+    let stencil = stencil.map(|s| s.to_fbo_attachment());
+    let depthstencil = depthstencil.map(|ds| ds.to_fbo_attachment());
+
+    if let (Some(depth), Some(stencil)) = (depth, stencil) {
+        fbo::FramebufferDepthStencilAttachments::DepthAndStencilAttachments(depth, stencil)
+    } else if let Some(depth) = depth {
+        fbo::FramebufferDepthStencilAttachments::DepthAttachment(depth)
+    } else if let Some(stencil) = stencil {
+        fbo::FramebufferDepthStencilAttachments::StencilAttachment(stencil)
+    } else if let Some(depthstencil) = depthstencil {
+        fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(depthstencil)
+    } else {
+        fbo::FramebufferDepthStencilAttachments::None
+    }
+}
+
+/// Returns true if a fragment output of type `ty` can be written to `attachment`.
+fn is_output_compatible(ty: UniformType, attachment: &fbo::Attachment) -> bool {
+    let kind = get_attachment_kind(attachment);
+
+    match ty {
+        UniformType::Float | UniformType::FloatVec2 | UniformType::FloatVec3 |
+        UniformType::FloatVec4 => kind == AttachmentKind::Float,
+        UniformType::Int | UniformType::IntVec2 | UniformType::IntVec3 |
+        UniformType::IntVec4 => kind == AttachmentKind::Integral,
+        UniformType::UnsignedInt | UniformType::UnsignedIntVec2 | UniformType::UnsignedIntVec3 |
+        UniformType::UnsignedIntVec4 => kind == AttachmentKind::Unsigned,
+        _ => false,
+    }
+}
+
+/// Type of the values stored in a color attachment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum AttachmentKind {
+    Float,
+    Integral,
+    Unsigned,
+}
+
+fn get_attachment_kind(attachment: &fbo::Attachment) -> AttachmentKind {
+    match attachment {
+        &fbo::Attachment::Texture { texture, .. } => match texture.get_requested_format() {
+            TextureFormatRequest::AnyIntegral |
+            TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(_)) => {
+                AttachmentKind::Integral
+            },
+            TextureFormatRequest::AnyUnsigned |
+            TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(_)) => {
+                AttachmentKind::Unsigned
+            },
+            _ => AttachmentKind::Float,
+        },
+
+        // color render buffers always have a floating-point format
+        &fbo::Attachment::RenderBuffer(_) => AttachmentKind::Float,
+    }
+}
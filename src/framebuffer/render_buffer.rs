@@ -11,16 +11,26 @@ use std::rc::Rc;
 use std::ops::{Deref, DerefMut};
 use std::mem;
 
+use smallvec::SmallVec;
+
 use framebuffer::{ColorAttachment, ToColorAttachment};
 use framebuffer::{DepthAttachment, ToDepthAttachment};
 use framebuffer::{StencilAttachment, ToStencilAttachment};
 use framebuffer::{DepthStencilAttachment, ToDepthStencilAttachment};
+use framebuffer::SimpleFrameBuffer;
 use texture::{UncompressedFloatFormat, DepthFormat, StencilFormat, DepthStencilFormat};
+use uniforms::MagnifySamplerFilter;
 
 use image_format;
+use image_format::FormatNotSupportedError;
 
 use gl;
+use ops;
+use fbo;
 use GlObject;
+use FboAttachments;
+use RenderBufferExt;
+use {BlitError, BlitMask, BlitTarget, Rect, Surface};
 use fbo::FramebuffersContainer;
 use backend::Facade;
 use context::Context;
@@ -28,6 +38,25 @@ use ContextExt;
 use version::Version;
 use version::Api;
 
+/// Error that can happen when creating a render buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CreationError {
+    /// The requested format is not supported by the backend.
+    FormatNotSupported,
+
+    /// The backend doesn't support multisample render buffers.
+    MultisamplingNotSupported,
+
+    /// The requested number of samples is greater than the maximum supported by the backend.
+    SamplesCountNotSupported,
+}
+
+impl From<FormatNotSupportedError> for CreationError {
+    fn from(_: FormatNotSupportedError) -> CreationError {
+        CreationError::FormatNotSupported
+    }
+}
+
 /// A render buffer is similar to a texture, but is optimized for usage as a draw target.
 ///
 /// Contrary to a texture, you can't sample or modify the content of the `RenderBuffer`.
@@ -45,9 +74,43 @@ impl RenderBuffer {
         let format = format.expect("Format not supported");
 
         RenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, width, height)
+            buffer: RenderBufferAny::new(facade, format, width, height, None).unwrap()
         }
     }
+
+    /// Builds a new multisample render buffer.
+    ///
+    /// Returns an error if the format is not supported, or if the backend doesn't support
+    /// `samples` samples. The maximum is given by `Capabilities::max_samples`.
+    pub fn new_multisample<F>(facade: &F, format: UncompressedFloatFormat, width: u32, height: u32,
+                              samples: u32) -> Result<RenderBuffer, CreationError>
+                              where F: Facade
+    {
+        let format = image_format::TextureFormat::UncompressedFloat(format);
+
+        Ok(RenderBuffer {
+            buffer: try!(RenderBufferAny::new_multisample(facade, format, width, height,
+                                                          samples))
+        })
+    }
+
+    /// Resolves the content of this render buffer into the color buffer of `target`.
+    ///
+    /// If the render buffer is multisampled, the samples of each pixel are merged together.
+    /// The target must have the same dimensions as the render buffer and must not be
+    /// multisampled itself.
+    pub fn resolve_to(&self, target: &SimpleFrameBuffer) -> Result<(), BlitError> {
+        let attachments = fbo::FramebufferSpecificAttachments {
+            colors: {
+                let mut v = SmallVec::new();
+                v.push((0, fbo::Attachment::RenderBuffer(&self.buffer)));
+                v
+            },
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::None,
+        };
+
+        self.buffer.resolve(attachments, target, BlitMask::color())
+    }
 }
 
 impl ToColorAttachment for RenderBuffer {
@@ -94,9 +157,40 @@ impl DepthRenderBuffer {
         let format = format.expect("Format not supported");
 
         DepthRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, width, height)
+            buffer: RenderBufferAny::new(facade, format, width, height, None).unwrap()
         }
     }
+
+    /// Builds a new multisample render buffer.
+    ///
+    /// Returns an error if the format is not supported, or if the backend doesn't support
+    /// `samples` samples. The maximum is given by `Capabilities::max_samples`.
+    pub fn new_multisample<F>(facade: &F, format: DepthFormat, width: u32, height: u32,
+                              samples: u32) -> Result<DepthRenderBuffer, CreationError>
+                              where F: Facade
+    {
+        let format = image_format::TextureFormat::DepthFormat(format);
+
+        Ok(DepthRenderBuffer {
+            buffer: try!(RenderBufferAny::new_multisample(facade, format, width, height,
+                                                          samples))
+        })
+    }
+
+    /// Resolves the content of this render buffer into the depth buffer of `target`.
+    ///
+    /// If the render buffer is multisampled, the samples of each pixel are merged together.
+    /// The target must have the same dimensions as the render buffer and must not be
+    /// multisampled itself.
+    pub fn resolve_to(&self, target: &SimpleFrameBuffer) -> Result<(), BlitError> {
+        let attachments = fbo::FramebufferSpecificAttachments {
+            colors: SmallVec::new(),
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::DepthAttachment(
+                                                    fbo::Attachment::RenderBuffer(&self.buffer)),
+        };
+
+        self.buffer.resolve(attachments, target, BlitMask::depth())
+    }
 }
 
 impl ToDepthAttachment for DepthRenderBuffer {
@@ -143,9 +237,40 @@ impl StencilRenderBuffer {
         let format = format.expect("Format not supported");
 
         StencilRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, width, height)
+            buffer: RenderBufferAny::new(facade, format, width, height, None).unwrap()
         }
     }
+
+    /// Builds a new multisample render buffer.
+    ///
+    /// Returns an error if the format is not supported, or if the backend doesn't support
+    /// `samples` samples. The maximum is given by `Capabilities::max_samples`.
+    pub fn new_multisample<F>(facade: &F, format: StencilFormat, width: u32, height: u32,
+                              samples: u32) -> Result<StencilRenderBuffer, CreationError>
+                              where F: Facade
+    {
+        let format = image_format::TextureFormat::StencilFormat(format);
+
+        Ok(StencilRenderBuffer {
+            buffer: try!(RenderBufferAny::new_multisample(facade, format, width, height,
+                                                          samples))
+        })
+    }
+
+    /// Resolves the content of this render buffer into the stencil buffer of `target`.
+    ///
+    /// If the render buffer is multisampled, the samples of each pixel are merged together.
+    /// The target must have the same dimensions as the render buffer and must not be
+    /// multisampled itself.
+    pub fn resolve_to(&self, target: &SimpleFrameBuffer) -> Result<(), BlitError> {
+        let attachments = fbo::FramebufferSpecificAttachments {
+            colors: SmallVec::new(),
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::StencilAttachment(
+                                                    fbo::Attachment::RenderBuffer(&self.buffer)),
+        };
+
+        self.buffer.resolve(attachments, target, BlitMask::stencil())
+    }
 }
 
 impl ToStencilAttachment for StencilRenderBuffer {
@@ -192,9 +317,40 @@ impl DepthStencilRenderBuffer {
         let format = format.expect("Format not supported");
 
         DepthStencilRenderBuffer {
-            buffer: RenderBufferAny::new(facade, format, width, height)
+            buffer: RenderBufferAny::new(facade, format, width, height, None).unwrap()
         }
     }
+
+    /// Builds a new multisample render buffer.
+    ///
+    /// Returns an error if the format is not supported, or if the backend doesn't support
+    /// `samples` samples. The maximum is given by `Capabilities::max_samples`.
+    pub fn new_multisample<F>(facade: &F, format: DepthStencilFormat, width: u32, height: u32,
+                              samples: u32) -> Result<DepthStencilRenderBuffer, CreationError>
+                              where F: Facade
+    {
+        let format = image_format::TextureFormat::DepthStencilFormat(format);
+
+        Ok(DepthStencilRenderBuffer {
+            buffer: try!(RenderBufferAny::new_multisample(facade, format, width, height,
+                                                          samples))
+        })
+    }
+
+    /// Resolves the content of this render buffer into the depth and stencil buffers of `target`.
+    ///
+    /// If the render buffer is multisampled, the samples of each pixel are merged together.
+    /// The target must have the same dimensions as the render buffer and must not be
+    /// multisampled itself.
+    pub fn resolve_to(&self, target: &SimpleFrameBuffer) -> Result<(), BlitError> {
+        let attachments = fbo::FramebufferSpecificAttachments {
+            colors: SmallVec::new(),
+            depth_stencil: fbo::FramebufferDepthStencilAttachments::DepthStencilAttachment(
+                                                    fbo::Attachment::RenderBuffer(&self.buffer)),
+        };
+
+        self.buffer.resolve(attachments, target, BlitMask::depth_and_stencil())
+    }
 }
 
 impl ToDepthStencilAttachment for DepthStencilRenderBuffer {
@@ -228,15 +384,27 @@ impl GlObject for DepthStencilRenderBuffer {
 pub struct RenderBufferAny {
     context: Rc<Context>,
     id: gl::types::GLuint,
+    format: gl::types::GLenum,
     width: u32,
     height: u32,
+    samples: Option<u32>,
 }
 
 impl RenderBufferAny {
     /// Builds a new render buffer.
-    fn new<F>(facade: &F, format: gl::types::GLenum, width: u32, height: u32)
-              -> RenderBufferAny where F: Facade
+    fn new<F>(facade: &F, format: gl::types::GLenum, width: u32, height: u32,
+              samples: Option<u32>) -> Result<RenderBufferAny, CreationError> where F: Facade
     {
+        if let Some(samples) = samples {
+            match facade.get_context().capabilities().max_samples {
+                None => return Err(CreationError::MultisamplingNotSupported),
+                Some(max) if samples > max as u32 => {
+                    return Err(CreationError::SamplesCountNotSupported);
+                },
+                _ => ()
+            }
+        }
+
         // TODO: check that dimensions don't exceed GL_MAX_RENDERBUFFER_SIZE
         let mut ctxt = facade.get_context().make_current();
 
@@ -247,8 +415,17 @@ impl RenderBufferAny {
                 ctxt.extensions.gl_arb_direct_state_access
             {
                 ctxt.gl.CreateRenderbuffers(1, &mut id);
-                ctxt.gl.NamedRenderbufferStorage(id, format, width as gl::types::GLsizei,
-                                                 height as gl::types::GLsizei);
+
+                if let Some(samples) = samples {
+                    ctxt.gl.NamedRenderbufferStorageMultisample(id,
+                                                                samples as gl::types::GLsizei,
+                                                                format,
+                                                                width as gl::types::GLsizei,
+                                                                height as gl::types::GLsizei);
+                } else {
+                    ctxt.gl.NamedRenderbufferStorage(id, format, width as gl::types::GLsizei,
+                                                     height as gl::types::GLsizei);
+                }
 
             } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
                       ctxt.version >= &Version(Api::GlEs, 2, 0)
@@ -256,18 +433,47 @@ impl RenderBufferAny {
                 ctxt.gl.GenRenderbuffers(1, &mut id);
                 ctxt.gl.BindRenderbuffer(gl::RENDERBUFFER, id);
                 ctxt.state.renderbuffer = id;
-                // FIXME: gles2 only supports very few formats
-                ctxt.gl.RenderbufferStorage(gl::RENDERBUFFER, format,
-                                            width as gl::types::GLsizei,
-                                            height as gl::types::GLsizei);
+
+                if let Some(samples) = samples {
+                    if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                       ctxt.version >= &Version(Api::GlEs, 3, 0)
+                    {
+                        ctxt.gl.RenderbufferStorageMultisample(gl::RENDERBUFFER,
+                                                               samples as gl::types::GLsizei,
+                                                               format,
+                                                               width as gl::types::GLsizei,
+                                                               height as gl::types::GLsizei);
+                    } else {
+                        ctxt.gl.RenderbufferStorageMultisampleEXT(gl::RENDERBUFFER,
+                                                                  samples as gl::types::GLsizei,
+                                                                  format,
+                                                                  width as gl::types::GLsizei,
+                                                                  height as gl::types::GLsizei);
+                    }
+
+                } else {
+                    // FIXME: gles2 only supports very few formats
+                    ctxt.gl.RenderbufferStorage(gl::RENDERBUFFER, format,
+                                                width as gl::types::GLsizei,
+                                                height as gl::types::GLsizei);
+                }
 
             } else if ctxt.extensions.gl_ext_framebuffer_object {
                 ctxt.gl.GenRenderbuffersEXT(1, &mut id);
                 ctxt.gl.BindRenderbufferEXT(gl::RENDERBUFFER_EXT, id);
                 ctxt.state.renderbuffer = id;
-                ctxt.gl.RenderbufferStorageEXT(gl::RENDERBUFFER_EXT, format,
-                                               width as gl::types::GLsizei,
-                                               height as gl::types::GLsizei);
+
+                if let Some(samples) = samples {
+                    ctxt.gl.RenderbufferStorageMultisampleEXT(gl::RENDERBUFFER_EXT,
+                                                              samples as gl::types::GLsizei,
+                                                              format,
+                                                              width as gl::types::GLsizei,
+                                                              height as gl::types::GLsizei);
+                } else {
+                    ctxt.gl.RenderbufferStorageEXT(gl::RENDERBUFFER_EXT, format,
+                                                   width as gl::types::GLsizei,
+                                                   height as gl::types::GLsizei);
+                }
 
             } else {
                 unreachable!();
@@ -276,18 +482,94 @@ impl RenderBufferAny {
             id
         };
 
-        RenderBufferAny {
+        Ok(RenderBufferAny {
             context: facade.get_context().clone(),
             id: id,
+            format: format,
             width: width,
             height: height,
-        }
+            samples: samples,
+        })
+    }
+
+    /// Builds a new multisample render buffer with the GL format that corresponds to `format`.
+    fn new_multisample<F>(facade: &F, format: image_format::TextureFormat, width: u32,
+                          height: u32, samples: u32) -> Result<RenderBufferAny, CreationError>
+                          where F: Facade
+    {
+        let format = image_format::TextureFormatRequest::Specific(format);
+        let (_, format) = try!(image_format::format_request_to_glenum(&facade.get_context(),
+                                                                      None, format));
+        let format = match format {
+            Some(f) => f,
+            None => return Err(CreationError::FormatNotSupported)
+        };
+
+        RenderBufferAny::new(facade, format, width, height, Some(samples))
     }
 
     /// Returns the dimensions of the render buffer.
     pub fn get_dimensions(&self) -> (u32, u32) {
         (self.width, self.height)
     }
+
+    /// Returns the number of samples of the render buffer, or `None` if it is not multisampled.
+    pub fn get_samples(&self) -> Option<u32> {
+        self.samples
+    }
+
+    /// Returns the number of depth bits and stencil bits of the render buffer.
+    ///
+    /// Both are `0` for color render buffers.
+    pub fn get_depth_stencil_bits(&self) -> (u16, u16) {
+        match self.format {
+            gl::DEPTH_COMPONENT16 => (16, 0),
+            gl::DEPTH_COMPONENT24 => (24, 0),
+            gl::DEPTH_COMPONENT32 | gl::DEPTH_COMPONENT32F => (32, 0),
+            gl::DEPTH24_STENCIL8 => (24, 8),
+            gl::DEPTH32F_STENCIL8 => (32, 8),
+            gl::STENCIL_INDEX1 => (0, 1),
+            gl::STENCIL_INDEX4 => (0, 4),
+            gl::STENCIL_INDEX8 => (0, 8),
+            gl::STENCIL_INDEX16 => (0, 16),
+            _ => (0, 0),
+        }
+    }
+
+    /// Blits the whole render buffer, attached with `attachments`, to `target`.
+    fn resolve(&self, attachments: fbo::FramebufferSpecificAttachments,
+               target: &SimpleFrameBuffer, mask: BlitMask) -> Result<(), BlitError>
+    {
+        if target.get_dimensions() != self.get_dimensions() {
+            return Err(BlitError::MultisampleDimensionsMismatch);
+        }
+
+        let attachments = fbo::FramebufferAttachments::Regular(attachments);
+        let attachments = attachments.validate(&*self.context).unwrap();
+
+        let source_rect = Rect {
+            left: 0,
+            bottom: 0,
+            width: self.width,
+            height: self.height,
+        };
+
+        let target_rect = BlitTarget {
+            left: 0,
+            bottom: 0,
+            width: self.width as i32,
+            height: self.height as i32,
+        };
+
+        ops::blit(&self.context, Some(&attachments), target.get_attachments(), mask,
+                  &source_rect, &target_rect, MagnifySamplerFilter::Nearest)
+    }
+}
+
+impl RenderBufferExt for RenderBufferAny {
+    fn get_internal_format(&self) -> gl::types::GLenum {
+        self.format
+    }
 }
 
 impl Drop for RenderBufferAny {
@@ -106,11 +106,12 @@ extern crate smallvec;
 pub use backend::glutin_backend::glutin;
 pub use draw_parameters::{BlendingFunction, LinearBlendingFactor, BackfaceCullingMode};
 pub use draw_parameters::{DepthTest, PolygonMode, DrawParameters, StencilTest, StencilOperation};
-pub use draw_parameters::{Smooth};
+pub use draw_parameters::{LogicOp, PolygonOffset, ProvokingVertex, Smooth};
 pub use index::IndexBuffer;
 pub use vertex::{VertexBuffer, Vertex, VertexFormat};
 pub use program::{Program, ProgramCreationError};
 pub use program::ProgramCreationError::{CompilationError, LinkingError, ShaderTypeNotSupported};
+pub use render_queue::RenderQueue;
 pub use sync::{LinearSyncFence, SyncFence};
 pub use texture::{Texture, Texture2d};
 pub use version::{Api, Version, get_supported_glsl_version};
@@ -133,6 +134,7 @@ pub mod framebuffer;
 pub mod index;
 pub mod pixel_buffer;
 pub mod program;
+pub mod render_queue;
 pub mod uniforms;
 pub mod vertex;
 pub mod texture;
@@ -314,6 +316,12 @@ trait QueryExt {
     fn is_unused(&self) -> bool;
 }
 
+/// Internal trait for render buffers.
+trait RenderBufferExt {
+    /// Returns the sized internal format of the render buffer (eg. `GL_DEPTH_COMPONENT24`).
+    fn get_internal_format(&self) -> gl::types::GLenum;
+}
+
 /// Internal trait for textures.
 trait TextureExt {
     /// Returns the context associated to this texture.
@@ -322,6 +330,9 @@ trait TextureExt {
     /// Returns the bind point of the texture.
     fn get_bind_point(&self) -> gl::types::GLenum;
 
+    /// Returns the format that was requested when the texture was created.
+    fn get_requested_format(&self) -> image_format::TextureFormatRequest;
+
     /// Makes sure that the texture is binded to the current texture unit and returns the
     /// bind point to use to access the texture (eg. `GL_TEXTURE_2D`, `GL_TEXTURE_3D`, etc.).
     fn bind_to_current(&self, &mut CommandContext) -> gl::types::GLenum;
@@ -371,6 +382,12 @@ trait UniformsExt {
                             -> Result<(), DrawError> where P: ProgramExt;
 }
 
+/// Internal trait for uniform values.
+trait UniformValueExt<'a> {
+    /// Returns the texture contained in the value, or `None` if the value is not a texture.
+    fn get_texture(&self) -> Option<&'a texture::TextureAny>;
+}
+
 
 /// A raw value of a uniform. "Raw" means that it's passed directly with `glUniform`. Textures
 /// for example are just passed as integers.
@@ -426,6 +443,145 @@ pub struct BlitTarget {
     pub height: i32,
 }
 
+/// Buffers to copy when blitting from one surface to another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlitMask {
+    /// True if the color buffer must be copied.
+    pub color: bool,
+    /// True if the depth buffer must be copied.
+    pub depth: bool,
+    /// True if the stencil buffer must be copied.
+    pub stencil: bool,
+}
+
+impl BlitMask {
+    /// Copies only the color buffer.
+    pub fn color() -> BlitMask {
+        BlitMask { color: true, depth: false, stencil: false }
+    }
+
+    /// Copies only the depth buffer.
+    pub fn depth() -> BlitMask {
+        BlitMask { color: false, depth: true, stencil: false }
+    }
+
+    /// Copies only the stencil buffer.
+    pub fn stencil() -> BlitMask {
+        BlitMask { color: false, depth: false, stencil: true }
+    }
+
+    /// Copies the depth and stencil buffers.
+    pub fn depth_and_stencil() -> BlitMask {
+        BlitMask { color: false, depth: true, stencil: true }
+    }
+
+    /// Copies the color, depth and stencil buffers.
+    pub fn all() -> BlitMask {
+        BlitMask { color: true, depth: true, stencil: true }
+    }
+
+    fn to_glbitfield(&self) -> gl::types::GLbitfield {
+        let mut mask = 0;
+        if self.color { mask |= gl::COLOR_BUFFER_BIT; }
+        if self.depth { mask |= gl::DEPTH_BUFFER_BIT; }
+        if self.stencil { mask |= gl::STENCIL_BUFFER_BIT; }
+        mask
+    }
+}
+
+/// Buffers to invalidate with `Surface::invalidate`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InvalidateMask {
+    /// True if the color buffers must be invalidated.
+    pub color: bool,
+    /// True if the depth buffer must be invalidated.
+    pub depth: bool,
+    /// True if the stencil buffer must be invalidated.
+    pub stencil: bool,
+}
+
+impl InvalidateMask {
+    /// Invalidates only the color buffers.
+    pub fn color() -> InvalidateMask {
+        InvalidateMask { color: true, depth: false, stencil: false }
+    }
+
+    /// Invalidates only the depth buffer.
+    pub fn depth() -> InvalidateMask {
+        InvalidateMask { color: false, depth: true, stencil: false }
+    }
+
+    /// Invalidates only the stencil buffer.
+    pub fn stencil() -> InvalidateMask {
+        InvalidateMask { color: false, depth: false, stencil: true }
+    }
+
+    /// Invalidates the depth and stencil buffers.
+    pub fn depth_and_stencil() -> InvalidateMask {
+        InvalidateMask { color: false, depth: true, stencil: true }
+    }
+
+    /// Invalidates the color, depth and stencil buffers.
+    pub fn all() -> InvalidateMask {
+        InvalidateMask { color: true, depth: true, stencil: true }
+    }
+}
+
+/// Error that can happen while blitting.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlitError {
+    /// The depth or stencil buffer has been requested, but the filter is not `Nearest`.
+    FilterNotNearest,
+
+    /// The color buffer has been requested, but the source doesn't have one.
+    NoColorBuffer,
+
+    /// The depth buffer has been requested, but the source or the target doesn't have one.
+    NoDepthBuffer,
+
+    /// The stencil buffer has been requested, but the source or the target doesn't have one.
+    NoStencilBuffer,
+
+    /// The depth buffers of the source and the target don't have the same format.
+    DepthFormatMismatch,
+
+    /// The stencil buffers of the source and the target don't have the same format.
+    StencilFormatMismatch,
+
+    /// The target is multisampled. Multisample surfaces can only be the source of a blit.
+    MultisampledTarget,
+
+    /// The source and target rectangles don't have the same dimensions. This is required when
+    /// the source is multisampled or when resolving a render buffer.
+    MultisampleDimensionsMismatch,
+}
+
+impl std::fmt::Display for BlitError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            &BlitError::FilterNotNearest => write!(fmt, "Depth and stencil buffers can only be \
+                                                         blitted with the `Nearest` filter."),
+            &BlitError::NoColorBuffer => write!(fmt, "The source doesn't have a color buffer."),
+            &BlitError::NoDepthBuffer => write!(fmt, "The source or the target doesn't have a \
+                                                      depth buffer."),
+            &BlitError::NoStencilBuffer => write!(fmt, "The source or the target doesn't have a \
+                                                        stencil buffer."),
+            &BlitError::DepthFormatMismatch => write!(fmt, "The depth buffers of the source and \
+                                                            the target don't have the same \
+                                                            format."),
+            &BlitError::StencilFormatMismatch => write!(fmt, "The stencil buffers of the source \
+                                                              and the target don't have the same \
+                                                              format."),
+            &BlitError::MultisampledTarget => write!(fmt, "Can't blit to a multisampled \
+                                                           surface."),
+            &BlitError::MultisampleDimensionsMismatch => write!(fmt, "When blitting from a \
+                                                                      multisampled surface, the \
+                                                                      source and target must \
+                                                                      have the same dimensions."),
+        }
+    }
+}
+
 /// Object that can be drawn upon.
 ///
 /// # What does the GPU do when you draw?
@@ -675,6 +831,16 @@ pub trait Surface: Sized {
         self.clear(None, Some(color), Some(depth), Some(stencil));
     }
 
+    /// Invalidates the content of some buffers of the target.
+    ///
+    /// This tells the backend that the content of these buffers is no longer needed, so that
+    /// tile-based and bandwidth-bound hardware doesn't write it back to memory. The content of
+    /// the invalidated buffers is undefined afterwards. If `rect` is `Some`, only the given
+    /// rectangle is invalidated.
+    ///
+    /// Invalidating is only a hint. Does nothing if the backend doesn't support it.
+    fn invalidate(&mut self, mask: InvalidateMask, rect: Option<&Rect>);
+
     /// Returns the dimensions in pixels of the target.
     fn get_dimensions(&self) -> (u32, u32);
 
@@ -710,19 +876,143 @@ pub trait Surface: Sized {
         V: vertex::MultiVerticesSource<'b>, I: Into<index::IndicesSource<'a>>,
         U: uniforms::Uniforms;
 
+    /// Returns the buffer that the `read_*` methods read from, or `None` if the surface can't
+    /// be read.
+    ///
+    /// The default implementation returns `None`.
+    #[doc(hidden)]
+    fn get_read_source(&self) -> Option<SurfaceReadSource> {
+        None
+    }
+
+    /// Reads the content of a rectangle of the color buffer of the surface.
+    ///
+    /// The type of the pixels is chosen with `T`, for example `(u8, u8, u8, u8)` or
+    /// `(f32, f32, f32, f32)`. Returns `Err` if the backend can't read pixels of this type.
+    fn read_pixels<T, S>(&self, rect: &Rect) -> Result<S, ()>
+                         where T: texture::PixelValue, S: texture::Texture2dDataSink<T>
+    {
+        let mut data = Vec::with_capacity(0);
+        try!(ops::read_surface(self.get_read_source(), ops::Aspect::Color, rect, &mut data));
+        Ok(S::from_raw(std::borrow::Cow::Owned(data), rect.width, rect.height))
+    }
+
+    /// Reads the content of a rectangle of the depth buffer of the surface.
+    ///
+    /// Returns `Err` if the surface has no depth buffer or if the backend can't read depth
+    /// values, which is the case of OpenGL ES.
+    fn read_depth_pixels<S>(&self, rect: &Rect) -> Result<S, ()>
+                            where S: texture::Texture2dDataSink<f32>
+    {
+        if !self.has_depth_buffer() {
+            return Err(());
+        }
+
+        let mut data = Vec::with_capacity(0);
+        try!(ops::read_surface(self.get_read_source(), ops::Aspect::Depth, rect, &mut data));
+        Ok(S::from_raw(std::borrow::Cow::Owned(data), rect.width, rect.height))
+    }
+
+    /// Reads the content of a rectangle of the stencil buffer of the surface.
+    ///
+    /// Returns `Err` if the surface has no stencil buffer or if the backend can't read stencil
+    /// values, which is the case of OpenGL ES.
+    fn read_stencil_pixels<S>(&self, rect: &Rect) -> Result<S, ()>
+                              where S: texture::Texture2dDataSink<u8>
+    {
+        if !self.has_stencil_buffer() {
+            return Err(());
+        }
+
+        let mut data = Vec::with_capacity(0);
+        try!(ops::read_surface(self.get_read_source(), ops::Aspect::Stencil, rect, &mut data));
+        Ok(S::from_raw(std::borrow::Cow::Owned(data), rect.width, rect.height))
+    }
+
+    /// Same as `read_pixels`, but writes the pixels to a pixel buffer instead. This operation
+    /// is asynchronous and doesn't wait for the GPU.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the pixel buffer is not large enough.
+    fn read_pixels_to_buffer<T>(&self, rect: &Rect, dest: &pixel_buffer::PixelBuffer<T>)
+                                -> Result<(), ()> where T: texture::PixelValue
+    {
+        ops::read_surface(self.get_read_source(), ops::Aspect::Color, rect, dest)
+    }
+
+    /// Same as `read_depth_pixels`, but writes the values to a pixel buffer instead. This
+    /// operation is asynchronous and doesn't wait for the GPU.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the pixel buffer is not large enough.
+    fn read_depth_pixels_to_buffer(&self, rect: &Rect, dest: &pixel_buffer::PixelBuffer<f32>)
+                                   -> Result<(), ()>
+    {
+        if !self.has_depth_buffer() {
+            return Err(());
+        }
+
+        ops::read_surface(self.get_read_source(), ops::Aspect::Depth, rect, dest)
+    }
+
+    /// Same as `read_stencil_pixels`, but writes the values to a pixel buffer instead. This
+    /// operation is asynchronous and doesn't wait for the GPU.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the pixel buffer is not large enough.
+    fn read_stencil_pixels_to_buffer(&self, rect: &Rect, dest: &pixel_buffer::PixelBuffer<u8>)
+                                     -> Result<(), ()>
+    {
+        if !self.has_stencil_buffer() {
+            return Err(());
+        }
+
+        ops::read_surface(self.get_read_source(), ops::Aspect::Stencil, rect, dest)
+    }
+
+    /// Copies a rectangle of the color buffer of the surface to a mipmap of a texture.
+    ///
+    /// `offset` is the position in the texture where the bottom-left corner of `source_rect`
+    /// is copied. If the texture has a depth or a stencil format, the content of the depth or
+    /// stencil buffer is copied instead. The data never goes through the CPU.
+    ///
+    /// Returns `Err` if this type of texture is not supported, for example multisample and
+    /// cubemap textures. The behavior is undefined if the texture is attached to the surface.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the rectangle doesn't fit in the texture.
+    fn copy_to_texture(&self, source_rect: &Rect, target: &texture::TextureAnyMipmap,
+                       offset: (u32, u32)) -> Result<(), ()>;
+
     /// Blits from the default framebuffer.
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter);
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError>;
 
     /// Blits from a simple framebuffer.
     fn blit_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
-                                    filter: uniforms::MagnifySamplerFilter);
+                                    filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                    -> Result<(), BlitError>;
 
     /// Blits from a multi-output framebuffer.
     fn blit_from_multioutput_framebuffer(&self, source: &framebuffer::MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
-                                         filter: uniforms::MagnifySamplerFilter);
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                         -> Result<(), BlitError>;
+
+    /// Copies a rectangle of the buffers given by `mask` from this surface to another surface.
+    ///
+    /// Works like `blit_color`, except that the depth and stencil buffers can be copied as
+    /// well. When they are, the filter must be `Nearest` and the depth or stencil buffers of
+    /// the source and the target must have the same format.
+    fn blit_buffers<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError> where S: Surface;
 
     /// Copies a rectangle of pixels from this surface to another surface.
     ///
@@ -736,12 +1026,19 @@ pub trait Surface: Sized {
     ///
     /// Note that there is no alpha blending, depth/stencil checking, etc. This function just
     /// copies pixels.
+    ///
+    /// Returns an error if the target is multisampled, or if the source is multisampled and
+    /// the rectangles don't have the same dimensions.
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface;
+                     filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError>
+                     where S: Surface
+    {
+        self.blit_buffers(source_rect, target, target_rect, filter, BlitMask::color())
+    }
 
     /// Copies the entire surface to a target surface. See `blit_color`.
     fn blit_whole_color_to<S>(&self, target: &S, target_rect: &BlitTarget,
-        filter: uniforms::MagnifySamplerFilter) where S: Surface
+        filter: uniforms::MagnifySamplerFilter) -> Result<(), BlitError> where S: Surface
     {
         let src_dim = self.get_dimensions();
         let src_rect = Rect { left: 0, bottom: 0, width: src_dim.0 as u32, height: src_dim.1 as u32 };
@@ -749,7 +1046,9 @@ pub trait Surface: Sized {
     }
 
     /// Copies the entire surface to the entire target. See `blit_color`.
-    fn fill<S>(&self, target: &S, filter: uniforms::MagnifySamplerFilter) where S: Surface {
+    fn fill<S>(&self, target: &S, filter: uniforms::MagnifySamplerFilter)
+               -> Result<(), BlitError> where S: Surface
+    {
         let src_dim = self.get_dimensions();
         let src_rect = Rect { left: 0, bottom: 0, width: src_dim.0 as u32, height: src_dim.1 as u32 };
         let target_dim = target.get_dimensions();
@@ -758,6 +1057,15 @@ pub trait Surface: Sized {
     }
 }
 
+/// The buffer of a surface that pixels are read from.
+///
+/// Returned by `Surface::get_read_source`. This can't be built outside of glium.
+#[doc(hidden)]
+pub struct SurfaceReadSource<'a> {
+    context: &'a Context,
+    source: ops::Source<'a>,
+}
+
 /// Private trait for framebuffer-like objects that provide attachments.
 trait FboAttachments {
     /// Returns the list of attachments of this FBO, or `None` if it is the default framebuffer.
@@ -840,6 +1148,77 @@ pub enum DrawError {
 
     /// You requested smoothing, but this is not supported by the backend.
     SmoothingNotSupported,
+
+    /// A color attachment of the framebuffer doesn't correspond to any fragment output of
+    /// the program.
+    FragmentOutputNotFound {
+        /// Name of the attachment.
+        name: String,
+    },
+
+    /// The type of a fragment output of the program doesn't match the format of the
+    /// color attachment it is written to.
+    ///
+    /// This error is only reported if the backend can introspect the fragment outputs of a
+    /// program, which requires OpenGL 4.3, OpenGL ES 3.1 or `GL_ARB_program_interface_query`.
+    /// Otherwise the types are not checked.
+    FragmentOutputTypeMismatch {
+        /// Name of the fragment output.
+        name: String,
+    },
+
+    /// Requested different blending functions or color masks for individual draw buffers,
+    /// but this is not supported by the backend.
+    DrawBufferParametersNotSupported,
+
+    /// The index of a draw buffer in `DrawParameters::draw_buffers` is greater than or equal
+    /// to the maximum number of draw buffers.
+    DrawBufferIndexOutOfRange,
+
+    /// Requested dual-source blending, but this is not supported by the backend.
+    DualSourceBlendingNotSupported,
+
+    /// Dual-source blending can only be used when drawing to a single draw buffer.
+    DualSourceBlendingMultipleDrawBuffers,
+
+    /// Requested primitive restart, but this is not supported by the backend.
+    PrimitiveRestartNotSupported,
+
+    /// A bit of `clip_planes_bitmask` is greater than or equal to the maximum number of clip
+    /// distances supported by the backend.
+    ClipPlaneIndexOutOfBounds,
+
+    /// Requested depth clamping, but this is not supported by the backend.
+    DepthClampNotSupported,
+
+    /// Requested a logic operation, but this is not supported by the backend.
+    LogicOperationNotSupported,
+
+    /// Requested the first vertex as the provoking vertex, but this is not supported by
+    /// the backend.
+    ProvokingVertexNotSupported,
+
+    /// Requested a sample mask, but this is not supported by the backend.
+    SampleMaskNotSupported,
+
+    /// A bit of `sample_mask` is greater than or equal to the number of samples of the surface.
+    SampleMaskOutOfBounds,
+
+    /// Requested per-sample shading, but this is not supported by the backend.
+    SampleShadingNotSupported,
+
+    /// The value of `min_sample_shading` is not between 0.0 and 1.0.
+    InvalidMinSampleShading,
+
+    /// Requested multiple viewports or scissor boxes, but this is not supported by the backend.
+    ViewportArrayNotSupported,
+
+    /// The number of viewports or scissor boxes is greater than the maximum supported by
+    /// the backend.
+    TooManyViewports,
+
+    /// The number of scissor boxes is different from the number of viewports.
+    ScissorArrayLengthMismatch,
 }
 
 impl std::fmt::Display for DrawError {
@@ -893,6 +1272,72 @@ impl std::fmt::Display for DrawError {
             &DrawError::WrongQueryOperation => write!(fmt, "Wrong query operation."),
             &DrawError::SmoothingNotSupported => write!(fmt, "Trying to use smoothing, but this is \
                                                               not supported by the backend."),
+            &DrawError::FragmentOutputNotFound { ref name } => {
+                write!(fmt, "The fragment output `{}` was not found in the program.", name)
+            },
+            &DrawError::FragmentOutputTypeMismatch { ref name } => {
+                write!(fmt, "The type of the fragment output `{}` doesn't match the format of \
+                             the attachment.", name)
+            },
+            &DrawError::DrawBufferParametersNotSupported => {
+                write!(fmt, "Requested different blending functions or color masks for \
+                             individual draw buffers, but this is not supported by the backend.")
+            },
+            &DrawError::DrawBufferIndexOutOfRange => {
+                write!(fmt, "The index of a draw buffer is out of range.")
+            },
+            &DrawError::DualSourceBlendingNotSupported => {
+                write!(fmt, "Requested dual-source blending, but this is not supported by the \
+                             backend.")
+            },
+            &DrawError::DualSourceBlendingMultipleDrawBuffers => {
+                write!(fmt, "Dual-source blending can only be used when drawing to a single \
+                             draw buffer.")
+            },
+            &DrawError::PrimitiveRestartNotSupported => {
+                write!(fmt, "Requested primitive restart, but this is not supported by the \
+                             backend.")
+            },
+            &DrawError::ClipPlaneIndexOutOfBounds => {
+                write!(fmt, "One of the requested clip planes is not supported by the backend.")
+            },
+            &DrawError::DepthClampNotSupported => {
+                write!(fmt, "Requested depth clamping, but this is not supported by the backend.")
+            },
+            &DrawError::LogicOperationNotSupported => {
+                write!(fmt, "Requested a logic operation, but this is not supported by the \
+                             backend.")
+            },
+            &DrawError::ProvokingVertexNotSupported => {
+                write!(fmt, "Requested the first vertex as the provoking vertex, but this is \
+                             not supported by the backend.")
+            },
+            &DrawError::SampleMaskNotSupported => {
+                write!(fmt, "Requested a sample mask, but this is not supported by the backend.")
+            },
+            &DrawError::SampleMaskOutOfBounds => {
+                write!(fmt, "The sample mask contains samples that the surface doesn't have.")
+            },
+            &DrawError::SampleShadingNotSupported => {
+                write!(fmt, "Requested per-sample shading, but this is not supported by the \
+                             backend.")
+            },
+            &DrawError::InvalidMinSampleShading => {
+                write!(fmt, "The minimum fraction of samples to shade must be between 0.0 and \
+                             1.0.")
+            },
+            &DrawError::ViewportArrayNotSupported => {
+                write!(fmt, "Requested multiple viewports or scissor boxes, but this is not \
+                             supported by the backend.")
+            },
+            &DrawError::TooManyViewports => {
+                write!(fmt, "The number of viewports or scissor boxes is greater than the \
+                             maximum supported by the backend.")
+            },
+            &DrawError::ScissorArrayLengthMismatch => {
+                write!(fmt, "The number of scissor boxes is different from the number of \
+                             viewports.")
+            },
         }
     }
 }
@@ -958,32 +1403,62 @@ impl Frame {
         self.destroyed = true;
         self.context.swap_buffers()
     }
-}
 
-impl Surface for Frame {
-    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
-             depth: Option<f32>, stencil: Option<i32>)
-    {
-        ops::clear(&self.context, None, None, color, depth, stencil);
+    /// Returns a surface that targets the back buffer of the left eye.
+    ///
+    /// Drawing on the `Frame` itself draws on the back buffers of both eyes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the context doesn't support stereoscopic rendering. This is indicated by
+    /// the `stereo` capability.
+    pub fn left(&mut self) -> StereoSurface {
+        assert!(self.context.capabilities().stereo,
+                "The context doesn't support stereoscopic rendering");
+        StereoSurface { frame: self, buffer: gl::BACK_LEFT }
     }
 
-    fn get_dimensions(&self) -> (u32, u32) {
-        self.dimensions
+    /// Returns a surface that targets the back buffer of the right eye.
+    ///
+    /// Drawing on the `Frame` itself draws on the back buffers of both eyes.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the context doesn't support stereoscopic rendering. This is indicated by
+    /// the `stereo` capability.
+    pub fn right(&mut self) -> StereoSurface {
+        assert!(self.context.capabilities().stereo,
+                "The context doesn't support stereoscopic rendering");
+        StereoSurface { frame: self, buffer: gl::BACK_RIGHT }
     }
 
-    fn get_depth_buffer_bits(&self) -> Option<u16> {
-        self.context.capabilities().depth_bits
+    /// Invalidates the depth and stencil buffers of the default framebuffer.
+    ///
+    /// Call this after the last draw command of the frame, so that tile-based and
+    /// bandwidth-bound hardware doesn't write the depth and stencil buffers back to memory.
+    /// Their content is undefined afterwards.
+    pub fn invalidate_depth_stencil(&mut self) {
+        self.invalidate(InvalidateMask::depth_and_stencil(), None);
     }
 
-    fn get_stencil_buffer_bits(&self) -> Option<u16> {
-        self.context.capabilities().stencil_bits
+    /// Makes the given buffers of the default framebuffer the target of the next drawing
+    /// operations.
+    fn select_draw_buffer(&self, buffer: gl::types::GLenum) {
+        let mut ctxt = self.context.make_current();
+        fbo::FramebuffersContainer::bind_default_framebuffer_for_drawing(&mut ctxt, buffer);
     }
 
-    fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
-                         index_buffer: I, program: &Program, uniforms: &U,
-                         draw_parameters: &DrawParameters) -> Result<(), DrawError>
-                         where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
-                         V: vertex::MultiVerticesSource<'b>
+    /// Makes the given buffer of the default framebuffer the source of the next blit.
+    fn select_read_buffer(&self, buffer: gl::types::GLenum) {
+        let mut ctxt = self.context.make_current();
+        fbo::FramebuffersContainer::bind_default_framebuffer_for_reading(&mut ctxt, buffer);
+    }
+
+    fn draw_impl<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                                  index_buffer: I, program: &Program, uniforms: &U,
+                                  draw_parameters: &DrawParameters) -> Result<(), DrawError>
+                                  where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
+                                  V: vertex::MultiVerticesSource<'b>
     {
         if !self.has_depth_buffer() && (draw_parameters.depth_test.requires_depth_buffer() ||
                 draw_parameters.depth_write)
@@ -1007,34 +1482,91 @@ impl Surface for Frame {
         ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
                   uniforms, draw_parameters, (self.dimensions.0 as u32, self.dimensions.1 as u32))
     }
+}
 
-    fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
-                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+impl Surface for Frame {
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        self.select_draw_buffer(gl::BACK);
+        ops::clear(&self.context, None, rect, color, depth, stencil);
+    }
+
+    fn invalidate(&mut self, mask: InvalidateMask, rect: Option<&Rect>) {
+        ops::invalidate(&self.context, None, self.dimensions, mask, rect);
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        self.context.capabilities().depth_bits
+    }
+
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        self.context.capabilities().stencil_bits
+    }
+
+    fn draw<'a, 'b, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, program: &Program, uniforms: &U,
+                         draw_parameters: &DrawParameters) -> Result<(), DrawError>
+                         where I: Into<index::IndicesSource<'a>>, U: uniforms::Uniforms,
+                         V: vertex::MultiVerticesSource<'b>
     {
-        target.blit_from_frame(source_rect, target_rect, filter)
+        self.select_draw_buffer(gl::BACK);
+        self.draw_impl(vertex_buffer, index_buffer, program, uniforms, draw_parameters)
+    }
+
+    fn get_read_source(&self) -> Option<SurfaceReadSource> {
+        Some(SurfaceReadSource {
+            context: &self.context,
+            source: ops::Source::DefaultFramebuffer(gl::BACK),
+        })
+    }
+
+    fn copy_to_texture(&self, source_rect: &Rect, target: &texture::TextureAnyMipmap,
+                       offset: (u32, u32)) -> Result<(), ()>
+    {
+        let source = ops::Source::DefaultFramebuffer(gl::BACK);
+        ops::copy_to_texture(&self.context, source, source_rect, target, offset)
+    }
+
+    fn blit_buffers<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError> where S: Surface
+    {
+        self.select_read_buffer(gl::BACK);
+        target.blit_from_frame(source_rect, target_rect, filter, mask)
     }
 
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
-                       filter: uniforms::MagnifySamplerFilter)
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError>
     {
+        self.select_draw_buffer(gl::BACK);
         ops::blit(&self.context, None, self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 
     fn blit_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer,
                                     source_rect: &Rect, target_rect: &BlitTarget,
-                                    filter: uniforms::MagnifySamplerFilter)
+                                    filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                    -> Result<(), BlitError>
     {
+        self.select_draw_buffer(gl::BACK);
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 
     fn blit_from_multioutput_framebuffer(&self, source: &framebuffer::MultiOutputFrameBuffer,
                                          source_rect: &Rect, target_rect: &BlitTarget,
-                                         filter: uniforms::MagnifySamplerFilter)
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                         -> Result<(), BlitError>
     {
+        self.select_draw_buffer(gl::BACK);
         ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
-                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+                  mask, source_rect, target_rect, filter)
     }
 }
 
@@ -1053,6 +1585,118 @@ impl Drop for Frame {
     }
 }
 
+/// Implementation of `Surface` targeting the back buffer of one eye of a stereo `Frame`.
+///
+/// Obtained with `Frame::left` or `Frame::right`.
+///
+/// OpenGL can't invalidate the color buffer of a single eye, so `invalidate` only invalidates
+/// the depth and stencil buffers.
+pub struct StereoSurface<'a> {
+    frame: &'a mut Frame,
+    buffer: gl::types::GLenum,
+}
+
+impl<'a> StereoSurface<'a> {
+    /// Reads the content of the back buffer of this eye.
+    pub fn read<T>(&self) -> T where T: texture::Texture2dDataSink<(u8, u8, u8, u8)> {
+        let (width, height) = self.get_dimensions();
+        let rect = Rect { left: 0, bottom: 0, width: width, height: height };
+
+        // reading `(u8, u8, u8, u8)` is always supported
+        self.read_pixels(&rect).unwrap()
+    }
+}
+
+impl<'a> Surface for StereoSurface<'a> {
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        self.frame.select_draw_buffer(self.buffer);
+        ops::clear(&self.frame.context, None, rect, color, depth, stencil);
+    }
+
+    fn invalidate(&mut self, mask: InvalidateMask, rect: Option<&Rect>) {
+        // the color buffers of the default framebuffer can only be invalidated all at once,
+        // so we don't invalidate the color buffer of a single eye
+        let mask = InvalidateMask { color: false, .. mask };
+        let dimensions = self.get_dimensions();
+        ops::invalidate(&self.frame.context, None, dimensions, mask, rect);
+    }
+
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.frame.get_dimensions()
+    }
+
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        self.frame.get_depth_buffer_bits()
+    }
+
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        self.frame.get_stencil_buffer_bits()
+    }
+
+    fn draw<'b, 'c, V, I, U>(&mut self, vertex_buffer: V,
+                         index_buffer: I, program: &Program, uniforms: &U,
+                         draw_parameters: &DrawParameters) -> Result<(), DrawError>
+                         where I: Into<index::IndicesSource<'b>>, U: uniforms::Uniforms,
+                         V: vertex::MultiVerticesSource<'c>
+    {
+        self.frame.select_draw_buffer(self.buffer);
+        self.frame.draw_impl(vertex_buffer, index_buffer, program, uniforms, draw_parameters)
+    }
+
+    fn get_read_source(&self) -> Option<SurfaceReadSource> {
+        Some(SurfaceReadSource {
+            context: &self.frame.context,
+            source: ops::Source::DefaultFramebuffer(self.buffer),
+        })
+    }
+
+    fn copy_to_texture(&self, source_rect: &Rect, target: &texture::TextureAnyMipmap,
+                       offset: (u32, u32)) -> Result<(), ()>
+    {
+        let source = ops::Source::DefaultFramebuffer(self.buffer);
+        ops::copy_to_texture(&self.frame.context, source, source_rect, target, offset)
+    }
+
+    fn blit_buffers<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError> where S: Surface
+    {
+        self.frame.select_read_buffer(self.buffer);
+        target.blit_from_frame(source_rect, target_rect, filter, mask)
+    }
+
+    fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                       -> Result<(), BlitError>
+    {
+        self.frame.select_draw_buffer(self.buffer);
+        ops::blit(&self.frame.context, None, None,
+                  mask, source_rect, target_rect, filter)
+    }
+
+    fn blit_from_simple_framebuffer(&self, source: &framebuffer::SimpleFrameBuffer,
+                                    source_rect: &Rect, target_rect: &BlitTarget,
+                                    filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                    -> Result<(), BlitError>
+    {
+        self.frame.select_draw_buffer(self.buffer);
+        ops::blit(&self.frame.context, source.get_attachments(), None,
+                  mask, source_rect, target_rect, filter)
+    }
+
+    fn blit_from_multioutput_framebuffer(&self, source: &framebuffer::MultiOutputFrameBuffer,
+                                         source_rect: &Rect, target_rect: &BlitTarget,
+                                         filter: uniforms::MagnifySamplerFilter, mask: BlitMask)
+                                         -> Result<(), BlitError>
+    {
+        self.frame.select_draw_buffer(self.buffer);
+        ops::blit(&self.frame.context, source.get_attachments(), None,
+                  mask, source_rect, target_rect, filter)
+    }
+}
+
 /// Objects that can build a facade object.
 pub trait DisplayBuild {
     /// The object that this `DisplayBuild` builds.
@@ -1,20 +1,77 @@
+use BlitError;
+use BlitMask;
 use BlitTarget;
 use Rect;
+use ToGlEnum;
 
 use context::Context;
 use ContextExt;
 
+use fbo;
 use fbo::FramebuffersContainer;
 use fbo::ValidatedAttachments;
 
+use uniforms::MagnifySamplerFilter;
+
 use gl;
 use version::Version;
 use version::Api;
 
 pub fn blit(context: &Context, source: Option<&ValidatedAttachments>,
-            target: Option<&ValidatedAttachments>, mask: gl::types::GLbitfield,
-            src_rect: &Rect, target_rect: &BlitTarget, filter: gl::types::GLenum)
+            target: Option<&ValidatedAttachments>, mask: BlitMask,
+            src_rect: &Rect, target_rect: &BlitTarget, filter: MagnifySamplerFilter)
+            -> Result<(), BlitError>
 {
+    if mask.depth || mask.stencil {
+        if filter != MagnifySamplerFilter::Nearest {
+            return Err(BlitError::FilterNotNearest);
+        }
+    }
+
+    // multisample surfaces can only be resolved, not written to or scaled
+    if target.and_then(|a| a.get_samples()).is_some() {
+        return Err(BlitError::MultisampledTarget);
+    }
+
+    if source.and_then(|a| a.get_samples()).is_some() {
+        if src_rect.width as i32 != target_rect.width ||
+           src_rect.height as i32 != target_rect.height
+        {
+            return Err(BlitError::MultisampleDimensionsMismatch);
+        }
+    }
+
+    // the default framebuffer's buffers are described by the capabilities
+    let default_depth = context.capabilities().depth_bits
+                               .map(|b| fbo::get_sized_depth_stencil_format(true, b, false));
+    let default_stencil = context.capabilities().stencil_bits
+                                 .map(|b| fbo::get_sized_depth_stencil_format(false, b, false));
+
+    if mask.depth {
+        let src = source.map(|a| a.get_depth_buffer_format()).unwrap_or(default_depth);
+        let dst = target.map(|a| a.get_depth_buffer_format()).unwrap_or(default_depth);
+
+        match (src, dst) {
+            (Some(src), Some(dst)) if src != dst => return Err(BlitError::DepthFormatMismatch),
+            (Some(_), Some(_)) => (),
+            _ => return Err(BlitError::NoDepthBuffer),
+        }
+    }
+
+    if mask.stencil {
+        let src = source.map(|a| a.get_stencil_buffer_format()).unwrap_or(default_stencil);
+        let dst = target.map(|a| a.get_stencil_buffer_format()).unwrap_or(default_stencil);
+
+        match (src, dst) {
+            (Some(src), Some(dst)) if src != dst => return Err(BlitError::StencilFormatMismatch),
+            (Some(_), Some(_)) => (),
+            _ => return Err(BlitError::NoStencilBuffer),
+        }
+    }
+
+    let filter = filter.to_glenum();
+    let mask = mask.to_glbitfield();
+
     unsafe {
         let mut ctxt = context.make_current();
 
@@ -39,7 +96,7 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments>,
                 (target_rect.left as i32 + target_rect.width) as gl::types::GLint,
                 (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
 
-            return;
+            return Ok(());
         }
 
         // binding source framebuffer
@@ -86,4 +143,6 @@ pub fn blit(context: &Context, source: Option<&ValidatedAttachments>,
                 (target_rect.bottom as i32 + target_rect.height) as gl::types::GLint, mask, filter);
         }
     }
+
+    Ok(())
 }
@@ -1,8 +1,9 @@
 use fbo::{self, ValidatedAttachments};
 
-use context::Context;
+use context::{Context, CommandContext};
 use ContextExt;
 use Rect;
+use framebuffer::ClearColor;
 
 use Surface;
 
@@ -20,51 +21,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments>,
 {
     unsafe {
         let mut ctxt = context.make_current();
-
-        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
-        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
-
-        if ctxt.state.enabled_rasterizer_discard {
-            ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
-            ctxt.state.enabled_rasterizer_discard = false;
-        }
-
-        if ctxt.state.color_mask != (1, 1, 1, 1) {
-            ctxt.state.color_mask = (1, 1, 1, 1);
-            ctxt.gl.ColorMask(1, 1, 1, 1);
-        }
-    
-        if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
-           ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control
-        {
-            if !ctxt.state.enabled_framebuffer_srgb {
-                ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
-                ctxt.state.enabled_framebuffer_srgb = true;
-            }
-        }
-
-        TimeElapsedQuery::end_conditional_render(&mut ctxt);
-
-        if let Some(rect) = rect {
-            let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
-                        rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
-
-            if ctxt.state.scissor != Some(rect) {
-                ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
-                ctxt.state.scissor = Some(rect);
-            }
-
-            if !ctxt.state.enabled_scissor_test {
-                ctxt.gl.Enable(gl::SCISSOR_TEST);
-                ctxt.state.enabled_scissor_test = true;
-            }
-
-        } else {
-            if ctxt.state.enabled_scissor_test {
-                ctxt.gl.Disable(gl::SCISSOR_TEST);
-                ctxt.state.enabled_scissor_test = false;
-            }
-        }
+        prepare(&mut ctxt, framebuffer, rect);
 
         let mut flags = 0;
 
@@ -117,3 +74,95 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments>,
         ctxt.gl.Clear(flags);
     }
 }
+
+/// Clears a single color attachment of a framebuffer with `glClearBuffer*v`.
+///
+/// `draw_buffer` is the index of the attachment in the list of draw buffers of `framebuffer`.
+pub fn clear_color_attachment(context: &Context, framebuffer: Option<&ValidatedAttachments>,
+                              rect: Option<&Rect>, draw_buffer: u32, value: ClearColor)
+                              -> Result<(), ()>
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0) ||
+             ctxt.version >= &Version(Api::GlEs, 3, 0))
+        {
+            return Err(());
+        }
+
+        prepare(&mut ctxt, framebuffer, rect);
+
+        let draw_buffer = draw_buffer as gl::types::GLint;
+
+        match value {
+            ClearColor::Float(r, g, b, a) => {
+                let value = [r, g, b, a];
+                ctxt.gl.ClearBufferfv(gl::COLOR, draw_buffer, value.as_ptr());
+            },
+            ClearColor::Integral(r, g, b, a) => {
+                let value = [r, g, b, a];
+                ctxt.gl.ClearBufferiv(gl::COLOR, draw_buffer, value.as_ptr());
+            },
+            ClearColor::Unsigned(r, g, b, a) => {
+                let value = [r, g, b, a];
+                ctxt.gl.ClearBufferuiv(gl::COLOR, draw_buffer, value.as_ptr());
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Binds the framebuffer and sets up the state that affects clear operations.
+unsafe fn prepare(mut ctxt: &mut CommandContext, framebuffer: Option<&ValidatedAttachments>,
+                  rect: Option<&Rect>)
+{
+    let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
+    fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+    if ctxt.state.enabled_rasterizer_discard {
+        ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
+        ctxt.state.enabled_rasterizer_discard = false;
+    }
+
+    if ctxt.state.color_mask != (1, 1, 1, 1) ||
+       ctxt.state.indexed_color_mask.iter().any(|m| m.is_some())
+    {
+        ctxt.state.color_mask = (1, 1, 1, 1);
+        ctxt.state.indexed_color_mask.clear();
+        ctxt.gl.ColorMask(1, 1, 1, 1);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
+       ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control
+    {
+        if !ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = true;
+        }
+    }
+
+    TimeElapsedQuery::end_conditional_render(&mut ctxt);
+
+    if let Some(rect) = rect {
+        let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                    rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
+
+        if ctxt.state.scissor != Some(rect) {
+            ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
+            ctxt.state.scissor = Some(rect);
+        }
+
+        if !ctxt.state.enabled_scissor_test {
+            ctxt.gl.Enable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = true;
+        }
+
+    } else {
+        if ctxt.state.enabled_scissor_test {
+            ctxt.gl.Disable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = false;
+        }
+    }
+}
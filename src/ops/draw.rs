@@ -15,13 +15,17 @@ use fbo::{self, ValidatedAttachments};
 
 use uniforms::Uniforms;
 use {Program, ToGlEnum};
-use index::{self, IndicesSource, PrimitiveType};
+use index::{self, IndicesSource, IndexType, PrimitiveType};
 use vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession};
 use vertex_array_object::VertexAttributesSystem;
 
+use context::IndexedBlendingState;
+
 use draw_parameters::DrawParameters;
 use draw_parameters::{BlendingFunction, BackfaceCullingMode};
-use draw_parameters::{DepthTest, PolygonMode, StencilTest};
+use draw_parameters::{DrawBuffer, DrawBufferParameters};
+use draw_parameters::{DepthTest, LogicOp, PolygonMode, PolygonOffset, StencilTest};
+use draw_parameters::ProvokingVertex;
 use draw_parameters::{SamplesQueryParam, TransformFeedbackPrimitivesWrittenQuery};
 use draw_parameters::{PrimitivesGeneratedQuery, TimeElapsedQuery, ConditionalRendering};
 use draw_parameters::{Smooth};
@@ -39,7 +43,7 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                       dimensions: (u32, u32)) -> Result<(), DrawError>
                       where U: Uniforms, V: MultiVerticesSource<'a>
 {
-    try!(draw_parameters::validate(context, draw_parameters));
+    try!(draw_parameters::validate(context, draw_parameters, framebuffer));
 
     // this contains the list of fences that will need to be fulfilled after the draw command
     // has started
@@ -174,20 +178,32 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
     try!(uniforms.bind_uniforms(&mut ctxt, program, &mut fences));
 
     // sync-ing draw_parameters
+    let draw_buffers = try!(get_draw_buffers_indices(program, draw_parameters.draw_buffers));
     unsafe {
         sync_depth(&mut ctxt, draw_parameters.depth_test, draw_parameters.depth_write,
                    draw_parameters.depth_range);
+        sync_depth_clamp(&mut ctxt, draw_parameters.depth_clamp);
         sync_stencil(&mut ctxt, &draw_parameters);
-        sync_blending(&mut ctxt, draw_parameters.blending_function);
-        sync_color_mask(&mut ctxt, draw_parameters.color_mask);
+        sync_blending(&mut ctxt, &draw_buffers, draw_parameters.blending_function,
+                      draw_parameters.alpha_blending_function, draw_parameters.constant_color);
+        sync_color_mask(&mut ctxt, &draw_buffers, draw_parameters.color_mask);
+        sync_draw_buffers(&mut ctxt, &draw_buffers);
+        sync_logic_operation(&mut ctxt, draw_parameters.logic_operation);
         sync_line_width(&mut ctxt, draw_parameters.line_width);
         sync_point_size(&mut ctxt, draw_parameters.point_size);
         sync_polygon_mode(&mut ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode);
+        sync_polygon_offset(&mut ctxt, draw_parameters.polygon_offset);
+        sync_provoking_vertex(&mut ctxt, draw_parameters.provoking_vertex);
+        sync_clip_planes_bitmask(&mut ctxt, draw_parameters.clip_planes_bitmask);
         sync_multisampling(&mut ctxt, draw_parameters.multisampling);
+        sync_alpha_to_coverage(&mut ctxt, draw_parameters.alpha_to_coverage);
+        sync_sample_mask(&mut ctxt, draw_parameters.sample_mask);
+        sync_sample_shading(&mut ctxt, draw_parameters.min_sample_shading);
         sync_dithering(&mut ctxt, draw_parameters.dithering);
         sync_viewport_scissor(&mut ctxt, draw_parameters.viewport, draw_parameters.scissor,
-                              dimensions);
+                              draw_parameters.viewports, draw_parameters.scissors, dimensions);
         sync_rasterizer_discard(&mut ctxt, draw_parameters.draw_primitives);
+        sync_primitive_restart(&mut ctxt, draw_parameters.primitive_restart_index, &indices);
         sync_vertices_per_patch(&mut ctxt, vertices_per_patch);
         try!(sync_queries(&mut ctxt, draw_parameters.samples_passed_query,
                           draw_parameters.time_elapsed_query,
@@ -379,6 +395,17 @@ fn sync_depth(ctxt: &mut context::CommandContext, depth_test: DepthTest, depth_w
     }
 }
 
+fn sync_depth_clamp(ctxt: &mut context::CommandContext, depth_clamp: bool) {
+    if ctxt.state.enabled_depth_clamp != depth_clamp {
+        if depth_clamp {
+            unsafe { ctxt.gl.Enable(gl::DEPTH_CLAMP) };
+        } else {
+            unsafe { ctxt.gl.Disable(gl::DEPTH_CLAMP) };
+        }
+        ctxt.state.enabled_depth_clamp = depth_clamp;
+    }
+}
+
 fn sync_stencil(ctxt: &mut context::CommandContext, params: &DrawParameters) {
     // TODO: optimize me
 
@@ -453,84 +480,136 @@ fn sync_stencil(ctxt: &mut context::CommandContext, params: &DrawParameters) {
     }
 }
 
-fn sync_blending(ctxt: &mut context::CommandContext, blending_function: Option<BlendingFunction>) {
-    let blend_factors = match blending_function {
-        Some(BlendingFunction::AlwaysReplace) => unsafe {
-            if ctxt.state.enabled_blend {
+fn sync_blending(ctxt: &mut context::CommandContext,
+                 draw_buffers: &[(gl::types::GLuint, DrawBufferParameters)],
+                 blending_function: Option<BlendingFunction>,
+                 alpha_blending_function: Option<BlendingFunction>,
+                 constant_color: (f32, f32, f32, f32))
+{
+    // if the blending of a draw buffer has been modified individually and is not going to be
+    // set again by `sync_draw_buffers`, the cached values don't apply to this draw buffer and
+    // have to be set again
+    let force = ctxt.state.indexed_blending.iter().enumerate().any(|(index, state)| {
+        state.is_some() && !draw_buffers.iter().any(|&(i, _)| i as usize == index)
+    });
+
+    let color = match blending_function {
+        Some(f) => f,
+        None => return,
+    };
+
+    let alpha = alpha_blending_function.unwrap_or(color);
+
+    if color == BlendingFunction::AlwaysReplace && alpha == BlendingFunction::AlwaysReplace {
+        unsafe {
+            if ctxt.state.enabled_blend || force {
                 ctxt.gl.Disable(gl::BLEND);
                 ctxt.state.enabled_blend = false;
+                update_indexed_blending(ctxt, |s| s.enabled = false);
             }
-            None
-        },
-        Some(BlendingFunction::Min) => unsafe {
-            if ctxt.state.blend_equation != gl::MIN {
-                ctxt.gl.BlendEquation(gl::MIN);
-                ctxt.state.blend_equation = gl::MIN;
-            }
-            if !ctxt.state.enabled_blend {
-                ctxt.gl.Enable(gl::BLEND);
-                ctxt.state.enabled_blend = true;
+
+            if force {
+                let (equation, func) = (ctxt.state.blend_equation, ctxt.state.blend_func);
+                ctxt.gl.BlendEquationSeparate(equation.0, equation.1);
+                ctxt.gl.BlendFuncSeparate(func.0, func.1, func.2, func.3);
+                update_indexed_blending(ctxt, |s| { s.equation = equation; s.func = func; });
             }
-            None
-        },
-        Some(BlendingFunction::Max) => unsafe {
-            if ctxt.state.blend_equation != gl::MAX {
-                ctxt.gl.BlendEquation(gl::MAX);
-                ctxt.state.blend_equation = gl::MAX;
+        }
+
+    } else {
+        // `Min` and `Max` ignore the blending factors, so we keep the current ones
+        let (color_equation, color_factors) = blending_function_to_gl(color);
+        let color_factors = color_factors.unwrap_or((ctxt.state.blend_func.0,
+                                                     ctxt.state.blend_func.1));
+        let (alpha_equation, alpha_factors) = blending_function_to_gl(alpha);
+        let alpha_factors = alpha_factors.unwrap_or((ctxt.state.blend_func.2,
+                                                     ctxt.state.blend_func.3));
+
+        unsafe {
+            let equation = (color_equation, alpha_equation);
+            if ctxt.state.blend_equation != equation || force {
+                ctxt.gl.BlendEquationSeparate(equation.0, equation.1);
+                ctxt.state.blend_equation = equation;
+                update_indexed_blending(ctxt, |s| s.equation = equation);
             }
-            if !ctxt.state.enabled_blend {
-                ctxt.gl.Enable(gl::BLEND);
-                ctxt.state.enabled_blend = true;
+
+            let func = (color_factors.0, color_factors.1, alpha_factors.0, alpha_factors.1);
+            if ctxt.state.blend_func != func || force {
+                ctxt.gl.BlendFuncSeparate(func.0, func.1, func.2, func.3);
+                ctxt.state.blend_func = func;
+                update_indexed_blending(ctxt, |s| s.func = func);
             }
-            None
-        },
-        Some(BlendingFunction::Addition { source, destination }) => unsafe {
-            if ctxt.state.blend_equation != gl::FUNC_ADD {
-                ctxt.gl.BlendEquation(gl::FUNC_ADD);
-                ctxt.state.blend_equation = gl::FUNC_ADD;
+
+            if ctxt.state.blend_color != constant_color {
+                ctxt.gl.BlendColor(constant_color.0, constant_color.1, constant_color.2,
+                                   constant_color.3);
+                ctxt.state.blend_color = constant_color;
             }
-            if !ctxt.state.enabled_blend {
+
+            if !ctxt.state.enabled_blend || force {
                 ctxt.gl.Enable(gl::BLEND);
                 ctxt.state.enabled_blend = true;
+                update_indexed_blending(ctxt, |s| s.enabled = true);
             }
-            Some((source, destination))
+        }
+    }
+
+    // the draw buffers whose state is now the same as the global state no longer need to be
+    // tracked individually
+    let global = global_blending_state(ctxt);
+    for state in ctxt.state.indexed_blending.iter_mut() {
+        if *state == Some(global) {
+            *state = None;
+        }
+    }
+}
+
+/// Returns the blending state that applies to the draw buffers that haven't been modified
+/// individually.
+fn global_blending_state(ctxt: &context::CommandContext) -> IndexedBlendingState {
+    IndexedBlendingState {
+        enabled: ctxt.state.enabled_blend,
+        equation: ctxt.state.blend_equation,
+        func: ctxt.state.blend_func,
+    }
+}
+
+/// Applies a modification made by a non-indexed blending function to the cached state of the
+/// draw buffers that have been modified individually.
+fn update_indexed_blending<F>(ctxt: &mut context::CommandContext, mut f: F)
+                              where F: FnMut(&mut IndexedBlendingState)
+{
+    for state in ctxt.state.indexed_blending.iter_mut() {
+        if let Some(ref mut state) = *state {
+            f(state);
+        }
+    }
+}
+
+/// Returns the blending equation and the source and destination factors, if relevant.
+fn blending_function_to_gl(function: BlendingFunction)
+                           -> (gl::types::GLenum, Option<(gl::types::GLenum, gl::types::GLenum)>)
+{
+    match function {
+        BlendingFunction::AlwaysReplace => (gl::FUNC_ADD, Some((gl::ONE, gl::ZERO))),
+        BlendingFunction::Min => (gl::MIN, None),
+        BlendingFunction::Max => (gl::MAX, None),
+        BlendingFunction::Addition { source, destination } => {
+            (gl::FUNC_ADD, Some((source.to_glenum(), destination.to_glenum())))
         },
-        Some(BlendingFunction::Subtraction { source, destination }) => unsafe {
-            if ctxt.state.blend_equation != gl::FUNC_SUBTRACT {
-                ctxt.gl.BlendEquation(gl::FUNC_SUBTRACT);
-                ctxt.state.blend_equation = gl::FUNC_SUBTRACT;
-            }
-            if !ctxt.state.enabled_blend {
-                ctxt.gl.Enable(gl::BLEND);
-                ctxt.state.enabled_blend = true;
-            }
-            Some((source, destination))
+        BlendingFunction::Subtraction { source, destination } => {
+            (gl::FUNC_SUBTRACT, Some((source.to_glenum(), destination.to_glenum())))
         },
-        Some(BlendingFunction::ReverseSubtraction { source, destination }) => unsafe {
-            if ctxt.state.blend_equation != gl::FUNC_REVERSE_SUBTRACT {
-                ctxt.gl.BlendEquation(gl::FUNC_REVERSE_SUBTRACT);
-                ctxt.state.blend_equation = gl::FUNC_REVERSE_SUBTRACT;
-            }
-            if !ctxt.state.enabled_blend {
-                ctxt.gl.Enable(gl::BLEND);
-                ctxt.state.enabled_blend = true;
-            }
-            Some((source, destination))
+        BlendingFunction::ReverseSubtraction { source, destination } => {
+            (gl::FUNC_REVERSE_SUBTRACT, Some((source.to_glenum(), destination.to_glenum())))
         },
-        _ => None
-    };
-    if let Some((source, destination)) = blend_factors {
-        let source = source.to_glenum();
-        let destination = destination.to_glenum();
-
-        if ctxt.state.blend_func != (source, destination) {
-            unsafe { ctxt.gl.BlendFunc(source, destination) };
-            ctxt.state.blend_func = (source, destination);
-        }
-    };
+    }
 }
 
-fn sync_color_mask(ctxt: &mut context::CommandContext, mask: (bool, bool, bool, bool)) {
+fn sync_color_mask(ctxt: &mut context::CommandContext,
+                   draw_buffers: &[(gl::types::GLuint, DrawBufferParameters)],
+                   mask: (bool, bool, bool, bool))
+{
     let mask = (
         if mask.0 { 1 } else { 0 },
         if mask.1 { 1 } else { 0 },
@@ -538,12 +617,169 @@ fn sync_color_mask(ctxt: &mut context::CommandContext, mask: (bool, bool, bool,
         if mask.3 { 1 } else { 0 },
     );
 
-    if ctxt.state.color_mask != mask {
+    // same as in `sync_blending`
+    let force = ctxt.state.indexed_color_mask.iter().enumerate().any(|(index, mask)| {
+        mask.is_some() && !draw_buffers.iter().any(|&(i, _)| i as usize == index)
+    });
+
+    if ctxt.state.color_mask != mask || force {
         unsafe {
             ctxt.gl.ColorMask(mask.0, mask.1, mask.2, mask.3);
         }
 
         ctxt.state.color_mask = mask;
+        ctxt.state.indexed_color_mask.clear();
+    }
+}
+
+/// Returns the index of each draw buffer of `draw_buffers` alongside with its parameters.
+fn get_draw_buffers_indices<'a>(program: &Program, draw_buffers: &[DrawBufferParameters<'a>])
+                                -> Result<Vec<(gl::types::GLuint, DrawBufferParameters<'a>)>,
+                                          DrawError>
+{
+    let mut result = Vec::with_capacity(draw_buffers.len());
+
+    for params in draw_buffers.iter() {
+        let index = match params.draw_buffer {
+            DrawBuffer::Index(index) => index,
+            DrawBuffer::Output(name) => match program.get_frag_data_location(name) {
+                Some(location) => location,
+                None => return Err(DrawError::FragmentOutputNotFound { name: name.to_string() })
+            },
+        };
+
+        result.push((index, *params));
+    }
+
+    Ok(result)
+}
+
+fn sync_draw_buffers(ctxt: &mut context::CommandContext,
+                     draw_buffers: &[(gl::types::GLuint, DrawBufferParameters)])
+{
+    // `is_draw_buffer_parameters_supported` has been checked in `validate`, so the GL 3.0
+    // functions are replaced by `GL_EXT_draw_buffers2` and the GL 4.0 functions by
+    // `GL_ARB_draw_buffers_blend`
+    let gl30 = ctxt.version >= &Version(Api::Gl, 3, 0);
+    let gl40 = ctxt.version >= &Version(Api::Gl, 4, 0);
+
+    for &(index, ref params) in draw_buffers.iter() {
+        let color = params.blending_function;
+        let alpha = params.alpha_blending_function.unwrap_or(color);
+
+        let mask = (
+            if params.color_mask.0 { 1 } else { 0 },
+            if params.color_mask.1 { 1 } else { 0 },
+            if params.color_mask.2 { 1 } else { 0 },
+            if params.color_mask.3 { 1 } else { 0 },
+        );
+
+        // blending
+        let global = global_blending_state(ctxt);
+        let current = ctxt.state.indexed_blending.get(index as usize).and_then(|s| *s)
+                                                 .unwrap_or(global);
+
+        let blending = if color == BlendingFunction::AlwaysReplace &&
+                          alpha == BlendingFunction::AlwaysReplace
+        {
+            IndexedBlendingState { enabled: false, .. current }
+
+        } else {
+            // `Min` and `Max` ignore the blending factors, so we keep the current ones
+            let (color_equation, color_factors) = blending_function_to_gl(color);
+            let color_factors = color_factors.unwrap_or((current.func.0, current.func.1));
+            let (alpha_equation, alpha_factors) = blending_function_to_gl(alpha);
+            let alpha_factors = alpha_factors.unwrap_or((current.func.2, current.func.3));
+
+            IndexedBlendingState {
+                enabled: true,
+                equation: (color_equation, alpha_equation),
+                func: (color_factors.0, color_factors.1, alpha_factors.0, alpha_factors.1),
+            }
+        };
+
+        unsafe {
+            if current.equation != blending.equation {
+                let equation = blending.equation;
+                if gl40 {
+                    ctxt.gl.BlendEquationSeparatei(index, equation.0, equation.1);
+                } else {
+                    ctxt.gl.BlendEquationSeparateiARB(index, equation.0, equation.1);
+                }
+            }
+
+            if current.func != blending.func {
+                let func = blending.func;
+                if gl40 {
+                    ctxt.gl.BlendFuncSeparatei(index, func.0, func.1, func.2, func.3);
+                } else {
+                    ctxt.gl.BlendFuncSeparateiARB(index, func.0, func.1, func.2, func.3);
+                }
+            }
+
+            if current.enabled != blending.enabled {
+                match (blending.enabled, gl30) {
+                    (true, true) => ctxt.gl.Enablei(gl::BLEND, index),
+                    (true, false) => ctxt.gl.EnableIndexedEXT(gl::BLEND, index),
+                    (false, true) => ctxt.gl.Disablei(gl::BLEND, index),
+                    (false, false) => ctxt.gl.DisableIndexedEXT(gl::BLEND, index),
+                }
+            }
+        }
+
+        for _ in (0 .. (1 + index as usize).saturating_sub(ctxt.state.indexed_blending.len())) {
+            ctxt.state.indexed_blending.push(None);
+        }
+        ctxt.state.indexed_blending[index as usize] = if blending == global {
+            None
+        } else {
+            Some(blending)
+        };
+
+        // color mask
+        let current = ctxt.state.indexed_color_mask.get(index as usize).and_then(|m| *m)
+                                                   .unwrap_or(ctxt.state.color_mask);
+
+        if current != mask {
+            unsafe {
+                if gl30 {
+                    ctxt.gl.ColorMaski(index, mask.0, mask.1, mask.2, mask.3);
+                } else {
+                    ctxt.gl.ColorMaskIndexedEXT(index, mask.0, mask.1, mask.2, mask.3);
+                }
+            }
+        }
+
+        for _ in (0 .. (1 + index as usize).saturating_sub(ctxt.state.indexed_color_mask.len())) {
+            ctxt.state.indexed_color_mask.push(None);
+        }
+        ctxt.state.indexed_color_mask[index as usize] = if mask == ctxt.state.color_mask {
+            None
+        } else {
+            Some(mask)
+        };
+    }
+}
+
+fn sync_logic_operation(ctxt: &mut context::CommandContext, operation: Option<LogicOp>) {
+    if let Some(operation) = operation {
+        let operation = operation.to_glenum();
+
+        unsafe {
+            if !ctxt.state.enabled_color_logic_op {
+                ctxt.gl.Enable(gl::COLOR_LOGIC_OP);
+                ctxt.state.enabled_color_logic_op = true;
+            }
+
+            if ctxt.state.logic_op != operation {
+                ctxt.gl.LogicOp(operation);
+                ctxt.state.logic_op = operation;
+            }
+        }
+
+    } else if ctxt.state.enabled_color_logic_op {
+        unsafe { ctxt.gl.Disable(gl::COLOR_LOGIC_OP) };
+        ctxt.state.enabled_color_logic_op = false;
     }
 }
 
@@ -614,6 +850,80 @@ fn sync_polygon_mode(ctxt: &mut context::CommandContext, backface_culling: Backf
     }
 }
 
+fn sync_polygon_offset(ctxt: &mut context::CommandContext, offset: PolygonOffset) {
+    let (factor, units) = (offset.factor, offset.units);
+
+    let enabled = offset.point || offset.line || offset.fill;
+    if enabled && ctxt.state.polygon_offset != (factor, units) {
+        unsafe { ctxt.gl.PolygonOffset(factor, units) };
+        ctxt.state.polygon_offset = (factor, units);
+    }
+
+    if offset.fill != ctxt.state.enabled_polygon_offset_fill {
+        if offset.fill {
+            unsafe { ctxt.gl.Enable(gl::POLYGON_OFFSET_FILL) };
+        } else {
+            unsafe { ctxt.gl.Disable(gl::POLYGON_OFFSET_FILL) };
+        }
+        ctxt.state.enabled_polygon_offset_fill = offset.fill;
+    }
+
+    // OpenGL ES only supports `GL_POLYGON_OFFSET_FILL`
+    if ctxt.version.0 == Api::GlEs {
+        return;
+    }
+
+    if offset.line != ctxt.state.enabled_polygon_offset_line {
+        if offset.line {
+            unsafe { ctxt.gl.Enable(gl::POLYGON_OFFSET_LINE) };
+        } else {
+            unsafe { ctxt.gl.Disable(gl::POLYGON_OFFSET_LINE) };
+        }
+        ctxt.state.enabled_polygon_offset_line = offset.line;
+    }
+
+    if offset.point != ctxt.state.enabled_polygon_offset_point {
+        if offset.point {
+            unsafe { ctxt.gl.Enable(gl::POLYGON_OFFSET_POINT) };
+        } else {
+            unsafe { ctxt.gl.Disable(gl::POLYGON_OFFSET_POINT) };
+        }
+        ctxt.state.enabled_polygon_offset_point = offset.point;
+    }
+}
+
+fn sync_provoking_vertex(ctxt: &mut context::CommandContext, provoking_vertex: ProvokingVertex) {
+    let provoking_vertex = provoking_vertex.to_glenum();
+
+    // `validate` has already checked that `First` is supported, and backends that don't
+    // support `glProvokingVertex` always use the last vertex
+    if ctxt.state.provoking_vertex != provoking_vertex {
+        unsafe { ctxt.gl.ProvokingVertex(provoking_vertex) };
+        ctxt.state.provoking_vertex = provoking_vertex;
+    }
+}
+
+fn sync_clip_planes_bitmask(ctxt: &mut context::CommandContext, bitmask: u32) {
+    if ctxt.state.enabled_clip_distances == bitmask {
+        return;
+    }
+
+    // `validate` has already checked that the bitmask is compatible with `max_clip_distances`
+    for i in 0..32 {
+        let mask = 1 << i;
+
+        if (bitmask & mask) != (ctxt.state.enabled_clip_distances & mask) {
+            if (bitmask & mask) != 0 {
+                unsafe { ctxt.gl.Enable(gl::CLIP_DISTANCE0 + i) };
+            } else {
+                unsafe { ctxt.gl.Disable(gl::CLIP_DISTANCE0 + i) };
+            }
+        }
+    }
+
+    ctxt.state.enabled_clip_distances = bitmask;
+}
+
 fn sync_multisampling(ctxt: &mut context::CommandContext, multisampling: bool) {
     if ctxt.state.enabled_multisample != multisampling {
         unsafe {
@@ -628,6 +938,73 @@ fn sync_multisampling(ctxt: &mut context::CommandContext, multisampling: bool) {
     }
 }
 
+fn sync_alpha_to_coverage(ctxt: &mut context::CommandContext, alpha_to_coverage: bool) {
+    if ctxt.state.enabled_sample_alpha_to_coverage != alpha_to_coverage {
+        unsafe {
+            if alpha_to_coverage {
+                ctxt.gl.Enable(gl::SAMPLE_ALPHA_TO_COVERAGE);
+            } else {
+                ctxt.gl.Disable(gl::SAMPLE_ALPHA_TO_COVERAGE);
+            }
+        }
+
+        ctxt.state.enabled_sample_alpha_to_coverage = alpha_to_coverage;
+    }
+}
+
+fn sync_sample_mask(ctxt: &mut context::CommandContext, mask: Option<u32>) {
+    // the support for sample masks has been checked in `validate`
+    match mask {
+        Some(mask) => {
+            if !ctxt.state.enabled_sample_mask {
+                unsafe { ctxt.gl.Enable(gl::SAMPLE_MASK) };
+                ctxt.state.enabled_sample_mask = true;
+            }
+
+            if ctxt.state.sample_mask != mask {
+                unsafe { ctxt.gl.SampleMaski(0, mask) };
+                ctxt.state.sample_mask = mask;
+            }
+        },
+        None => {
+            if ctxt.state.enabled_sample_mask {
+                unsafe { ctxt.gl.Disable(gl::SAMPLE_MASK) };
+                ctxt.state.enabled_sample_mask = false;
+            }
+        },
+    }
+}
+
+fn sync_sample_shading(ctxt: &mut context::CommandContext, min_sample_shading: Option<f32>) {
+    // the support for sample shading has been checked in `validate`
+    match min_sample_shading {
+        Some(value) => {
+            if !ctxt.state.enabled_sample_shading {
+                unsafe { ctxt.gl.Enable(gl::SAMPLE_SHADING) };
+                ctxt.state.enabled_sample_shading = true;
+            }
+
+            if ctxt.state.min_sample_shading != value {
+                unsafe {
+                    if ctxt.version >= &Version(Api::Gl, 4, 0) {
+                        ctxt.gl.MinSampleShading(value);
+                    } else {
+                        ctxt.gl.MinSampleShadingARB(value);
+                    }
+                }
+
+                ctxt.state.min_sample_shading = value;
+            }
+        },
+        None => {
+            if ctxt.state.enabled_sample_shading {
+                unsafe { ctxt.gl.Disable(gl::SAMPLE_SHADING) };
+                ctxt.state.enabled_sample_shading = false;
+            }
+        },
+    }
+}
+
 fn sync_dithering(ctxt: &mut context::CommandContext, dithering: bool) {
     if ctxt.state.enabled_dither != dithering {
         unsafe {
@@ -643,10 +1020,27 @@ fn sync_dithering(ctxt: &mut context::CommandContext, dithering: bool) {
 }
 
 fn sync_viewport_scissor(ctxt: &mut context::CommandContext, viewport: Option<Rect>,
-                         scissor: Option<Rect>, surface_dimensions: (u32, u32))
+                         scissor: Option<Rect>, viewports: &[Rect], scissors: &[Rect],
+                         surface_dimensions: (u32, u32))
 {
     // viewport
-    if let Some(viewport) = viewport {
+    if !viewports.is_empty() {
+        // the support for viewport arrays has been checked in `validate`
+        // the dimensions of the viewports have been checked in `validate` too
+        let data = viewports.iter().map(|viewport| {
+            [viewport.left as gl::types::GLfloat, viewport.bottom as gl::types::GLfloat,
+             viewport.width as gl::types::GLfloat, viewport.height as gl::types::GLfloat]
+        }).collect::<Vec<_>>();
+
+        unsafe {
+            ctxt.gl.ViewportArrayv(0, data.len() as gl::types::GLsizei,
+                                   data.as_ptr() as *const gl::types::GLfloat);
+        }
+
+        // `glViewport` modifies all the viewports, so the next call must not be skipped
+        ctxt.state.viewport = None;
+
+    } else if let Some(viewport) = viewport {
         assert!(viewport.width <= ctxt.capabilities.max_viewport_dims.0 as u32,
                 "Viewport dimensions are too large");
         assert!(viewport.height <= ctxt.capabilities.max_viewport_dims.1 as u32,
@@ -677,7 +1071,26 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext, viewport: Option<Re
     }
 
     // scissor
-    if let Some(scissor) = scissor {
+    if !scissors.is_empty() {
+        let data = scissors.iter().map(|scissor| {
+            [scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
+             scissor.width as gl::types::GLsizei, scissor.height as gl::types::GLsizei]
+        }).collect::<Vec<_>>();
+
+        unsafe {
+            ctxt.gl.ScissorArrayv(0, data.len() as gl::types::GLsizei,
+                                  data.as_ptr() as *const gl::types::GLint);
+
+            // `glScissor` modifies all the scissor boxes, so the next call must not be skipped
+            ctxt.state.scissor = None;
+
+            if !ctxt.state.enabled_scissor_test {
+                ctxt.gl.Enable(gl::SCISSOR_TEST);
+                ctxt.state.enabled_scissor_test = true;
+            }
+        }
+
+    } else if let Some(scissor) = scissor {
         let scissor = (scissor.left as gl::types::GLint, scissor.bottom as gl::types::GLint,
                        scissor.width as gl::types::GLsizei,
                        scissor.height as gl::types::GLsizei);
@@ -729,6 +1142,57 @@ fn sync_rasterizer_discard(ctxt: &mut context::CommandContext, draw_primitives:
     }
 }
 
+unsafe fn sync_primitive_restart(ctxt: &mut context::CommandContext, enabled: bool,
+                                 indices: &IndicesSource)
+{
+    // primitive restart only applies to indexed draw commands
+    let index_type = match *indices {
+        IndicesSource::IndexBuffer { data_type, .. } => data_type,
+        IndicesSource::MultidrawElement { data_type, .. } => data_type,
+        _ => return
+    };
+
+    if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+       ctxt.extensions.gl_arb_es3_compatibility
+    {
+        if ctxt.state.enabled_primitive_restart_fixed_index != enabled {
+            if enabled {
+                ctxt.gl.Enable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            } else {
+                ctxt.gl.Disable(gl::PRIMITIVE_RESTART_FIXED_INDEX);
+            }
+            ctxt.state.enabled_primitive_restart_fixed_index = enabled;
+        }
+
+    } else if ctxt.version >= &Version(Api::Gl, 3, 1) {
+        if enabled {
+            let index = match index_type {
+                IndexType::U8 => 0xff,
+                IndexType::U16 => 0xffff,
+                IndexType::U32 => 0xffffffff,
+            };
+
+            if ctxt.state.primitive_restart_index != index {
+                ctxt.gl.PrimitiveRestartIndex(index);
+                ctxt.state.primitive_restart_index = index;
+            }
+        }
+
+        if ctxt.state.enabled_primitive_restart != enabled {
+            if enabled {
+                ctxt.gl.Enable(gl::PRIMITIVE_RESTART);
+            } else {
+                ctxt.gl.Disable(gl::PRIMITIVE_RESTART);
+            }
+            ctxt.state.enabled_primitive_restart = enabled;
+        }
+
+    } else {
+        // `validate` has already returned an error if primitive restart was requested
+        debug_assert!(!enabled);
+    }
+}
+
 unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext, vertices_per_patch: Option<u16>) {
     if let Some(vertices_per_patch) = vertices_per_patch {
         let vertices_per_patch = vertices_per_patch as gl::types::GLint;
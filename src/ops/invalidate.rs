@@ -0,0 +1,93 @@
+use fbo;
+use fbo::ValidatedAttachments;
+
+use context::Context;
+use ContextExt;
+use InvalidateMask;
+use Rect;
+
+use gl;
+use version::Version;
+use version::Api;
+
+/// Invalidates some buffers of a framebuffer.
+///
+/// `dimensions` are the dimensions of the framebuffer.
+pub fn invalidate(context: &Context, framebuffer: Option<&ValidatedAttachments>,
+                  dimensions: (u32, u32), mask: InvalidateMask, rect: Option<&Rect>)
+{
+    let attachments = match framebuffer {
+        Some(framebuffer) => {
+            framebuffer.get_attachment_points(mask.color, mask.depth, mask.stencil)
+        },
+        None => {
+            // the default framebuffer only accepts `GL_COLOR`, `GL_DEPTH` and `GL_STENCIL`
+            let mut attachments = Vec::with_capacity(3);
+            if mask.color { attachments.push(gl::COLOR); }
+            if mask.depth { attachments.push(gl::DEPTH); }
+            if mask.stencil { attachments.push(gl::STENCIL); }
+            attachments
+        },
+    };
+
+    if attachments.is_empty() {
+        return;
+    }
+
+    let rect = match rect {
+        Some(r) if r.left == 0 && r.bottom == 0 && (r.width, r.height) == dimensions => None,
+        r => r,
+    };
+
+    let mut ctxt = context.make_current();
+    let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
+
+    unsafe {
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access {
+            if let Some(rect) = rect {
+                ctxt.gl.InvalidateNamedFramebufferSubData(fbo_id,
+                                                          attachments.len() as gl::types::GLsizei,
+                                                          attachments.as_ptr(),
+                                                          rect.left as gl::types::GLint,
+                                                          rect.bottom as gl::types::GLint,
+                                                          rect.width as gl::types::GLsizei,
+                                                          rect.height as gl::types::GLsizei);
+            } else {
+                ctxt.gl.InvalidateNamedFramebufferData(fbo_id,
+                                                       attachments.len() as gl::types::GLsizei,
+                                                       attachments.as_ptr());
+            }
+
+        } else if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                  ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                  ctxt.extensions.gl_arb_invalidate_subdata
+        {
+            fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+
+            if let Some(rect) = rect {
+                ctxt.gl.InvalidateSubFramebuffer(gl::FRAMEBUFFER,
+                                                 attachments.len() as gl::types::GLsizei,
+                                                 attachments.as_ptr(),
+                                                 rect.left as gl::types::GLint,
+                                                 rect.bottom as gl::types::GLint,
+                                                 rect.width as gl::types::GLsizei,
+                                                 rect.height as gl::types::GLsizei);
+            } else {
+                ctxt.gl.InvalidateFramebuffer(gl::FRAMEBUFFER,
+                                              attachments.len() as gl::types::GLsizei,
+                                              attachments.as_ptr());
+            }
+
+        } else if ctxt.extensions.gl_ext_discard_framebuffer {
+            // `glDiscardFramebufferEXT` can only discard whole buffers
+            if rect.is_none() {
+                fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
+                ctxt.gl.DiscardFramebufferEXT(gl::FRAMEBUFFER,
+                                              attachments.len() as gl::types::GLsizei,
+                                              attachments.as_ptr());
+            }
+        }
+
+        // if nothing is supported, the buffers are simply not invalidated
+    }
+}
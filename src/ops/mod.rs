@@ -1,9 +1,12 @@
 pub use self::blit::blit;
-pub use self::clear::clear;
+pub use self::clear::{clear, clear_color_attachment};
 pub use self::draw::draw;
+pub use self::invalidate::invalidate;
 pub use self::read::{read, read_if_supported, Source, Destination};
+pub use self::read::{read_surface, copy_to_texture, Aspect};
 
 mod blit;
 mod clear;
 mod draw;
+mod invalidate;
 mod read;
@@ -3,15 +3,23 @@ use std::ptr;
 use pixel_buffer::PixelBuffer;
 use texture::ClientFormat;
 use texture::PixelValue;
+use texture::TextureAnyMipmap;
 
 use fbo;
 use fbo::FramebuffersContainer;
+use fbo::ValidatedAttachments;
 
 use buffer::BufferViewAny;
 use BufferViewExt;
+use ContextExt;
+use TextureExt;
 use Rect;
+use SurfaceReadSource;
+use context::Context;
 use context::CommandContext;
 use gl;
+use version::Version;
+use version::Api;
 
 /// A source for reading pixels.
 pub enum Source<'a> {
@@ -19,6 +27,17 @@ pub enum Source<'a> {
     Attachment(&'a fbo::Attachment<'a>),
     // TODO: use a Rust enum
     DefaultFramebuffer(gl::types::GLenum),
+    /// A framebuffer object. Colors are read from the attachment whose index in the list of
+    /// draw buffers is the second parameter.
+    Framebuffer(&'a ValidatedAttachments<'a>, u32),
+}
+
+/// The buffer of the source to read from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aspect {
+    Color,
+    Depth,
+    Stencil,
 }
 
 impl<'a> From<&'a fbo::Attachment<'a>> for Source<'a> {
@@ -28,10 +47,11 @@ impl<'a> From<&'a fbo::Attachment<'a>> for Source<'a> {
 }
 
 /// A destination for reading pixels.
+///
+/// Copying to a texture is done with `copy_to_texture` instead.
 pub enum Destination<'a, P> where P: PixelValue {
     Memory(&'a mut Vec<P>),
     PixelBuffer(&'a PixelBuffer<P>),
-    // TODO: texture with glCopyTexSubImage2D
 }
 
 impl<'a, P> From<&'a mut Vec<P>> for Destination<'a, P> where P: PixelValue {
@@ -63,29 +83,63 @@ pub fn read<'a, S, D>(mut ctxt: &mut CommandContext, source: S, rect: &Rect, des
 /// Reads pixels from the source into the destination.
 ///
 /// Panicks if the destination is not large enough.
-pub fn read_if_supported<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S, rect: &Rect,
+pub fn read_if_supported<'a, S, D, T>(ctxt: &mut CommandContext, source: S, rect: &Rect,
                                       dest: D) -> Result<(), ()>
                                       where S: Into<Source<'a>>, D: Into<Destination<'a, T>>,
                                             T: PixelValue
+{
+    read_aspect_if_supported(ctxt, source, Aspect::Color, rect, dest)
+}
+
+/// Reads a rectangle of the color, depth or stencil buffer of a surface into the destination.
+///
+/// Returns `Err` if the surface can't be read or if reading this aspect with this pixel type
+/// is not supported.
+pub fn read_surface<'a, D, T>(source: Option<SurfaceReadSource<'a>>, aspect: Aspect, rect: &Rect,
+                              dest: D) -> Result<(), ()>
+                              where D: Into<Destination<'a, T>>, T: PixelValue
+{
+    let source = match source {
+        Some(s) => s,
+        None => return Err(())
+    };
+
+    let mut ctxt = source.context.make_current();
+    read_aspect_if_supported(&mut ctxt, source.source, aspect, rect, dest)
+}
+
+/// Reads pixels of the color, depth or stencil buffer of the source into the destination.
+///
+/// Panicks if the destination is not large enough.
+pub fn read_aspect_if_supported<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S,
+                                             aspect: Aspect, rect: &Rect, dest: D)
+                                             -> Result<(), ()>
+                                             where S: Into<Source<'a>>,
+                                                   D: Into<Destination<'a, T>>, T: PixelValue
 {
     let source = source.into();
     let dest = dest.into();
 
     let pixels_to_read = rect.width * rect.height;
 
+    // OpenGL ES can't read depth and stencil values with `glReadPixels`
+    if aspect != Aspect::Color && !(ctxt.version >= &Version(Api::Gl, 1, 0)) {
+        return Err(());
+    }
+
     // FIXME: check if format is supported by ReadPixels
 
     let (format, gltype) = client_format_to_gl_enum(&<T as PixelValue>::get_format());
 
-    match source {
-        Source::Attachment(attachment) => {
-            unsafe { FramebuffersContainer::bind_framebuffer_for_reading(&mut ctxt, attachment) };
-        },
-        Source::DefaultFramebuffer(read_buffer) => {
-            FramebuffersContainer::bind_default_framebuffer_for_reading(&mut ctxt, read_buffer);
-        },
+    let format = match aspect {
+        Aspect::Color => format,
+        Aspect::Depth if format == gl::RED => gl::DEPTH_COMPONENT,
+        Aspect::Stencil if format == gl::RED => gl::STENCIL_INDEX,
+        _ => return Err(())
     };
 
+    bind_source(&mut ctxt, source, aspect);
+
     unsafe {
         // reading
         match dest {
@@ -135,6 +189,80 @@ pub fn read_if_supported<'a, S, D, T>(mut ctxt: &mut CommandContext, source: S,
     Ok(())
 }
 
+/// Copies a rectangle of the source to a mipmap of a texture with `glCopyTexSubImage*`.
+///
+/// Whether colors, depth or stencil values are copied depends on the format of the texture.
+/// Returns `Err` if the type of texture is not supported, for example multisample textures.
+///
+/// # Panic
+///
+/// Panics if the rectangle at `offset` doesn't fit in the mipmap.
+pub fn copy_to_texture<'a>(context: &Context, source: Source<'a>, rect: &Rect,
+                           target: &TextureAnyMipmap, offset: (u32, u32)) -> Result<(), ()>
+{
+    let (width, height) = (target.get_width(), target.get_height().unwrap_or(1));
+    assert!(offset.0 + rect.width <= width && offset.1 + rect.height <= height,
+            "The rectangle doesn't fit in the texture");
+
+    let mut ctxt = context.make_current();
+
+    let texture = target.get_texture();
+    let bind_point = texture.get_bind_point();
+
+    match bind_point {
+        gl::TEXTURE_1D | gl::TEXTURE_2D | gl::TEXTURE_2D_ARRAY | gl::TEXTURE_3D => (),
+        _ => return Err(())
+    };
+
+    bind_source(&mut ctxt, source, Aspect::Color);
+
+    let level = target.get_level() as gl::types::GLint;
+    let (x, y) = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint);
+    let (w, h) = (rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
+    let (xoffset, yoffset) = (offset.0 as gl::types::GLint, offset.1 as gl::types::GLint);
+    let layer = target.get_layer() as gl::types::GLint;
+
+    unsafe {
+        texture.bind_to_current(&mut ctxt);
+
+        match bind_point {
+            gl::TEXTURE_1D => {
+                ctxt.gl.CopyTexSubImage1D(bind_point, level, xoffset, x, y, w);
+            },
+            gl::TEXTURE_2D => {
+                ctxt.gl.CopyTexSubImage2D(bind_point, level, xoffset, yoffset, x, y, w, h);
+            },
+            gl::TEXTURE_2D_ARRAY | gl::TEXTURE_3D => {
+                ctxt.gl.CopyTexSubImage3D(bind_point, level, xoffset, yoffset, layer, x, y, w, h);
+            },
+            _ => unreachable!()
+        }
+    }
+
+    Ok(())
+}
+
+/// Binds the source to `GL_READ_FRAMEBUFFER` and selects the buffer to read colors from.
+fn bind_source(mut ctxt: &mut CommandContext, source: Source, aspect: Aspect) {
+    match source {
+        Source::Attachment(attachment) => {
+            unsafe { FramebuffersContainer::bind_framebuffer_for_reading(&mut ctxt, attachment) };
+        },
+        Source::DefaultFramebuffer(read_buffer) => {
+            FramebuffersContainer::bind_default_framebuffer_for_reading(&mut ctxt, read_buffer);
+        },
+        Source::Framebuffer(attachments, index) => unsafe {
+            let id = FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt,
+                                                                        Some(attachments));
+            fbo::bind_framebuffer(&mut ctxt, id, false, true);
+
+            if aspect == Aspect::Color {
+                ctxt.gl.ReadBuffer(gl::COLOR_ATTACHMENT0 + index);     // TODO: cache
+            }
+        },
+    };
+}
+
 fn client_format_to_gl_enum(format: &ClientFormat) -> (gl::types::GLenum, gl::types::GLenum) {
     match *format {
         ClientFormat::U8 => (gl::RED, gl::UNSIGNED_BYTE),
@@ -25,6 +25,7 @@ use program::shader::build_shader;
 
 use program::raw::RawProgram;
 
+use uniforms::UniformType;
 use vertex::VertexFormat;
 
 /// A combination of shaders linked together.
@@ -143,6 +144,19 @@ impl Program {
         })
     }
 
+    /// Attaches a label to the program.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the program with `{:?}`.
+    pub fn set_label(&self, label: &str) {
+        self.raw.set_label(label)
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.raw.get_label()
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -166,6 +180,31 @@ impl Program {
         self.raw.get_frag_data_location(name)
     }
 
+    /// Returns the *index* of an output fragment, if it exists.
+    ///
+    /// When using dual-source blending, the outputs with an index of 1 are used by the
+    /// `SourceOneColor`, `OneMinusSourceOneColor`, `SourceOneAlpha` and
+    /// `OneMinusSourceOneAlpha` blending factors. You can declare them in your shaders by
+    /// writing:
+    ///
+    /// ```notrust
+    /// layout(location = 0, index = 1) out vec4 foo;
+    /// ```
+    ///
+    /// Returns `None` if the output doesn't exist, or if the backend doesn't support
+    /// OpenGL 3.3 or `GL_ARB_blend_func_extended`.
+    pub fn get_frag_data_index(&self, name: &str) -> Option<u32> {
+        self.raw.get_frag_data_index(name)
+    }
+
+    /// Returns the type of the fragment output `name`.
+    ///
+    /// Returns `None` if the output doesn't exist, or if the backend doesn't support
+    /// OpenGL 4.3, OpenGL ES 3.1 or `GL_ARB_program_interface_query`.
+    pub fn get_frag_data_type(&self, name: &str) -> Option<UniformType> {
+        self.raw.get_frag_data_type(name)
+    }
+
     /// Returns informations about a uniform variable, if it exists.
     pub fn get_uniform(&self, name: &str) -> Option<&Uniform> {
         self.raw.get_uniform(name)
@@ -1,6 +1,7 @@
 use gl;
 use libc;
 
+use context;
 use context::CommandContext;
 use version::Version;
 use version::Api;
@@ -33,9 +34,10 @@ use program::reflection::{Attribute, TransformFeedbackMode, TransformFeedbackBuf
 use program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use program::reflection::{reflect_transform_feedback, reflect_geometry_output_type};
 use program::reflection::{reflect_tess_eval_output_type, reflect_shader_storage_blocks};
+use program::reflection::reflect_fragment_outputs;
 use program::shader::Shader;
 
-use uniforms::Uniforms;
+use uniforms::{Uniforms, UniformType};
 
 use vertex::VertexFormat;
 use vertex_array_object::VertexAttributesSystem;
@@ -49,10 +51,12 @@ pub struct RawProgram {
     uniform_blocks: HashMap<String, UniformBlock>,
     attributes: HashMap<String, Attribute>,
     frag_data_locations: RefCell<HashMap<String, Option<u32>>>,
+    frag_data_types: HashMap<String, UniformType>,
     tf_buffers: Vec<TransformFeedbackBuffer>,
     ssbos: HashMap<String, UniformBlock>,
     output_primitives: Option<OutputPrimitives>,
     has_tessellation_shaders: bool,
+    label: RefCell<Option<String>>,
 }
 
 impl RawProgram {
@@ -154,6 +158,7 @@ impl RawProgram {
         let blocks = unsafe { reflect_uniform_blocks(&mut ctxt, id) };
         let tf_buffers = unsafe { reflect_transform_feedback(&mut ctxt, id) };
         let ssbos = unsafe { reflect_shader_storage_blocks(&mut ctxt, id) };
+        let frag_data_types = unsafe { reflect_fragment_outputs(&mut ctxt, id) };
 
         let output_primitives = if has_geometry_shader {
             Some(unsafe { reflect_geometry_output_type(&mut ctxt, id) })
@@ -171,10 +176,12 @@ impl RawProgram {
             uniform_blocks: blocks,
             attributes: attributes,
             frag_data_locations: RefCell::new(HashMap::new()),
+            frag_data_types: frag_data_types,
             tf_buffers: tf_buffers,
             ssbos: ssbos,
             output_primitives: output_primitives,
             has_tessellation_shaders: has_tessellation_shaders,
+            label: RefCell::new(None),
         })
     }
 
@@ -203,13 +210,14 @@ impl RawProgram {
             id
         };
 
-        let (uniforms, attributes, blocks, tf_buffers, ssbos) = unsafe {
+        let (uniforms, attributes, blocks, tf_buffers, ssbos, frag_data_types) = unsafe {
             (
                 reflect_uniforms(&mut ctxt, id),
                 reflect_attributes(&mut ctxt, id),
                 reflect_uniform_blocks(&mut ctxt, id),
                 reflect_transform_feedback(&mut ctxt, id),
                 reflect_shader_storage_blocks(&mut ctxt, id),
+                reflect_fragment_outputs(&mut ctxt, id),
             )
         };
 
@@ -221,13 +229,33 @@ impl RawProgram {
             uniform_blocks: blocks,
             attributes: attributes,
             frag_data_locations: RefCell::new(HashMap::new()),
+            frag_data_types: frag_data_types,
             tf_buffers: tf_buffers,
             ssbos: ssbos,
             output_primitives: None,            // FIXME: 
             has_tessellation_shaders: true,     // FIXME: 
+            label: RefCell::new(None),
         })
     }
 
+    /// Attaches a label to the program.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the program with `{:?}`.
+    pub fn set_label(&self, label: &str) {
+        if let Handle::Id(id) = self.id {
+            let mut ctxt = self.context.make_current();
+            let _ = context::set_object_label(&mut ctxt, gl::PROGRAM, id, label);
+        }
+
+        *self.label.borrow_mut() = Some(label.to_string());
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.label.borrow().clone()
+    }
+
     /// Returns the program's compiled binary.
     ///
     /// You can store the result in a file, then reload it later. This avoids having to compile
@@ -309,6 +337,50 @@ impl RawProgram {
         location
     }
 
+    /// Returns the *index* of an output fragment, if it exists.
+    ///
+    /// Outputs with an index of 1 are the second inputs of dual-source blending.
+    ///
+    /// Returns `None` if the output doesn't exist, or if the backend doesn't support
+    /// OpenGL 3.3 or `GL_ARB_blend_func_extended`.
+    pub fn get_frag_data_index(&self, name: &str) -> Option<u32> {
+        let ctxt = self.context.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 3)) &&
+           !ctxt.extensions.gl_arb_blend_func_extended
+        {
+            return None;
+        }
+
+        let name_c = ffi::CString::new(name.as_bytes()).unwrap();
+
+        let value = unsafe {
+            match self.id {
+                Handle::Id(id) => {
+                    ctxt.gl.GetFragDataIndex(id, name_c.as_bytes_with_nul().as_ptr()
+                                             as *const libc::c_char)
+                },
+                Handle::Handle(_) => {
+                    // not supported
+                    -1
+                }
+            }
+        };
+
+        match value {
+            -1 => None,
+            a => Some(a as u32),
+        }
+    }
+
+    /// Returns the type of a fragment output.
+    ///
+    /// Returns `None` if the output doesn't exist, or if the backend can't introspect the
+    /// fragment outputs of a program.
+    pub fn get_frag_data_type(&self, name: &str) -> Option<UniformType> {
+        self.frag_data_types.get(name).cloned()
+    }
+
     /// Returns informations about a uniform variable, if it exists.
     pub fn get_uniform(&self, name: &str) -> Option<&Uniform> {
         self.uniforms.get(name)
@@ -466,7 +538,10 @@ impl RawProgram {
 
 impl fmt::Debug for RawProgram {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        (format!("Program #{:?}", self.id)).fmt(formatter)
+        match *self.label.borrow() {
+            Some(ref label) => (format!("Program #{:?} ({:?})", self.id, label)).fmt(formatter),
+            None => (format!("Program #{:?}", self.id)).fmt(formatter),
+        }
     }
 }
 
@@ -734,6 +734,72 @@ pub unsafe fn reflect_shader_storage_blocks(ctxt: &mut CommandContext, program:
     blocks
 }
 
+/// Returns the type of each fragment output of a program.
+///
+/// Returns an empty list if the backend doesn't support program interface queries.
+pub unsafe fn reflect_fragment_outputs(ctxt: &mut CommandContext, program: Handle)
+                                       -> HashMap<String, UniformType>
+{
+    if !(ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+         ctxt.extensions.gl_arb_program_interface_query)
+    {
+        // not supported
+        return HashMap::with_capacity(0);
+    }
+
+    let program = match program {
+        Handle::Id(program) => program,
+        Handle::Handle(program) => return HashMap::with_capacity(0)
+    };
+
+    // number of active outputs
+    let active_outputs = {
+        let mut active_outputs: gl::types::GLint = mem::uninitialized();
+        ctxt.gl.GetProgramInterfaceiv(program, gl::PROGRAM_OUTPUT,
+                                      gl::ACTIVE_RESOURCES, &mut active_outputs);
+        active_outputs as gl::types::GLuint
+    };
+
+    // the result of this function
+    let mut outputs = HashMap::with_capacity(active_outputs as usize);
+
+    for output_id in (0 .. active_outputs) {
+        let (ty, name_len) = {
+            let mut output: [gl::types::GLint; 2] = mem::uninitialized();
+            ctxt.gl.GetProgramResourceiv(program, gl::PROGRAM_OUTPUT, output_id, 2,
+                                         [gl::TYPE, gl::NAME_LENGTH].as_ptr(), 2,
+                                         ptr::null_mut(), output.as_mut_ptr() as *mut _);
+            (output[0] as gl::types::GLenum, output[1] as usize)
+        };
+
+        let mut name = {
+            let mut name_tmp: Vec<u8> = Vec::with_capacity(1 + name_len);
+            let mut name_tmp_len = name_len as gl::types::GLsizei;
+
+            ctxt.gl.GetProgramResourceName(program, gl::PROGRAM_OUTPUT, output_id,
+                                           name_tmp_len, &mut name_tmp_len,
+                                           name_tmp.as_mut_ptr() as *mut _);
+            name_tmp.set_len(name_tmp_len as usize);
+            String::from_utf8(name_tmp).unwrap()
+        };
+
+        // built-in outputs like `gl_FragDepth` don't go to a color attachment
+        if name.starts_with("gl_") {
+            continue;
+        }
+
+        // arrays of outputs are reported as `name[0]`
+        if name.ends_with("[0]") {
+            let len = name.len();
+            name.truncate(len - 3);
+        }
+
+        outputs.insert(name, glenum_to_uniform_type(ty));
+    }
+
+    outputs
+}
+
 /// Takes a list of elements produced by OpenGL's introspection API and turns them into
 /// a `BlockLayout` object.
 ///
@@ -0,0 +1,336 @@
+/*!
+A render queue records draw commands and executes them later in an order that minimizes the
+number of state changes.
+
+Switching between programs, draw parameters or textures has a cost. If your scene contains
+lots of objects drawn in an arbitrary order, you can record the draw commands in a
+`RenderQueue` instead of submitting them directly to the surface. When you call `submit`, the
+commands are sorted by program, then by draw parameters, then by textures, and are executed
+one by one on the surface.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::uninitialized() };
+# let vertex_buffer: glium::vertex::VertexBufferAny = unsafe { ::std::mem::uninitialized() };
+# let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+# let program: glium::Program = unsafe { ::std::mem::uninitialized() };
+# let texture: glium::Texture2d = unsafe { ::std::mem::uninitialized() };
+use glium::Surface;
+
+let mut queue = glium::RenderQueue::new();
+queue.draw(&vertex_buffer, &indices, &program, uniform! { tex: &texture },
+           &Default::default());
+
+let mut target = display.draw();
+let stats = queue.submit(&mut target).unwrap();
+println!("saved {} state changes", stats.state_changes_saved);
+target.finish().unwrap();
+```
+
+Note that the order in which the commands are executed is not preserved. You shouldn't use
+a render queue for commands whose result depends on the order of execution, for example
+commands that use blending without being commutative, or that write to a transform feedback
+buffer.
+
+*/
+use std::collections::HashMap;
+use std::vec::IntoIter;
+
+use DrawError;
+use Program;
+use Surface;
+use UniformValueExt;
+
+use draw_parameters::DrawParameters;
+use index::IndicesSource;
+use uniforms::{Uniforms, UniformValue};
+use vertex::{MultiVerticesSource, VerticesSource};
+
+use GlObject;
+use gl;
+
+/// Records draw commands and executes them sorted by state.
+///
+/// See the documentation of the module for more infos.
+pub struct RenderQueue<'a> {
+    commands: Vec<Command<'a>>,
+}
+
+/// Statistics about the execution of a `RenderQueue`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RenderQueueStats {
+    /// Number of draw commands that have been executed.
+    pub draws: usize,
+
+    /// Number of program, draw parameters and textures changes between two consecutive
+    /// commands in the order of execution.
+    pub state_changes: usize,
+
+    /// Number of state changes that have been avoided compared to executing the commands in
+    /// the order in which they were recorded.
+    pub state_changes_saved: usize,
+}
+
+/// A recorded draw command.
+struct Command<'a> {
+    vertices: Vec<VerticesSource<'a>>,
+    indices: IndicesSource<'a>,
+    program: &'a Program,
+    uniforms: Box<RecordedUniforms + 'a>,
+    draw_parameters: DrawParameters<'a>,
+
+    /// Ids of the textures used by the uniforms, ordered by uniform name.
+    textures: Vec<gl::types::GLuint>,
+}
+
+/// Sort key of a command. Each element is the index of the first recorded command that has
+/// the same program, draw parameters or textures.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct SortKey {
+    program: usize,
+    draw_parameters: usize,
+    textures: usize,
+}
+
+impl<'a> RenderQueue<'a> {
+    /// Builds a new empty queue.
+    pub fn new() -> RenderQueue<'a> {
+        RenderQueue {
+            commands: Vec::new(),
+        }
+    }
+
+    /// Records a draw command.
+    ///
+    /// The parameters are the same as `Surface::draw`, except that the uniforms are moved
+    /// inside the queue. Nothing is drawn until you call `submit`.
+    pub fn draw<V, I, U>(&mut self, vertices: V, indices: I, program: &'a Program, uniforms: U,
+                         draw_parameters: &DrawParameters<'a>)
+                         where V: MultiVerticesSource<'a>, I: Into<IndicesSource<'a>>,
+                               U: Uniforms + 'a
+    {
+        let mut textures = Vec::new();
+        uniforms.visit_values(|name, value| {
+            if let Some(texture) = value.get_texture() {
+                textures.push((name.to_string(), texture.get_id()));
+            }
+        });
+        textures.sort_by(|a, b| a.0.cmp(&b.0));
+
+        self.commands.push(Command {
+            vertices: vertices.iter().collect(),
+            indices: indices.into(),
+            program: program,
+            uniforms: Box::new(uniforms),
+            draw_parameters: *draw_parameters,
+            textures: textures.into_iter().map(|(_, id)| id).collect(),
+        });
+    }
+
+    /// Returns the number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if no command has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Sorts the recorded commands and executes them on the surface.
+    ///
+    /// If sorting doesn't reduce the number of state changes, the commands are executed in
+    /// the order in which they were recorded.
+    ///
+    /// Returns the first error that happens. The remaining commands are not executed.
+    pub fn submit<S>(self, surface: &mut S) -> Result<RenderQueueStats, DrawError>
+                     where S: Surface
+    {
+        let keys = build_sort_keys(&self.commands);
+        let recorded_changes = count_state_changes(&keys);
+
+        let mut order = (0 .. keys.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| keys[a].cmp(&keys[b]));     // stable
+        let sorted_keys = order.iter().map(|&i| keys[i]).collect::<Vec<_>>();
+        let sorted_changes = count_state_changes(&sorted_keys);
+
+        let (order, state_changes) = if sorted_changes < recorded_changes {
+            (order, sorted_changes)
+        } else {
+            ((0 .. keys.len()).collect(), recorded_changes)
+        };
+
+        let mut commands = self.commands.into_iter().map(|c| Some(c)).collect::<Vec<_>>();
+        for index in order {
+            let command = commands[index].take().unwrap();
+            try!(surface.draw(RecordedVertices(command.vertices), command.indices,
+                              command.program, &RecordedUniformsRef(&*command.uniforms),
+                              &command.draw_parameters));
+        }
+
+        Ok(RenderQueueStats {
+            draws: keys.len(),
+            state_changes: state_changes,
+            state_changes_saved: recorded_changes - state_changes,
+        })
+    }
+}
+
+/// Builds the sort key of each command.
+fn build_sort_keys(commands: &[Command]) -> Vec<SortKey> {
+    let mut keys: Vec<SortKey> = Vec::with_capacity(commands.len());
+
+    // index of the first command that uses each program and each list of textures
+    let mut programs: HashMap<*const Program, usize> = HashMap::new();
+    let mut textures: HashMap<Vec<gl::types::GLuint>, usize> = HashMap::new();
+
+    // draw parameters can't be hashed, so we compare them with each distinct value that has
+    // been found so far
+    let mut draw_parameters: Vec<(&DrawParameters, usize)> = Vec::new();
+
+    for (index, command) in commands.iter().enumerate() {
+        let program = *programs.entry(command.program as *const Program).or_insert(index);
+
+        let params = draw_parameters.iter().find(|&&(p, _)| {
+            same_draw_parameters(p, &command.draw_parameters)
+        }).map(|&(_, first)| first);
+
+        let params = match params {
+            Some(first) => first,
+            None => {
+                draw_parameters.push((&command.draw_parameters, index));
+                index
+            },
+        };
+
+        let textures = *textures.entry(command.textures.clone()).or_insert(index);
+
+        keys.push(SortKey {
+            program: program,
+            draw_parameters: params,
+            textures: textures,
+        });
+    }
+
+    keys
+}
+
+/// Counts the number of state changes when executing commands in the given order.
+fn count_state_changes(keys: &[SortKey]) -> usize {
+    keys.windows(2).fold(0, |count, w| {
+        count + (w[0].program != w[1].program) as usize +
+        (w[0].draw_parameters != w[1].draw_parameters) as usize +
+        (w[0].textures != w[1].textures) as usize
+    })
+}
+
+/// Returns true if drawing with `a` and `b` results in the same state.
+///
+/// Parameters that use queries, conditional rendering or transform feedback are never
+/// considered equal, as they hold references to objects that are modified by the draw.
+fn same_draw_parameters(a: &DrawParameters, b: &DrawParameters) -> bool {
+    // all the members are listed, so that adding a member to `DrawParameters` requires
+    // updating this function
+    let DrawParameters {
+        depth_test, depth_write, depth_range, depth_clamp, stencil_test_clockwise,
+        stencil_reference_value_clockwise, stencil_write_mask_clockwise,
+        stencil_fail_operation_clockwise, stencil_pass_depth_fail_operation_clockwise,
+        stencil_depth_pass_operation_clockwise, stencil_test_counter_clockwise,
+        stencil_reference_value_counter_clockwise, stencil_write_mask_counter_clockwise,
+        stencil_fail_operation_counter_clockwise,
+        stencil_pass_depth_fail_operation_counter_clockwise,
+        stencil_depth_pass_operation_counter_clockwise, blending_function,
+        alpha_blending_function, constant_color, color_mask, draw_buffers, logic_operation,
+        line_width, point_size, backface_culling, polygon_mode, provoking_vertex,
+        polygon_offset, multisampling, alpha_to_coverage, sample_mask, min_sample_shading,
+        dithering, viewport, scissor, viewports, scissors, draw_primitives,
+        samples_passed_query, time_elapsed_query, primitives_generated_query,
+        transform_feedback_primitives_written_query, condition, transform_feedback,
+        clip_planes_bitmask, primitive_restart_index, smooth,
+    } = *a;
+
+    if samples_passed_query.is_some() || b.samples_passed_query.is_some() ||
+       time_elapsed_query.is_some() || b.time_elapsed_query.is_some() ||
+       primitives_generated_query.is_some() || b.primitives_generated_query.is_some() ||
+       transform_feedback_primitives_written_query.is_some() ||
+       b.transform_feedback_primitives_written_query.is_some() ||
+       condition.is_some() || b.condition.is_some() ||
+       transform_feedback.is_some() || b.transform_feedback.is_some()
+    {
+        return false;
+    }
+
+    depth_test == b.depth_test &&
+    depth_write == b.depth_write &&
+    depth_range == b.depth_range &&
+    depth_clamp == b.depth_clamp &&
+    stencil_test_clockwise == b.stencil_test_clockwise &&
+    stencil_reference_value_clockwise == b.stencil_reference_value_clockwise &&
+    stencil_write_mask_clockwise == b.stencil_write_mask_clockwise &&
+    stencil_fail_operation_clockwise == b.stencil_fail_operation_clockwise &&
+    stencil_pass_depth_fail_operation_clockwise == b.stencil_pass_depth_fail_operation_clockwise &&
+    stencil_depth_pass_operation_clockwise == b.stencil_depth_pass_operation_clockwise &&
+    stencil_test_counter_clockwise == b.stencil_test_counter_clockwise &&
+    stencil_reference_value_counter_clockwise == b.stencil_reference_value_counter_clockwise &&
+    stencil_write_mask_counter_clockwise == b.stencil_write_mask_counter_clockwise &&
+    stencil_fail_operation_counter_clockwise == b.stencil_fail_operation_counter_clockwise &&
+    stencil_pass_depth_fail_operation_counter_clockwise ==
+        b.stencil_pass_depth_fail_operation_counter_clockwise &&
+    stencil_depth_pass_operation_counter_clockwise ==
+        b.stencil_depth_pass_operation_counter_clockwise &&
+    blending_function == b.blending_function &&
+    alpha_blending_function == b.alpha_blending_function &&
+    constant_color == b.constant_color &&
+    color_mask == b.color_mask &&
+    draw_buffers == b.draw_buffers &&
+    logic_operation == b.logic_operation &&
+    line_width == b.line_width &&
+    point_size == b.point_size &&
+    backface_culling == b.backface_culling &&
+    polygon_mode == b.polygon_mode &&
+    provoking_vertex == b.provoking_vertex &&
+    polygon_offset == b.polygon_offset &&
+    multisampling == b.multisampling &&
+    alpha_to_coverage == b.alpha_to_coverage &&
+    sample_mask == b.sample_mask &&
+    min_sample_shading == b.min_sample_shading &&
+    dithering == b.dithering &&
+    viewport == b.viewport &&
+    scissor == b.scissor &&
+    viewports == b.viewports &&
+    scissors == b.scissors &&
+    draw_primitives == b.draw_primitives &&
+    clip_planes_bitmask == b.clip_planes_bitmask &&
+    primitive_restart_index == b.primitive_restart_index &&
+    smooth == b.smooth
+}
+
+/// List of vertex sources of a recorded command.
+struct RecordedVertices<'a>(Vec<VerticesSource<'a>>);
+
+impl<'a> MultiVerticesSource<'a> for RecordedVertices<'a> {
+    type Iterator = IntoIter<VerticesSource<'a>>;
+
+    fn iter(self) -> IntoIter<VerticesSource<'a>> {
+        self.0.into_iter()
+    }
+}
+
+/// Object-safe version of `Uniforms`, so that commands can store uniforms of any type.
+trait RecordedUniforms {
+    fn visit<'b>(&'b self, output: &mut FnMut(&str, UniformValue<'b>));
+}
+
+impl<U> RecordedUniforms for U where U: Uniforms {
+    fn visit<'b>(&'b self, output: &mut FnMut(&str, UniformValue<'b>)) {
+        self.visit_values(|name, value| output(name, value));
+    }
+}
+
+/// Wrapper that implements `Uniforms` for recorded uniforms.
+struct RecordedUniformsRef<'r, 'a: 'r>(&'r (RecordedUniforms + 'a));
+
+impl<'r, 'a: 'r> Uniforms for RecordedUniformsRef<'r, 'a> {
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut output: F) {
+        self.0.visit(&mut output);
+    }
+}
@@ -3,6 +3,7 @@ use GlObject;
 
 use backend::Facade;
 use version::Version;
+use context;
 use context::Context;
 use context::CommandContext;
 use ContextExt;
@@ -26,7 +27,7 @@ use std::fmt;
 use std::mem;
 use std::ptr;
 use std::borrow::Cow;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use ops;
@@ -49,6 +50,9 @@ pub struct TextureAny {
     levels: u32,
     /// Is automatic mipmap generation allowed for this texture?
     generate_mipmaps: bool,
+
+    /// Label attached with `set_label`.
+    label: RefCell<Option<String>>,
 }
 
 /// Represents a specific mipmap of a texture.
@@ -82,6 +86,8 @@ pub enum Dimensions {
     Texture2dMultisample { width: u32, height: u32, samples: u32 },
     Texture2dMultisampleArray { width: u32, height: u32, array_size: u32, samples: u32 },
     Texture3d { width: u32, height: u32, depth: u32 },
+    Cubemap { dimension: u32 },
+    CubemapArray { dimension: u32, array_size: u32 },
 }
 
 /// Builds a new texture.
@@ -104,6 +110,8 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         Dimensions::Texture2dMultisample { width, height, samples } => (width, Some(height), None, None, Some(samples)),
         Dimensions::Texture2dMultisampleArray { width, height, array_size, samples } => (width, Some(height), None, Some(array_size), Some(samples)),
         Dimensions::Texture3d { width, height, depth } => (width, Some(height), Some(depth), None, None),
+        Dimensions::Cubemap { dimension } => (dimension, Some(dimension), None, None, None),
+        Dimensions::CubemapArray { dimension, array_size } => (dimension, Some(dimension), None, Some(array_size), None),
     };
 
     let (is_client_compressed, data_bufsize) = match data {
@@ -130,8 +138,18 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         Dimensions::Texture2dMultisample { .. } => gl::TEXTURE_2D_MULTISAMPLE,
         Dimensions::Texture2dMultisampleArray { .. } => gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
         Dimensions::Texture3d { .. } => gl::TEXTURE_3D,
+        Dimensions::Cubemap { .. } => gl::TEXTURE_CUBE_MAP,
+        Dimensions::CubemapArray { .. } => gl::TEXTURE_CUBE_MAP_ARRAY,
     };
 
+    // checking support for cubemap arrays
+    if bind_point == gl::TEXTURE_CUBE_MAP_ARRAY &&
+        !(facade.get_context().get_version() >= &Version(Api::Gl, 4, 0) ||
+          facade.get_context().get_extensions().gl_arb_texture_cube_map_array)
+    {
+        return Err(TextureCreationError::TypeNotSupported);
+    }
+
     // checking non-power-of-two
     if facade.get_context().get_version() < &Version(Api::Gl, 2, 0) &&
         !facade.get_context().get_extensions().gl_arb_texture_non_power_of_two
@@ -363,6 +381,52 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
                 unreachable!();
             }
 
+        } else if bind_point == gl::TEXTURE_CUBE_MAP {
+            assert!(data_raw.is_null());
+
+            let dimension = match width as gl::types::GLsizei {
+                0 => 1,
+                a => a
+            };
+
+            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+                ctxt.gl.TexStorage2D(bind_point, texture_levels,
+                                     storage_internal_format.unwrap() as gl::types::GLenum,
+                                     dimension, dimension);
+
+            } else {
+                for face in (0 .. 6) {
+                    ctxt.gl.TexImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + face, 0,
+                                       teximg_internal_format as i32, dimension, dimension, 0,
+                                       client_format as u32, client_type, ptr::null());
+                }
+            }
+
+        } else if bind_point == gl::TEXTURE_CUBE_MAP_ARRAY {
+            assert!(data_raw.is_null());
+
+            let dimension = match width as gl::types::GLsizei {
+                0 => 1,
+                a => a
+            };
+
+            // each layer of a cubemap array is made of six faces
+            let layer_faces = match array_size.unwrap() as gl::types::GLsizei {
+                0 => 6,
+                a => a * 6
+            };
+
+            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+                ctxt.gl.TexStorage3D(bind_point, texture_levels,
+                                     storage_internal_format.unwrap() as gl::types::GLenum,
+                                     dimension, dimension, layer_faces);
+
+            } else {
+                ctxt.gl.TexImage3D(bind_point, 0, teximg_internal_format as i32, dimension,
+                                   dimension, layer_faces, 0, client_format as u32, client_type,
+                                   ptr::null());
+            }
+
         } else if bind_point == gl::TEXTURE_1D {
             let mut data_raw = data_raw;
 
@@ -425,6 +489,7 @@ pub fn new_texture<'a, F, P>(facade: &F, format: TextureFormatRequest,
         ty: ty,
         levels: texture_levels as u32,
         generate_mipmaps: generate_mipmaps,
+        label: RefCell::new(None),
     })
 }
 
@@ -443,6 +508,16 @@ impl<'a> TextureAnyMipmap<'a> {
     pub fn get_layer(&self) -> u32 {
         self.layer
     }
+
+    /// Returns the width of this mipmap level.
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of this mipmap level, or `None` for one-dimensional textures.
+    pub fn get_height(&self) -> Option<u32> {
+        self.height
+    }
 }
 
 impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
@@ -633,6 +708,21 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
 }
 
 impl TextureAny {
+    /// Attaches a label to the texture.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the texture with `{:?}`.
+    pub fn set_label(&self, label: &str) {
+        let mut ctxt = self.context.make_current();
+        let _ = context::set_object_label(&mut ctxt, gl::TEXTURE, self.id, label);
+        *self.label.borrow_mut() = Some(label.to_string());
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.label.borrow().clone()
+    }
+
     /// Returns the width of the texture.
     pub fn get_width(&self) -> u32 {
         match self.ty {
@@ -643,6 +733,8 @@ impl TextureAny {
             Dimensions::Texture2dMultisample { width, .. } => width,
             Dimensions::Texture2dMultisampleArray { width, .. } => width,
             Dimensions::Texture3d { width, .. } => width,
+            Dimensions::Cubemap { dimension } => dimension,
+            Dimensions::CubemapArray { dimension, .. } => dimension,
         }
     }
 
@@ -656,6 +748,8 @@ impl TextureAny {
             Dimensions::Texture2dMultisample { height, .. } => Some(height),
             Dimensions::Texture2dMultisampleArray { height, .. } => Some(height),
             Dimensions::Texture3d { height, .. } => Some(height),
+            Dimensions::Cubemap { dimension } => Some(dimension),
+            Dimensions::CubemapArray { dimension, .. } => Some(dimension),
         }
     }
 
@@ -677,6 +771,8 @@ impl TextureAny {
             Dimensions::Texture2dMultisample { .. } => None,
             Dimensions::Texture2dMultisampleArray { array_size, .. } => Some(array_size),
             Dimensions::Texture3d { .. } => None,
+            Dimensions::Cubemap { .. } => None,
+            Dimensions::CubemapArray { array_size, .. } => Some(array_size),
         }
     }
 
@@ -741,9 +837,15 @@ impl TextureExt for TextureAny {
             Dimensions::Texture2dMultisample { .. } => gl::TEXTURE_2D_MULTISAMPLE,
             Dimensions::Texture2dMultisampleArray { .. } => gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
             Dimensions::Texture3d { .. } => gl::TEXTURE_3D,
+            Dimensions::Cubemap { .. } => gl::TEXTURE_CUBE_MAP,
+            Dimensions::CubemapArray { .. } => gl::TEXTURE_CUBE_MAP_ARRAY,
         }
     }
 
+    fn get_requested_format(&self) -> TextureFormatRequest {
+        self.requested_format
+    }
+
     fn bind_to_current(&self, ctxt: &mut CommandContext) -> gl::types::GLenum {
         let bind_point = self.get_bind_point();
 
@@ -766,9 +868,15 @@ impl GlObject for TextureAny {
 
 impl fmt::Debug for TextureAny {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "Texture #{} (dimensions: {}x{}x{}x{})", self.id,
-               self.get_width(), self.get_height().unwrap_or(1), self.get_depth().unwrap_or(1),
-               self.get_array_size().unwrap_or(1))
+        try!(write!(fmt, "Texture #{} (dimensions: {}x{}x{}x{}", self.id,
+                    self.get_width(), self.get_height().unwrap_or(1),
+                    self.get_depth().unwrap_or(1), self.get_array_size().unwrap_or(1)));
+
+        if let Some(ref label) = *self.label.borrow() {
+            try!(write!(fmt, ", label: {:?}", label));
+        }
+
+        write!(fmt, ")")
     }
 }
 
@@ -136,6 +136,13 @@ pub fn get_format(ctxt: &mut CommandContext, texture: &TextureAny)
 
             let bind_point = texture.bind_to_current(ctxt);
 
+            // the parameters of a cubemap must be queried on one of its faces
+            let bind_point = if bind_point == gl::TEXTURE_CUBE_MAP {
+                gl::TEXTURE_CUBE_MAP_POSITIVE_X
+            } else {
+                bind_point
+            };
+
             let mut red_sz = mem::uninitialized();
             ctxt.gl.GetTexLevelParameteriv(bind_point, 0, gl::TEXTURE_RED_SIZE, &mut red_sz);
 
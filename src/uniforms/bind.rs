@@ -361,6 +361,36 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext,
         UniformValue::DepthTexture2dMultisampleArray(texture, sampler) => {
             bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_2D_MULTISAMPLE_ARRAY)
         },
+        UniformValue::Cubemap(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::SrgbCubemap(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::IntegralCubemap(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::UnsignedCubemap(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::DepthCubemap(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP)
+        },
+        UniformValue::CubemapArray(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::SrgbCubemapArray(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::IntegralCubemapArray(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::UnsignedCubemapArray(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
+        UniformValue::DepthCubemapArray(texture, sampler) => {
+            bind_texture_uniform(ctxt, texture, sampler, location, program, texture_bind_points, gl::TEXTURE_CUBE_MAP_ARRAY)
+        },
     }
 }
 
@@ -7,6 +7,8 @@ use uniforms::LayoutMismatchError;
 use uniforms::UniformBlock;
 use uniforms::SamplerBehavior;
 
+use UniformValueExt;
+
 use buffer::BufferViewAnySlice;
 
 #[cfg(feature = "cgmath")]
@@ -195,6 +197,16 @@ pub enum UniformValue<'a> {
     IntegralTexture2dMultisampleArray(&'a texture::IntegralTexture2dMultisampleArray, Option<SamplerBehavior>),
     UnsignedTexture2dMultisampleArray(&'a texture::UnsignedTexture2dMultisampleArray, Option<SamplerBehavior>),
     DepthTexture2dMultisampleArray(&'a texture::DepthTexture2dMultisampleArray, Option<SamplerBehavior>),
+    Cubemap(&'a texture::Cubemap, Option<SamplerBehavior>),
+    SrgbCubemap(&'a texture::SrgbCubemap, Option<SamplerBehavior>),
+    IntegralCubemap(&'a texture::IntegralCubemap, Option<SamplerBehavior>),
+    UnsignedCubemap(&'a texture::UnsignedCubemap, Option<SamplerBehavior>),
+    DepthCubemap(&'a texture::DepthCubemap, Option<SamplerBehavior>),
+    CubemapArray(&'a texture::CubemapArray, Option<SamplerBehavior>),
+    SrgbCubemapArray(&'a texture::SrgbCubemapArray, Option<SamplerBehavior>),
+    IntegralCubemapArray(&'a texture::IntegralCubemapArray, Option<SamplerBehavior>),
+    UnsignedCubemapArray(&'a texture::UnsignedCubemapArray, Option<SamplerBehavior>),
+    DepthCubemapArray(&'a texture::DepthCubemapArray, Option<SamplerBehavior>),
 }
 
 impl<'a> Clone for UniformValue<'a> {
@@ -251,11 +263,84 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::IntegralTexture2dArray(_, _), UniformType::ISampler2dArray) => true,
             (&UniformValue::UnsignedTexture2dArray(_, _), UniformType::USampler2dArray) => true,
             (&UniformValue::DepthTexture2dArray(_, _), UniformType::Sampler2dArray) => true,
+            (&UniformValue::Cubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::SrgbCubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::IntegralCubemap(_, _), UniformType::ISamplerCube) => true,
+            (&UniformValue::UnsignedCubemap(_, _), UniformType::USamplerCube) => true,
+            (&UniformValue::DepthCubemap(_, _), UniformType::SamplerCube) => true,
+            (&UniformValue::CubemapArray(_, _), UniformType::SamplerCubeArray) => true,
+            (&UniformValue::SrgbCubemapArray(_, _), UniformType::SamplerCubeArray) => true,
+            (&UniformValue::IntegralCubemapArray(_, _), UniformType::ISamplerCubeArray) => true,
+            (&UniformValue::UnsignedCubemapArray(_, _), UniformType::USamplerCubeArray) => true,
+            (&UniformValue::DepthCubemapArray(_, _), UniformType::SamplerCubeArray) => true,
             _ => false,
         }
     }
 }
 
+impl<'a> UniformValueExt<'a> for UniformValue<'a> {
+    fn get_texture(&self) -> Option<&'a texture::TextureAny> {
+        match *self {
+            UniformValue::Texture1d(texture, _) => Some(&**texture),
+            UniformValue::CompressedTexture1d(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture1d(texture, _) => Some(&**texture),
+            UniformValue::CompressedSrgbTexture1d(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture1d(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture1d(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture1d(texture, _) => Some(&**texture),
+            UniformValue::Texture2d(texture, _) => Some(&**texture),
+            UniformValue::CompressedTexture2d(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture2d(texture, _) => Some(&**texture),
+            UniformValue::CompressedSrgbTexture2d(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture2d(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture2d(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture2d(texture, _) => Some(&**texture),
+            UniformValue::Texture2dMultisample(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture2dMultisample(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture2dMultisample(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture2dMultisample(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture2dMultisample(texture, _) => Some(&**texture),
+            UniformValue::Texture3d(texture, _) => Some(&**texture),
+            UniformValue::CompressedTexture3d(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture3d(texture, _) => Some(&**texture),
+            UniformValue::CompressedSrgbTexture3d(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture3d(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture3d(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture3d(texture, _) => Some(&**texture),
+            UniformValue::Texture1dArray(texture, _) => Some(&**texture),
+            UniformValue::CompressedTexture1dArray(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture1dArray(texture, _) => Some(&**texture),
+            UniformValue::CompressedSrgbTexture1dArray(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture1dArray(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture1dArray(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture1dArray(texture, _) => Some(&**texture),
+            UniformValue::Texture2dArray(texture, _) => Some(&**texture),
+            UniformValue::CompressedTexture2dArray(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture2dArray(texture, _) => Some(&**texture),
+            UniformValue::CompressedSrgbTexture2dArray(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture2dArray(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture2dArray(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture2dArray(texture, _) => Some(&**texture),
+            UniformValue::Texture2dMultisampleArray(texture, _) => Some(&**texture),
+            UniformValue::SrgbTexture2dMultisampleArray(texture, _) => Some(&**texture),
+            UniformValue::IntegralTexture2dMultisampleArray(texture, _) => Some(&**texture),
+            UniformValue::UnsignedTexture2dMultisampleArray(texture, _) => Some(&**texture),
+            UniformValue::DepthTexture2dMultisampleArray(texture, _) => Some(&**texture),
+            UniformValue::Cubemap(texture, _) => Some(&**texture),
+            UniformValue::SrgbCubemap(texture, _) => Some(&**texture),
+            UniformValue::IntegralCubemap(texture, _) => Some(&**texture),
+            UniformValue::UnsignedCubemap(texture, _) => Some(&**texture),
+            UniformValue::DepthCubemap(texture, _) => Some(&**texture),
+            UniformValue::CubemapArray(texture, _) => Some(&**texture),
+            UniformValue::SrgbCubemapArray(texture, _) => Some(&**texture),
+            UniformValue::IntegralCubemapArray(texture, _) => Some(&**texture),
+            UniformValue::UnsignedCubemapArray(texture, _) => Some(&**texture),
+            UniformValue::DepthCubemapArray(texture, _) => Some(&**texture),
+            _ => None,
+        }
+    }
+}
+
 macro_rules! impl_uniform_block_basic {
     ($ty:ty, $uniform_ty:expr) => (
         impl UniformBlock for $ty {
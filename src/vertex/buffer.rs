@@ -352,6 +352,19 @@ impl VertexBufferAny {
         &self.bindings
     }
 
+    /// Attaches a label to the buffer.
+    ///
+    /// The label is passed to `glObjectLabel` if the backend supports it, so that debugging
+    /// tools can display it. It is also shown when printing the buffer with `{:?}`.
+    pub fn set_label(&self, label: &str) {
+        self.buffer.set_label(label)
+    }
+
+    /// Returns the label that was attached with `set_label`.
+    pub fn get_label(&self) -> Option<String> {
+        self.buffer.get_label()
+    }
+
     /// Turns the vertex buffer into a `VertexBuffer` without checking the type.
     pub unsafe fn into_vertex_buffer<T>(self) -> VertexBuffer<T> {
         unimplemented!();
@@ -1,7 +1,12 @@
 #[macro_use]
 extern crate glium;
 
-use glium::{Surface, BlitTarget, Rect};
+use glium::{Surface, BlitTarget, BlitMask, BlitError, Rect, CapabilitiesSource};
+use glium::backend::recording::Value;
+use glium::framebuffer::{SimpleFrameBuffer, RenderBuffer, DepthRenderBuffer};
+use glium::framebuffer::RenderBufferCreationError;
+use glium::texture::{DepthFormat, UncompressedFloatFormat};
+use glium::uniforms::MagnifySamplerFilter;
 
 mod support;
 
@@ -35,7 +40,7 @@ fn blit_texture_to_window() {
     target.clear_color(0.0, 0.0, 0.0, 0.0);
 
     texture.as_surface().blit_color(&src_rect, &target, &dest_rect,
-                                    glium::uniforms::MagnifySamplerFilter::Nearest);
+                                    glium::uniforms::MagnifySamplerFilter::Nearest).unwrap();
 
     target.finish().unwrap();
 
@@ -64,3 +69,263 @@ fn blit_texture_to_window() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn blit_depth_between_framebuffers() {
+    let display = support::build_display();
+
+    // OpenGL ES can't read back depth values
+    if display.get_opengl_version().0 == glium::Api::GlEs {
+        return;
+    }
+
+    let color1 = glium::Texture2d::empty(&display, 64, 64).unwrap();
+    let color2 = glium::Texture2d::empty(&display, 64, 64).unwrap();
+    let depth1 = DepthRenderBuffer::new(&display, DepthFormat::F32, 64, 64);
+    let depth2 = DepthRenderBuffer::new(&display, DepthFormat::F32, 64, 64);
+
+    let mut source = SimpleFrameBuffer::with_depth_buffer(&display, &color1, &depth1).unwrap();
+    let mut target = SimpleFrameBuffer::with_depth_buffer(&display, &color2, &depth2).unwrap();
+    source.clear_depth(0.25);
+    target.clear_depth(1.0);
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    source.blit_buffers(&rect, &target, &blit_target, MagnifySamplerFilter::Nearest,
+                        BlitMask::depth()).unwrap();
+
+    let data: Vec<Vec<f32>> = target.read_depth_pixels(&rect).unwrap();
+
+    assert_eq!(data[0][0], 0.25);
+    assert_eq!(data[63][63], 0.25);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn blit_depth_from_frame() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depth = DepthRenderBuffer::new(&context, DepthFormat::I24, 64, 64);
+    let framebuffer = SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth).unwrap();
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+    backend.clear_calls();
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    frame.blit_buffers(&rect, &framebuffer, &blit_target, MagnifySamplerFilter::Nearest,
+                       BlitMask::depth()).unwrap();
+    frame.finish().unwrap();
+
+    let calls = backend.get_calls();
+    let blit = calls.iter().find(|c| c.name == "glBlitFramebuffer").unwrap();
+    assert_eq!(blit.args[8], Value::UInt(0x100));       // GL_DEPTH_BUFFER_BIT
+    assert_eq!(blit.args[9], Value::UInt(0x2600));      // GL_NEAREST
+}
+
+#[test]
+fn blit_depth_linear_filter() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depth = DepthRenderBuffer::new(&context, DepthFormat::I24, 64, 64);
+    let framebuffer = SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth).unwrap();
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+    backend.clear_calls();
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    let result = frame.blit_buffers(&rect, &framebuffer, &blit_target,
+                                    MagnifySamplerFilter::Linear, BlitMask::depth());
+    frame.finish().unwrap();
+
+    assert_eq!(result, Err(BlitError::FilterNotNearest));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glBlitFramebuffer"));
+}
+
+#[test]
+fn blit_depth_format_mismatch() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depth = DepthRenderBuffer::new(&context, DepthFormat::I16, 64, 64);
+    let framebuffer = SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth).unwrap();
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    let result = frame.blit_buffers(&rect, &framebuffer, &blit_target,
+                                    MagnifySamplerFilter::Nearest, BlitMask::depth());
+    frame.finish().unwrap();
+
+    assert_eq!(result, Err(BlitError::DepthFormatMismatch));
+}
+
+#[test]
+fn blit_depth_fixed_to_float_mismatch() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color1 = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let color2 = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depth1 = DepthRenderBuffer::new(&context, DepthFormat::I32, 64, 64);
+    let depth2 = DepthRenderBuffer::new(&context, DepthFormat::F32, 64, 64);
+
+    let source = SimpleFrameBuffer::with_depth_buffer(&context, &color1, &depth1).unwrap();
+    let target = SimpleFrameBuffer::with_depth_buffer(&context, &color2, &depth2).unwrap();
+    assert_eq!(source.get_depth_buffer_bits(), target.get_depth_buffer_bits());
+    backend.clear_calls();
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    let result = source.blit_buffers(&rect, &target, &blit_target,
+                                     MagnifySamplerFilter::Nearest, BlitMask::depth());
+
+    assert_eq!(result, Err(BlitError::DepthFormatMismatch));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glBlitFramebuffer"));
+}
+
+#[test]
+fn blit_stencil_without_stencil_buffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depth = DepthRenderBuffer::new(&context, DepthFormat::I24, 64, 64);
+    let framebuffer = SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth).unwrap();
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    let result = framebuffer.blit_buffers(&rect, &frame, &blit_target,
+                                          MagnifySamplerFilter::Nearest, BlitMask::stencil());
+    frame.finish().unwrap();
+
+    assert_eq!(result, Err(BlitError::NoStencilBuffer));
+}
+
+#[test]
+fn multisample_render_buffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+    backend.clear_calls();
+
+    let buffer = RenderBuffer::new_multisample(&context, UncompressedFloatFormat::U8U8U8U8,
+                                               64, 64, 4).unwrap();
+    assert_eq!(buffer.get_samples(), Some(4));
+
+    let calls = backend.get_calls();
+    let storage = calls.iter().find(|c| c.name == "glRenderbufferStorageMultisample").unwrap();
+    assert_eq!(storage.args[1], Value::Int(4));
+}
+
+#[test]
+fn multisample_render_buffer_too_many_samples() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let max = context.get_capabilities().max_samples.unwrap() as u32;
+    let result = RenderBuffer::new_multisample(&context, UncompressedFloatFormat::U8U8U8U8,
+                                               64, 64, max + 1);
+
+    assert!(result.err() == Some(RenderBufferCreationError::SamplesCountNotSupported));
+}
+
+#[test]
+fn multisample_render_buffer_not_supported() {
+    let version = glium::Version(glium::Api::Gl, 2, 1);
+    let (_backend, context) = support::build_recording_context(version,
+                                                               &["GL_EXT_framebuffer_object"]);
+
+    let result = DepthRenderBuffer::new_multisample(&context, DepthFormat::I24, 64, 64, 4);
+    assert!(result.err() == Some(RenderBufferCreationError::MultisamplingNotSupported));
+}
+
+#[test]
+fn resolve_render_buffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let buffer = RenderBuffer::new_multisample(&context, UncompressedFloatFormat::U8U8U8U8,
+                                               64, 64, 4).unwrap();
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let framebuffer = SimpleFrameBuffer::new(&context, &texture).unwrap();
+    backend.clear_calls();
+
+    buffer.resolve_to(&framebuffer).unwrap();
+
+    let calls = backend.get_calls();
+    let blit = calls.iter().find(|c| c.name == "glBlitFramebuffer").unwrap();
+    assert_eq!(blit.args[2], Value::Int(64));
+    assert_eq!(blit.args[6], Value::Int(64));
+    assert_eq!(blit.args[8], Value::UInt(0x4000));      // GL_COLOR_BUFFER_BIT
+}
+
+#[test]
+fn resolve_render_buffer_dimensions_mismatch() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let buffer = RenderBuffer::new_multisample(&context, UncompressedFloatFormat::U8U8U8U8,
+                                               64, 64, 4).unwrap();
+    let texture = glium::Texture2d::empty(&context, 32, 32).unwrap();
+    let framebuffer = SimpleFrameBuffer::new(&context, &texture).unwrap();
+    backend.clear_calls();
+
+    assert_eq!(buffer.resolve_to(&framebuffer), Err(BlitError::MultisampleDimensionsMismatch));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glBlitFramebuffer"));
+}
+
+#[test]
+fn resolve_depth_render_buffer_without_depth_target() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let buffer = DepthRenderBuffer::new_multisample(&context, DepthFormat::I24, 64, 64, 4).unwrap();
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let framebuffer = SimpleFrameBuffer::new(&context, &texture).unwrap();
+    backend.clear_calls();
+
+    assert_eq!(buffer.resolve_to(&framebuffer), Err(BlitError::NoDepthBuffer));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glBlitFramebuffer"));
+}
+
+#[test]
+fn blit_to_multisample_framebuffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let buffer = RenderBuffer::new_multisample(&context, UncompressedFloatFormat::U8U8U8U8,
+                                               64, 64, 4).unwrap();
+    let target = SimpleFrameBuffer::new(&context, &buffer).unwrap();
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let source = SimpleFrameBuffer::new(&context, &texture).unwrap();
+
+    let rect = Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    let result = source.blit_buffers(&rect, &target, &blit_target,
+                                     MagnifySamplerFilter::Nearest, BlitMask::color());
+
+    assert_eq!(result, Err(BlitError::MultisampledTarget));
+}
+
+#[test]
+fn fill_multisample_framebuffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let buffer = RenderBuffer::new_multisample(&context, UncompressedFloatFormat::U8U8U8U8,
+                                               64, 64, 4).unwrap();
+    let target = SimpleFrameBuffer::new(&context, &buffer).unwrap();
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    backend.clear_calls();
+
+    let result = texture.as_surface().fill(&target, MagnifySamplerFilter::Nearest);
+
+    assert_eq!(result, Err(BlitError::MultisampledTarget));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glBlitFramebuffer"));
+}
@@ -921,3 +921,36 @@ fn dynamic_mapping_forget_then_draw() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn buffer_survives_display_rebuild() {
+    // headless displays can't be rebuilt
+    if ::std::env::var("GLIUM_HEADLESS_TESTS").is_ok() {
+        return;
+    }
+
+    let display = support::build_display();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        field1: [u8; 2],
+        field2: [u8; 2],
+    }
+
+    implement_vertex!(Vertex, field1, field2);
+
+    let mut vb = glium::VertexBuffer::new(&display,
+        &[
+            Vertex { field1: [ 2,  3], field2: [ 5,  7] },
+            Vertex { field1: [12, 13], field2: [15, 17] },
+        ]
+    ).unwrap();
+
+    support::rebuild_display(&display);
+
+    let mapping = vb.map();
+    assert_eq!(mapping[0].field1, [2, 3]);
+    assert_eq!(mapping[1].field2, [15, 17]);
+
+    display.assert_no_error(None);
+}
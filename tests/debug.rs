@@ -0,0 +1,149 @@
+#[macro_use]
+extern crate glium;
+
+mod support;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use glium::{Api, Version};
+use glium::backend::recording::Value;
+use glium::debug::{MessageType, Severity, Source};
+
+#[test]
+fn debug_callback_receives_messages() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                              &["GL_KHR_debug"]);
+
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let messages2 = messages.clone();
+
+    context.set_debug_callback(Box::new(move |source, ty, severity, id, message: &str| {
+        messages2.borrow_mut().push((source, ty, severity, id, message.to_string()));
+    })).unwrap();
+
+    backend.emit_debug_message(Source::Api as u32, MessageType::Error as u32, 12,
+                               Severity::High as u32, "hello");
+
+    let messages = messages.borrow();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0], (Source::Api, MessageType::Error, Severity::High, 12,
+                             "hello".to_string()));
+}
+
+#[test]
+fn debug_callback_not_supported() {
+    let (_backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+    assert!(context.set_debug_callback(Box::new(|_, _, _, _, _: &str| ())).is_err());
+}
+
+#[test]
+fn debug_messages_filter() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                              &["GL_KHR_debug"]);
+    backend.clear_calls();
+
+    context.set_debug_messages_enabled(None, Some(MessageType::Performance), None,
+                                       false).unwrap();
+
+    let calls = backend.get_calls();
+    let control = calls.iter().find(|c| c.name == "glDebugMessageControl").unwrap();
+    assert_eq!(control.args[1], Value::UInt(MessageType::Performance as u64));
+    assert_eq!(control.args[5], Value::UInt(0));
+}
+
+#[test]
+fn debug_messages_enabled_at_creation() {
+    // the debug output is only initialized in debug builds
+    if !cfg!(debug_assertions) || ::std::env::var("GLIUM_DISABLE_DEBUG_OUTPUT").is_ok() {
+        return;
+    }
+
+    let (backend, _context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                               &["GL_KHR_debug"]);
+
+    let calls = backend.get_calls();
+    let control = calls.iter().find(|c| c.name == "glDebugMessageControl").unwrap();
+    assert_eq!(control.args[0], Value::UInt(0x1100));       // GL_DONT_CARE
+    assert_eq!(control.args[2], Value::UInt(0x1100));
+    assert_eq!(control.args[5], Value::UInt(1));
+}
+
+#[test]
+fn debug_callback_keeps_messages_filter() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                              &["GL_KHR_debug"]);
+
+    context.set_debug_messages_enabled(None, Some(MessageType::Performance), None,
+                                       false).unwrap();
+    backend.clear_calls();
+
+    context.set_debug_callback(Box::new(|_, _, _, _, _: &str| ())).unwrap();
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glDebugMessageControl"));
+}
+
+#[test]
+fn debug_group() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                              &["GL_KHR_debug"]);
+    backend.clear_calls();
+
+    {
+        let _group = context.push_debug_group("shadows").unwrap();
+        assert!(backend.get_calls().iter().any(|c| c.name == "glPushDebugGroup"));
+        assert!(!backend.get_calls().iter().any(|c| c.name == "glPopDebugGroup"));
+    }
+
+    assert!(backend.get_calls().iter().any(|c| c.name == "glPopDebugGroup"));
+}
+
+#[test]
+fn debug_group_not_supported() {
+    let (_backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+    assert!(context.push_debug_group("shadows").is_err());
+}
+
+#[test]
+fn buffer_label() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                              &["GL_KHR_debug"]);
+
+    #[derive(Copy, Clone, Debug)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let buffer = glium::VertexBuffer::new(&context, &[
+        Vertex { position: [0.0, 0.0] },
+        Vertex { position: [1.0, 1.0] },
+    ]).unwrap();
+    assert_eq!(buffer.get_label(), None);
+
+    backend.clear_calls();
+    buffer.set_label("positions");
+
+    assert_eq!(buffer.get_label(), Some("positions".to_string()));
+    assert!(format!("{:?}", buffer).contains("positions"));
+    assert!(backend.get_calls().iter().any(|c| c.name == "glObjectLabel"));
+}
+
+#[test]
+fn framebuffer_label() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                              &["GL_KHR_debug"]);
+
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&context, &texture).unwrap();
+
+    backend.clear_calls();
+    framebuffer.set_label("shadows");
+    assert_eq!(framebuffer.get_label(), Some("shadows".to_string()));
+    assert_eq!(backend.get_calls().iter().filter(|c| c.name == "glObjectLabel").count(), 1);
+
+    // the framebuffer object already has this label
+    backend.clear_calls();
+    framebuffer.set_label("shadows");
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glObjectLabel"));
+}
@@ -1,7 +1,11 @@
 #[macro_use]
 extern crate glium;
 
-use glium::Surface;
+use glium::{Surface, CapabilitiesSource};
+use glium::backend::Context;
+use glium::backend::recording::{RecordingBackend, Value};
+
+use std::rc::Rc;
 
 mod support;
 
@@ -57,6 +61,19 @@ fn clear_color_rect() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn headless_dimensions() {
+    if ::std::env::var("GLIUM_HEADLESS_TESTS").is_err() {
+        return;
+    }
+
+    // the dimensions passed to the builder in `support::build_display`
+    let display = support::build_display();
+    assert_eq!(display.get_framebuffer_dimensions(), (1024, 768));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn release_shader_compiler() {
     let display = support::build_display();
@@ -149,3 +166,69 @@ fn is_context_lost() {
     display.is_context_lost();
     display.assert_no_error(None);
 }
+
+fn build_stereo_context() -> (Rc<RecordingBackend>, Rc<Context>) {
+    let backend = Rc::new(RecordingBackend::new(glium::Version(glium::Api::Gl, 3, 3), &[]));
+    backend.set_integer(0x0C33, 1);       // GL_STEREO
+    let context = unsafe { Context::new::<_, ()>(backend.clone(), false) }.unwrap();
+    (backend, context)
+}
+
+#[test]
+fn stereo_eyes() {
+    let (backend, context) = build_stereo_context();
+    assert!(context.get_capabilities().stereo);
+    backend.clear_calls();
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    frame.left().clear_color(1.0, 0.0, 0.0, 1.0);
+    frame.right().clear_color(0.0, 0.0, 1.0, 1.0);
+    frame.clear_color(0.0, 0.0, 0.0, 1.0);
+    frame.finish().unwrap();
+
+    let draw_buffers = backend.get_calls().into_iter().filter(|c| c.name == "glDrawBuffer")
+                                                      .map(|c| c.args[0].clone())
+                                                      .collect::<Vec<_>>();
+    assert_eq!(draw_buffers, vec![Value::UInt(0x0402), Value::UInt(0x0403),     // BACK_LEFT, RIGHT
+                                  Value::UInt(0x0405)]);                        // BACK
+}
+
+#[test]
+fn stereo_read() {
+    let (backend, context) = build_stereo_context();
+    backend.clear_calls();
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = frame.right().read();
+    frame.finish().unwrap();
+
+    assert_eq!(data.len(), 600);
+    let read_buffer = backend.get_calls().into_iter().find(|c| c.name == "glReadBuffer").unwrap();
+    assert_eq!(read_buffer.args[0], Value::UInt(0x0403));       // GL_BACK_RIGHT
+}
+
+#[test]
+#[should_panic]
+fn stereo_not_supported() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    frame.left();
+}
+
+#[test]
+fn stereo_invalidate() {
+    let backend = Rc::new(RecordingBackend::new(glium::Version(glium::Api::Gl, 4, 3), &[]));
+    backend.set_integer(0x0C33, 1);       // GL_STEREO
+    let context = unsafe { Context::new::<_, ()>(backend.clone(), false) }.unwrap();
+    backend.clear_calls();
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    frame.left().invalidate(glium::InvalidateMask::all(), None);
+    frame.finish().unwrap();
+
+    // only `GL_DEPTH` and `GL_STENCIL`, as `GL_BACK_LEFT` isn't accepted
+    let calls = backend.get_calls();
+    let invalidate = calls.iter().find(|c| c.name == "glInvalidateFramebuffer").unwrap();
+    assert_eq!(invalidate.args[1], Value::Int(2));
+}
@@ -1,8 +1,9 @@
 #[macro_use]
 extern crate glium;
 
-use glium::Surface;
+use glium::{Surface, CapabilitiesSource};
 use glium::index::PrimitiveType;
+use glium::framebuffer::ToColorAttachment;
 
 mod support;
 
@@ -662,15 +663,12 @@ fn cull_counterclockwise_trianglestrip() {
 }
 
 macro_rules! blending_test {
-    ($name:ident, $func:expr, $source:expr, $dest:expr, $result:expr) => (
+    ($name:ident, params: $params:expr, $source:expr, $dest:expr, $result:expr) => (
         #[test]
         fn $name() {
             let display = support::build_display();
 
-            let params = glium::DrawParameters {
-                blending_function: Some($func),
-                .. Default::default()
-            };
+            let params = $params;
 
             let (vb, ib) = support::build_rectangle_vb_ib(&display);
 
@@ -746,7 +744,15 @@ macro_rules! blending_test {
 
             display.assert_no_error(None);
         }
-    )
+    );
+
+    ($name:ident, $func:expr, $source:expr, $dest:expr, $result:expr) => (
+        blending_test!($name, params: glium::DrawParameters {
+                           blending_function: Some($func),
+                           .. Default::default()
+                       },
+                       $source, $dest, $result);
+    );
 }
 
 
@@ -761,3 +767,807 @@ blending_test!(one_plus_one, glium::BlendingFunction::Addition {
                    destination: glium::LinearBlendingFactor::One,
                },
                (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 255, 255, 255));
+
+blending_test!(separate_alpha_blending, params: glium::DrawParameters {
+                   blending_function: Some(glium::BlendingFunction::Addition {
+                       source: glium::LinearBlendingFactor::One,
+                       destination: glium::LinearBlendingFactor::One,
+                   }),
+                   alpha_blending_function: Some(glium::BlendingFunction::Addition {
+                       source: glium::LinearBlendingFactor::Zero,
+                       destination: glium::LinearBlendingFactor::One,
+                   }),
+                   .. Default::default()
+               },
+               (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 255, 255, 0));
+
+blending_test!(constant_color_blending, params: glium::DrawParameters {
+                   blending_function: Some(glium::BlendingFunction::Addition {
+                       source: glium::LinearBlendingFactor::ConstantColor,
+                       destination: glium::LinearBlendingFactor::Zero,
+                   }),
+                   constant_color: (1.0, 0.0, 0.0, 0.0),
+                   .. Default::default()
+               },
+               (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 0, 0, 0));
+
+#[test]
+fn draw_buffer_parameters_support() {
+    let (_, context) = support::build_recording_context(glium::Version(glium::Api::Gl, 3, 3),
+                                                        &[]);
+    assert!(!glium::draw_parameters::is_draw_buffer_parameters_supported(&context));
+
+    let (_, context) = support::build_recording_context(glium::Version(glium::Api::Gl, 3, 3),
+                                                        &["GL_ARB_draw_buffers_blend"]);
+    assert!(glium::draw_parameters::is_draw_buffer_parameters_supported(&context));
+
+    let (_, context) = support::build_recording_context(glium::Version(glium::Api::Gl, 4, 0),
+                                                        &[]);
+    assert!(glium::draw_parameters::is_draw_buffer_parameters_supported(&context));
+}
+
+#[test]
+fn draw_buffer_parameters_cached() {
+    let (backend, context) = support::build_recording_context(glium::Version(glium::Api::Gl, 4, 0),
+                                                              &[]);
+
+    let program = glium::Program::from_source(&context,
+        "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let draw_buffers = [
+        glium::draw_parameters::DrawBufferParameters {
+            draw_buffer: glium::draw_parameters::DrawBuffer::Index(0),
+            blending_function: glium::BlendingFunction::Addition {
+                source: glium::LinearBlendingFactor::One,
+                destination: glium::LinearBlendingFactor::One,
+            },
+            alpha_blending_function: None,
+            color_mask: (true, true, true, false),
+        },
+    ];
+
+    let params = glium::DrawParameters {
+        draw_buffers: &draw_buffers,
+        .. Default::default()
+    };
+
+    let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    frame.draw(glium::vertex::EmptyVertexAttributes { len: 3 }, &indices, &program,
+               &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let calls = backend.get_calls();
+    assert!(calls.iter().any(|c| c.name == "glBlendFuncSeparatei"));
+    assert!(calls.iter().any(|c| c.name == "glColorMaski"));
+
+    // drawing again with the same parameters doesn't modify the draw buffer
+    backend.clear_calls();
+    frame.draw(glium::vertex::EmptyVertexAttributes { len: 3 }, &indices, &program,
+               &glium::uniforms::EmptyUniforms, &params).unwrap();
+    frame.finish().unwrap();
+
+    let calls = backend.get_calls();
+    assert!(!calls.iter().any(|c| c.name == "glBlendEquationSeparatei"));
+    assert!(!calls.iter().any(|c| c.name == "glBlendFuncSeparatei"));
+    assert!(!calls.iter().any(|c| c.name == "glEnablei"));
+    assert!(!calls.iter().any(|c| c.name == "glColorMaski"));
+    assert!(!calls.iter().any(|c| c.name == "glColorMask"));
+}
+
+#[test]
+fn dual_source_blending() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            layout(location = 0, index = 0) out vec4 color;
+            layout(location = 0, index = 1) out vec4 factor;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+                factor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    assert_eq!(program.get_frag_data_index("color"), Some(0));
+    assert_eq!(program.get_frag_data_index("factor"), Some(1));
+
+    let params = glium::DrawParameters {
+        blending_function: Some(glium::BlendingFunction::Addition {
+            source: glium::LinearBlendingFactor::SourceOneColor,
+            destination: glium::LinearBlendingFactor::Zero,
+        }),
+        .. Default::default()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::DualSourceBlendingNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn dual_source_blending_multiple_draw_buffers() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 0.0, 0.0, 1.0);
+                color2 = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = support::build_renderable_texture(&display);
+    let color2 = support::build_renderable_texture(&display);
+    let attachment1 = color1.to_color_attachment();
+    let attachment2 = color2.to_color_attachment();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                     &[("color1", &attachment1), ("color2", &attachment2)]).unwrap();
+
+    let params = glium::DrawParameters {
+        blending_function: Some(glium::BlendingFunction::Addition {
+            source: glium::LinearBlendingFactor::One,
+            destination: glium::LinearBlendingFactor::OneMinusSourceOneAlpha,
+        }),
+        .. Default::default()
+    };
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::DualSourceBlendingNotSupported) => (),
+        Err(glium::DrawError::DualSourceBlendingMultipleDrawBuffers) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn polygon_offset_fill() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                           glium::texture::DepthFormat::I24,
+                                                           1024, 1024);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                   &texture,
+                                                                                   &depth).unwrap();
+
+    // the quad is at depth 0.5, so it doesn't pass the depth test without an offset
+    let params = glium::DrawParameters::new(&display)
+                    .with_depth(glium::DepthTest::IfLess, false, (0.0, 1.0));
+
+    framebuffer.clear_color_and_depth((0.0, 0.0, 0.0, 0.0), 0.5);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+
+    let params = params.with_polygon_offset(glium::PolygonOffset {
+        factor: 0.0,
+        units: -1000.0,
+        fill: true,
+        .. Default::default()
+    });
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn polygon_offset_default() {
+    let display = support::build_display();
+    let params: glium::DrawParameters = Default::default();
+
+    assert_eq!(params.polygon_offset, glium::PolygonOffset {
+        factor: 0.0,
+        units: 0.0,
+        point: false,
+        line: false,
+        fill: false,
+    });
+    assert_eq!(glium::DrawParameters::new(&display).polygon_offset, params.polygon_offset);
+}
+
+#[test]
+fn primitive_restart_index() {
+    let display = support::build_display();
+    let (_, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    // the vertex 255, which is the restart index of `u8` indices, would add a triangle that
+    // covers the bottom-right half of the surface if it wasn't handled as a restart
+    let mut vertices = vec![Vertex { position: [0.0, 0.0] }; 256];
+    vertices[0].position = [-1.0, 1.0];
+    vertices[1].position = [1.0, 1.0];
+    vertices[2].position = [-1.0, -1.0];
+    vertices[255].position = [1.0, -1.0];
+
+    let vb = glium::VertexBuffer::new(&display, &vertices).unwrap();
+    let ib = glium::IndexBuffer::new(&display, PrimitiveType::TriangleStrip,
+                                     &[0u8, 1, 2, 0xff]).unwrap();
+
+    let params = glium::DrawParameters::new(&display).with_primitive_restart_index();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::PrimitiveRestartNotSupported) => {
+            assert!(!glium::draw_parameters::is_primitive_restart_index_supported(&display));
+            return;
+        },
+        r => r.unwrap()
+    };
+
+    // top-left and bottom-right corners
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data.last().unwrap()[0], (255, 0, 0, 255));
+    assert_eq!(data[0].last().unwrap(), &(0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clip_planes() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                gl_ClipDistance[1] = -1.0;
+            }
+        ",
+        "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters::new(&display).with_clip_planes_bitmask(0b10);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clip_planes_out_of_bounds() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let max = display.get_capabilities().max_clip_distances;
+    if max >= 32 {
+        return;
+    }
+
+    let params = glium::DrawParameters::new(&display).with_clip_planes_bitmask(1 << max);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ClipPlaneIndexOutOfBounds) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_clamp() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // the rectangle is behind the far plane
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 2.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+
+    let params = glium::DrawParameters::new(&display).with_depth_clamp();
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::DepthClampNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn logic_operation_xor() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    // logic operations are ignored with floating-point textures
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+    texture.as_surface().clear_color(1.0, 1.0, 0.0, 1.0);
+
+    let params = glium::DrawParameters::new(&display)
+                    .with_logic_operation(glium::LogicOp::Xor);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::LogicOperationNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn provoking_vertex() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // the first two vertices are red and the last two are green
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 140
+
+            in vec2 position;
+            flat out vec4 v_color;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+                v_color = gl_VertexID < 2 ? vec4(1.0, 0.0, 0.0, 1.0) : vec4(0.0, 1.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 140
+
+            flat in vec4 v_color;
+            out vec4 color;
+
+            void main() {
+                color = v_color;
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(0, 255, 0, 255));
+
+    let params = glium::DrawParameters::new(&display)
+                    .with_provoking_vertex(glium::ProvokingVertex::First);
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ProvokingVertexNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn alpha_to_coverage() {
+    let display = support::build_display();
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+
+    // a fragment whose alpha is 0 doesn't cover any sample when alpha-to-coverage is enabled
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 0.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let buffer = match glium::framebuffer::RenderBuffer::new_multisample(&display,
+                                        glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                        64, 64, 4)
+    {
+        Ok(b) => b,
+        Err(_) => return
+    };
+
+    let texture = glium::Texture2d::empty(&display, 64, 64).unwrap();
+    let resolved = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &buffer).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+
+    let params = glium::DrawParameters::new(&display).with_alpha_to_coverage();
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    buffer.resolve_to(&resolved).unwrap();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[32][32], (0, 0, 0, 255));
+
+    // without alpha-to-coverage, all the samples are written
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    buffer.resolve_to(&resolved).unwrap();
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[32][32], (255, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sample_mask_zero() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let buffer = match glium::framebuffer::RenderBuffer::new_multisample(&display,
+                                        glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                        64, 64, 4)
+    {
+        Ok(b) => b,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &buffer).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters::new(&display).with_sample_mask(0);
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::SampleMaskNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let texture = glium::Texture2d::empty(&display, 64, 64).unwrap();
+    let resolved = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+    buffer.resolve_to(&resolved).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[32][32], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sample_mask_out_of_bounds() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    // the texture is not multisampled and thus only has one sample
+    let params = glium::DrawParameters::new(&display).with_sample_mask(0b10);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::SampleMaskNotSupported) => (),
+        Err(glium::DrawError::SampleMaskOutOfBounds) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalid_min_sample_shading() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters::new(&display).with_min_sample_shading(1.5);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::InvalidMinSampleShading) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn viewport_array() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    // the geometry shader sends everything to the second viewport
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 330
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        Some("
+            #version 330
+            #extension GL_ARB_viewport_array : require
+
+            layout(triangles) in;
+            layout(triangle_strip, max_vertices = 3) out;
+
+            void main() {
+                for (int i = 0; i < 3; ++i) {
+                    gl_Position = gl_in[i].gl_Position;
+                    gl_ViewportIndex = 1;
+                    EmitVertex();
+                }
+                EndPrimitive();
+            }
+        "))
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let viewports = [
+        glium::Rect { left: 0, bottom: 0, width: 512, height: 1024 },
+        glium::Rect { left: 512, bottom: 0, width: 512, height: 1024 },
+    ];
+
+    let params = glium::DrawParameters::new(&display).with_viewports(&viewports);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => return,
+        r => r.unwrap()
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 0, 0, 0));
+    assert_eq!(data[0][1023], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn too_many_viewports() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let max = display.get_capabilities().max_viewports as usize;
+    let viewports = vec![glium::Rect { left: 0, bottom: 0, width: 16, height: 16 }; max + 1];
+
+    let params = glium::DrawParameters::new(&display).with_viewports(&viewports);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => (),
+        Err(glium::DrawError::TooManyViewports) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn viewport_array_too_large() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let max = display.get_capabilities().max_viewport_dims;
+    let viewports = [
+        glium::Rect { left: 0, bottom: 0, width: 16, height: 16 },
+        glium::Rect { left: 0, bottom: 0, width: max.0 as u32 + 1, height: 16 },
+    ];
+
+    let params = glium::DrawParameters::new(&display).with_viewports(&viewports);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => (),
+        Err(glium::DrawError::ViewportTooLarge) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn scissor_array_length_mismatch() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let viewports = [glium::Rect { left: 0, bottom: 0, width: 16, height: 16 }];
+    let scissors = [glium::Rect { left: 0, bottom: 0, width: 8, height: 8 }; 2];
+
+    let params = glium::DrawParameters::new(&display).with_viewports(&viewports)
+                                                     .with_scissors(&scissors);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::ViewportArrayNotSupported) => (),
+        Err(glium::DrawError::ScissorArrayLengthMismatch) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
@@ -2,6 +2,8 @@
 extern crate glium;
 
 use glium::Surface;
+use glium::framebuffer::ToColorAttachment;
+use glium::backend::recording::Value;
 
 mod support;
 
@@ -13,7 +15,7 @@ fn no_depth_buffer_depth_test() {
     let texture = glium::texture::Texture2d::empty_with_format(&display,
                             glium::texture::UncompressedFloatFormat::U8U8U8U8, 
                             glium::texture::MipmapsOption::NoMipmap, 128, 128).unwrap();
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
 
     let parameters = glium::DrawParameters {
         depth_test: glium::DepthTest::IfLess,
@@ -38,7 +40,7 @@ fn no_depth_buffer_depth_write() {
     let texture = glium::texture::Texture2d::empty_with_format(&display,
                             glium::texture::UncompressedFloatFormat::U8U8U8U8,
                                             glium::texture::MipmapsOption::NoMipmap, 128, 128).unwrap();
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
 
     let parameters = glium::DrawParameters {
         depth_write: true,
@@ -64,7 +66,7 @@ fn simple_dimensions() {
                                             glium::texture::MipmapsOption::NoMipmap,
                                               128, 128).unwrap();
 
-    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
     assert_eq!(framebuffer.get_dimensions(), (128, 128));
 
     display.assert_no_error(None);
@@ -80,7 +82,7 @@ fn simple_render_to_texture() {
                                             glium::texture::MipmapsOption::NoMipmap,
                                               128, 128).unwrap();
 
-    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
     framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
 
     let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
@@ -154,7 +156,7 @@ fn depth_texture2d() {
 
     // drawing with the `IfLess` depth test
     let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
-                                                                                   &color, &depth);
+                                                                                   &color, &depth).unwrap();
     let params = glium::DrawParameters {
         depth_test: glium::DepthTest::IfLess,
         .. Default::default()
@@ -220,7 +222,7 @@ fn multioutput() {
 
     // building the framebuffer
     let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
-                                             &[("color1", &color1), ("color2", &color2)]);
+                                             &[("color1", &color1), ("color2", &color2)]).unwrap();
 
     framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
                      &Default::default()).unwrap();
@@ -242,5 +244,800 @@ fn multioutput() {
     }
 
 
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_rect() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 64, height: 128 };
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 1.0);
+    framebuffer.clear(Some(&rect), Some((1.0, 0.0, 0.0, 1.0)), None, None);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back[127][63], (255, 0, 0, 255));
+    assert_eq!(read_back[0][64], (0, 0, 0, 255));
+    assert_eq!(read_back[127][127], (0, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_clear_rect() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+    backend.clear_calls();
+
+    let rect = glium::Rect { left: 5, bottom: 10, width: 20, height: 30 };
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    frame.clear(Some(&rect), Some((1.0, 0.0, 0.0, 1.0)), None, None);
+    frame.finish().unwrap();
+
+    assert!(backend.is_enabled(0x0C11));      // GL_SCISSOR_TEST
+
+    let calls = backend.get_calls();
+    let scissor = calls.iter().find(|c| c.name == "glScissor").unwrap();
+    assert_eq!(scissor.args, vec![Value::Int(5), Value::Int(10), Value::Int(20), Value::Int(30)]);
+}
+
+#[test]
+fn multioutput_clear_attachment() {
+    let display = support::build_display();
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                             &[("color1", &color1), ("color2", &color2)]).unwrap();
+
+    framebuffer.clear_attachment("color1", None,
+                                 glium::framebuffer::ClearColor::Float(1.0, 0.0, 0.0, 1.0))
+               .unwrap();
+    framebuffer.clear_attachment("color2", None,
+                                 glium::framebuffer::ClearColor::Float(0.0, 0.0, 1.0, 1.0))
+               .unwrap();
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back1[0][0], (255, 0, 0, 255));
+    assert_eq!(read_back1[127][127], (255, 0, 0, 255));
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[0][0], (0, 0, 255, 255));
+    assert_eq!(read_back2[127][127], (0, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_clear_integral_attachment() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color1 = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let color2 = glium::texture::IntegralTexture2d::empty_with_format(&context,
+                                            glium::texture::UncompressedIntFormat::I32I32I32I32,
+                                            glium::texture::MipmapsOption::NoMipmap,
+                                            64, 64).unwrap();
+
+    let attachment1 = color1.to_color_attachment();
+    let attachment2 = color2.to_color_attachment();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&context,
+                                     &[("color1", &attachment1), ("color2", &attachment2)]).unwrap();
+
+    backend.clear_calls();
+    framebuffer.clear_attachment("color2", None,
+                                 glium::framebuffer::ClearColor::Integral(1, 2, 3, 4)).unwrap();
+
+    let calls = backend.get_calls();
+    let clear = calls.iter().find(|c| c.name == "glClearBufferiv").unwrap();
+    assert_eq!(clear.args[0], Value::UInt(0x1800));       // GL_COLOR
+    assert_eq!(clear.args[1], Value::Int(1));
+}
+
+#[test]
+fn multioutput_clear_attachment_format_mismatch() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&context,
+                                                                          &[("color", &color)]).unwrap();
+
+    backend.clear_calls();
+    let result = framebuffer.clear_attachment("color", None,
+                                    glium::framebuffer::ClearColor::Integral(1, 2, 3, 4));
+    assert_eq!(result, Err(glium::framebuffer::ClearAttachmentError::FormatMismatch));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glClearBufferiv"));
+}
+
+#[test]
+fn multioutput_clear_attachment_not_supported() {
+    let version = glium::Version(glium::Api::Gl, 2, 1);
+    let (_, context) = support::build_recording_context(version, &["GL_EXT_framebuffer_object"]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&context,
+                                                                          &[("color", &color)]).unwrap();
+
+    let result = framebuffer.clear_attachment("color", None,
+                                    glium::framebuffer::ClearColor::Float(1.0, 0.0, 0.0, 1.0));
+    assert_eq!(result, Err(glium::framebuffer::ClearAttachmentError::NotSupported));
+}
+
+#[test]
+fn read_pixels() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+    framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+
+    let rect = glium::Rect { left: 16, bottom: 16, width: 32, height: 8 };
+    let data: Vec<Vec<(f32, f32, f32, f32)>> = framebuffer.read_pixels(&rect).unwrap();
+
+    assert_eq!(data.len(), 8);
+    assert_eq!(data[0].len(), 32);
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(1.0, 0.0, 0.0, 1.0));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_depth_pixels() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                                           glium::texture::DepthFormat::F32,
+                                                           128, 128);
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display,
+                                                                                   &texture,
+                                                                                   &depth).unwrap();
+    framebuffer.clear_depth(0.5);
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 128, height: 128 };
+    let data: Vec<Vec<f32>> = match framebuffer.read_depth_pixels(&rect) {
+        Ok(d) => d,
+        Err(_) => return
+    };
+
+    assert_eq!(data[0][0], 0.5);
+    assert_eq!(data[127][127], 0.5);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn read_stencil_pixels_without_stencil_buffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&context, &texture).unwrap();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let data: Result<Vec<Vec<u8>>, _> = framebuffer.read_stencil_pixels(&rect);
+    assert!(data.is_err());
+}
+
+#[test]
+fn frame_read_depth_pixels_to_buffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+    let buffer = glium::pixel_buffer::PixelBuffer::new_empty(&context, 16 * 16);
+    backend.clear_calls();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 16, height: 16 };
+    frame.read_depth_pixels_to_buffer(&rect, &buffer).unwrap();
+    frame.finish().unwrap();
+
+    let calls = backend.get_calls();
+    let read = calls.iter().find(|c| c.name == "glReadPixels").unwrap();
+    assert_eq!(read.args[4], Value::UInt(0x1902));      // GL_DEPTH_COMPONENT
+    assert_eq!(read.args[6], Value::Pointer(0));
+}
+
+#[test]
+fn copy_to_texture() {
+    let display = support::build_display();
+
+    let source = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              64, 64).unwrap();
+    let target = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                              glium::texture::MipmapsOption::NoMipmap,
+                                              64, 64).unwrap();
+
+    source.as_surface().clear_color(1.0, 0.0, 0.0, 1.0);
+    target.as_surface().clear_color(0.0, 0.0, 0.0, 1.0);
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 16, height: 16 };
+    source.as_surface().copy_to_texture(&rect, &target.main_level(), (8, 8)).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(read_back[8][8], (255, 0, 0, 255));
+    assert_eq!(read_back[23][23], (255, 0, 0, 255));
+    assert_eq!(read_back[0][0], (0, 0, 0, 255));
+    assert_eq!(read_back[24][24], (0, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn frame_copy_to_texture() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+    backend.clear_calls();
+
+    let rect = glium::Rect { left: 100, bottom: 200, width: 32, height: 16 };
+    frame.copy_to_texture(&rect, &texture.main_level(), (4, 2)).unwrap();
+    frame.finish().unwrap();
+
+    let calls = backend.get_calls();
+    let copy = calls.iter().find(|c| c.name == "glCopyTexSubImage2D").unwrap();
+    assert_eq!(&copy.args[2..], &[Value::Int(4), Value::Int(2), Value::Int(100), Value::Int(200),
+                                  Value::Int(32), Value::Int(16)][..]);
+}
+
+#[test]
+#[should_panic]
+fn copy_to_texture_out_of_bounds() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let frame = glium::Frame::new(context.clone(), (800, 600));
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 32, height: 32 };
+    frame.copy_to_texture(&rect, &texture.main_level(), (48, 0)).ok();
+}
+
+#[test]
+fn layered_framebuffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::texture::Texture2dArray::empty(&context, 64, 64, 4).unwrap();
+    let depth = glium::texture::DepthTexture2dArray::empty(&context, 64, 64, 4).unwrap();
+    backend.clear_calls();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&context,
+                                                                                   &color,
+                                                                                   &depth).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let calls = backend.get_calls();
+    assert_eq!(calls.iter().filter(|c| c.name == "glFramebufferTexture").count(), 2);
+    assert!(!calls.iter().any(|c| c.name == "glFramebufferTextureLayer"));
+}
+
+#[test]
+fn layered_and_non_layered_attachments() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::texture::Texture2dArray::empty(&context, 64, 64, 4).unwrap();
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&context,
+                                                           glium::texture::DepthFormat::I24,
+                                                           64, 64);
+
+    match glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth) {
+        Err(glium::framebuffer::ValidationError::LayeringMismatch) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn layered_framebuffer_not_supported() {
+    let version = glium::Version(glium::Api::Gl, 3, 0);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::texture::Texture2dArray::empty(&context, 64, 64, 4).unwrap();
+
+    match glium::framebuffer::SimpleFrameBuffer::new(&context, &color) {
+        Err(glium::framebuffer::ValidationError::LayeredRenderingNotSupported) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn layered_cubemap_framebuffer() {
+    let version = glium::Version(glium::Api::Gl, 4, 0);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::texture::Cubemap::empty(&context, 64).unwrap();
+    let depth = glium::texture::DepthCubemap::empty(&context, 64).unwrap();
+    backend.clear_calls();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&context,
+                                                                                   &color,
+                                                                                   &depth).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let calls = backend.get_calls();
+    assert_eq!(calls.iter().filter(|c| c.name == "glFramebufferTexture").count(), 2);
+    assert!(!calls.iter().any(|c| c.name == "glFramebufferTextureLayer"));
+}
+
+#[test]
+fn layered_cubemap_array_framebuffer() {
+    let version = glium::Version(glium::Api::Gl, 4, 0);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::texture::CubemapArray::empty(&context, 64, 2).unwrap();
+    backend.clear_calls();
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&context, &color).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let calls = backend.get_calls();
+    assert!(calls.iter().any(|c| c.name == "glFramebufferTexture"));
+    assert!(!calls.iter().any(|c| c.name == "glFramebufferTextureLayer"));
+}
+
+#[test]
+fn cubemap_and_non_layered_attachments() {
+    let version = glium::Version(glium::Api::Gl, 4, 0);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::texture::Cubemap::empty(&context, 64).unwrap();
+    let depth = glium::texture::DepthTexture2d::empty(&context, 64, 64).unwrap();
+
+    match glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth) {
+        Err(glium::framebuffer::ValidationError::LayeringMismatch) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn multioutput_mixed_attachments() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let buffer = glium::framebuffer::RenderBuffer::new(&context,
+                                        glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                        64, 64);
+
+    let color1 = texture.to_color_attachment();
+    let color2 = buffer.to_color_attachment();
+
+    backend.clear_calls();
+    let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&context,
+                                             &[("color1", &color1), ("color2", &color2)]).unwrap();
+    framebuffer.set_label("mixed");
+
+    let calls = backend.get_calls();
+    assert!(calls.iter().any(|c| c.name == "glFramebufferTexture"));
+    assert!(calls.iter().any(|c| c.name == "glFramebufferRenderbuffer"));
+}
+
+#[test]
+fn multioutput_with_depth_stencil_buffer() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depthstencil = glium::framebuffer::DepthStencilRenderBuffer::new(&context,
+                                        glium::texture::DepthStencilFormat::I24I8, 64, 64);
+
+    let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::with_depth_stencil_buffer(
+                                        &context, &[("color", &color)], &depthstencil).unwrap();
+
+    assert_eq!(framebuffer.get_depth_buffer_bits(), Some(24));
+    assert_eq!(framebuffer.get_stencil_buffer_bits(), Some(8));
+}
+
+#[test]
+fn multioutput_integral_attachment() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color1;
+            out ivec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 0.0, 0.0, 1.0);
+                color2 = ivec4(-1, 2, -3, 4);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    let color2 = glium::texture::IntegralTexture2d::empty_with_format(&display,
+                                            glium::texture::UncompressedIntFormat::I32I32I32I32,
+                                            glium::texture::MipmapsOption::NoMipmap,
+                                            128, 128).unwrap();
+
+    let attachment1 = color1.to_color_attachment();
+    let attachment2 = color2.to_color_attachment();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                     &[("color1", &attachment1), ("color2", &attachment2)]).unwrap();
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_wrong_output_type() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    // the backend can't introspect the fragment outputs
+    if program.get_frag_data_type("color").is_none() {
+        return;
+    }
+
+    let texture = glium::texture::IntegralTexture2d::empty_with_format(&display,
+                                            glium::texture::UncompressedIntFormat::I32I32I32I32,
+                                            glium::texture::MipmapsOption::NoMipmap,
+                                            128, 128).unwrap();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                                                          &[("color", &texture)]).unwrap();
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                           &Default::default())
+    {
+        Err(glium::DrawError::FragmentOutputTypeMismatch { ref name }) if name == "color" => (),
+        e => panic!("{:?}", e)
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_missing_output() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let texture = glium::Texture2d::empty(&display, 128, 128).unwrap();
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                                                          &[("other", &texture)]).unwrap();
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                           &Default::default())
+    {
+        Err(glium::DrawError::FragmentOutputNotFound { ref name }) if name == "other" => (),
+        e => panic!("{:?}", e)
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn empty_framebuffer() {
+    let version = glium::Version(glium::Api::Gl, 4, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let mut framebuffer = glium::framebuffer::EmptyFrameBuffer::new(&context, 256, 128,
+                                                                    None, None).unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (256, 128));
+    assert!(!framebuffer.has_depth_buffer());
+
+    backend.clear_calls();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let calls = backend.get_calls();
+    assert_eq!(calls.iter().filter(|c| c.name == "glGenFramebuffers").count(), 1);
+    assert!(!calls.iter().any(|c| c.name.starts_with("glFramebufferTexture")));
+
+    let params = calls.iter().filter(|c| c.name == "glFramebufferParameteri")
+                      .map(|c| (c.args[1].clone(), c.args[2].clone())).collect::<Vec<_>>();
+    assert_eq!(params, vec![(Value::UInt(0x9310), Value::Int(256)),     // GL_FRAMEBUFFER_DEFAULT_WIDTH
+                            (Value::UInt(0x9311), Value::Int(128))]);   // GL_FRAMEBUFFER_DEFAULT_HEIGHT
+}
+
+#[test]
+fn empty_framebuffer_layered_multisample() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version,
+                                                     &["GL_ARB_framebuffer_no_attachments"]);
+
+    let mut framebuffer = glium::framebuffer::EmptyFrameBuffer::new(&context, 64, 64,
+                                                                    Some(6), Some(4)).unwrap();
+    backend.clear_calls();
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let calls = backend.get_calls();
+    assert!(calls.iter().any(|c| c.name == "glFramebufferParameteri" &&
+                                 c.args[1] == Value::UInt(0x9312) &&   // GL_FRAMEBUFFER_DEFAULT_LAYERS
+                                 c.args[2] == Value::Int(6)));
+    assert!(calls.iter().any(|c| c.name == "glFramebufferParameteri" &&
+                                 c.args[1] == Value::UInt(0x9313) &&   // GL_FRAMEBUFFER_DEFAULT_SAMPLES
+                                 c.args[2] == Value::Int(4)));
+}
+
+#[test]
+fn empty_framebuffer_not_supported() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    match glium::framebuffer::EmptyFrameBuffer::new(&context, 64, 64, None, None) {
+        Err(glium::framebuffer::ValidationError::EmptyFramebufferObjectsNotSupported) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn empty_framebuffer_too_large() {
+    let version = glium::Version(glium::Api::Gl, 4, 3);
+    let (_backend, context) = support::build_recording_context(version, &[]);
+
+    match glium::framebuffer::EmptyFrameBuffer::new(&context, 1000000, 64, None, None) {
+        Err(glium::framebuffer::ValidationError::EmptyFramebufferUnsupportedDimensions) => (),
+        _ => panic!()
+    }
+}
+
+#[test]
+fn empty_framebuffer_blit_color() {
+    let version = glium::Version(glium::Api::Gl, 4, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let framebuffer = glium::framebuffer::EmptyFrameBuffer::new(&context, 64, 64,
+                                                                None, None).unwrap();
+    let texture = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    backend.clear_calls();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let blit_target = glium::BlitTarget { left: 0, bottom: 0, width: 64, height: 64 };
+    let result = framebuffer.blit_color(&rect, &texture.as_surface(), &blit_target,
+                                        glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    assert_eq!(result, Err(glium::BlitError::NoColorBuffer));
+    assert!(!backend.get_calls().iter().any(|c| c.name == "glBlitFramebuffer"));
+}
+
+#[test]
+fn frame_invalidate_depth_stencil() {
+    let version = glium::Version(glium::Api::Gl, 4, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    backend.clear_calls();
+    frame.invalidate_depth_stencil();
+    frame.finish().unwrap();
+
+    let calls = backend.get_calls();
+    let invalidate = calls.iter().find(|c| c.name == "glInvalidateFramebuffer").unwrap();
+    assert_eq!(invalidate.args[1], Value::Int(2));
+}
+
+#[test]
+fn framebuffer_invalidate_rect() {
+    let version = glium::Version(glium::Api::Gl, 4, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let color = glium::Texture2d::empty(&context, 64, 64).unwrap();
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&context,
+                                                           glium::texture::DepthFormat::I24,
+                                                           64, 64);
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&context,
+                                                                                   &color,
+                                                                                   &depth).unwrap();
+    backend.clear_calls();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 32, height: 32 };
+    framebuffer.invalidate(glium::InvalidateMask::all(), Some(&rect));
+    framebuffer.invalidate(glium::InvalidateMask::color(), None);
+
+    let calls = backend.get_calls();
+    let sub = calls.iter().find(|c| c.name == "glInvalidateSubFramebuffer").unwrap();
+    assert_eq!(sub.args[1], Value::Int(2));     // no stencil attachment
+    assert_eq!(sub.args[5], Value::Int(32));
+    let whole = calls.iter().find(|c| c.name == "glInvalidateFramebuffer").unwrap();
+    assert_eq!(whole.args[1], Value::Int(1));
+}
+
+#[test]
+fn invalidate_not_supported() {
+    let version = glium::Version(glium::Api::Gl, 3, 3);
+    let (backend, context) = support::build_recording_context(version, &[]);
+
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    backend.clear_calls();
+    frame.invalidate(glium::InvalidateMask::all(), None);
+    frame.finish().unwrap();
+
+    assert!(!backend.get_calls().iter().any(|c| c.name.starts_with("glInvalidate")));
+}
+
+#[test]
+fn multioutput_draw_buffer_blending() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 0.0, 0.0, 1.0);
+                color2 = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(_)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+
+    let attachment1 = color1.to_color_attachment();
+    let attachment2 = color2.to_color_attachment();
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                                     &[("color1", &attachment1), ("color2", &attachment2)]).unwrap();
+    framebuffer.clear_color(0.0, 1.0, 0.0, 1.0);
+
+    let draw_buffers = [
+        glium::draw_parameters::DrawBufferParameters {
+            draw_buffer: glium::draw_parameters::DrawBuffer::Output("color1"),
+            blending_function: glium::BlendingFunction::Addition {
+                source: glium::LinearBlendingFactor::One,
+                destination: glium::LinearBlendingFactor::One,
+            },
+            alpha_blending_function: None,
+            color_mask: (true, true, true, true),
+        },
+    ];
+
+    let params = glium::DrawParameters {
+        draw_buffers: &draw_buffers,
+        .. Default::default()
+    };
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::DrawBufferParametersNotSupported) => {
+            assert!(!glium::draw_parameters::is_draw_buffer_parameters_supported(&display));
+            return;
+        },
+        r => r.unwrap()
+    };
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back[0][0], (255, 255, 0, 255));
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back[0][0], (255, 0, 0, 255));
+
     display.assert_no_error(None);
 }
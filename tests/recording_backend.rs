@@ -0,0 +1,128 @@
+#[macro_use]
+extern crate glium;
+
+mod support;
+
+use glium::{Api, Version, CapabilitiesSource, Surface};
+use glium::backend::recording::Value;
+
+#[test]
+fn context_creation() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+
+    assert_eq!(*context.get_version(), Version(Api::Gl, 3, 3));
+    assert!(backend.get_calls().iter().any(|c| c.name == "glGetString"));
+}
+
+#[test]
+fn advertised_extensions() {
+    let (_backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3),
+                                                               &["GL_ARB_debug_output",
+                                                                 "GL_KHR_debug"]);
+
+    assert!(context.get_extensions().gl_arb_debug_output);
+    assert!(context.get_extensions().gl_khr_debug);
+    assert!(!context.get_extensions().gl_arb_direct_state_access);
+}
+
+#[test]
+fn vertex_buffer_creation() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+    backend.clear_calls();
+
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    glium::VertexBuffer::new(&context, &[
+        Vertex { position: [-0.5, -0.5] },
+        Vertex { position: [ 0.0,  0.5] },
+        Vertex { position: [ 0.5, -0.5] },
+    ]).unwrap();
+
+    let calls = backend.get_calls();
+    let data = calls.iter().find(|c| c.name == "glBufferData").unwrap();
+    assert_eq!(data.args[1], Value::Int(3 * 2 * 4));
+}
+
+#[test]
+fn texture2d_creation() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+    backend.clear_calls();
+
+    glium::Texture2d::new(&context, vec![vec![(0u8, 0u8, 0u8, 0u8); 16]; 8]).unwrap();
+
+    let calls = backend.get_calls();
+    let image = calls.iter().find(|c| c.name == "glTexImage2D").unwrap();
+    assert_eq!(image.args[0], Value::UInt(0x0DE1));      // GL_TEXTURE_2D
+    assert_eq!(image.args[3], Value::Int(16));
+    assert_eq!(image.args[4], Value::Int(8));
+    assert!(!calls.iter().any(|c| c.name == "glTexStorage2D"));
+}
+
+#[test]
+fn texture2d_creation_with_storage() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 4, 2), &[]);
+    backend.clear_calls();
+
+    glium::Texture2d::new(&context, vec![vec![(0u8, 0u8, 0u8, 0u8); 16]; 8]).unwrap();
+
+    let calls = backend.get_calls();
+    let storage = calls.iter().position(|c| c.name == "glTexStorage2D").unwrap();
+    let upload = calls.iter().position(|c| c.name == "glTexSubImage2D").unwrap();
+    assert!(storage < upload);
+    assert_eq!(calls[storage].args[3], Value::Int(16));
+    assert_eq!(calls[storage].args[4], Value::Int(8));
+    assert!(!calls.iter().any(|c| c.name == "glTexImage2D"));
+}
+
+#[test]
+fn frame_draw() {
+    let (backend, context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+
+    let program = glium::Program::from_source(&context,
+        "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    // the backend doesn't know about any output of the program
+    assert_eq!(program.get_frag_data_location("color"), None);
+
+    backend.clear_calls();
+
+    let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+    let mut frame = glium::Frame::new(context.clone(), (800, 600));
+    frame.draw(glium::vertex::EmptyVertexAttributes { len: 3 }, &indices, &program,
+               &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+    frame.finish().unwrap();
+
+    let calls = backend.get_calls();
+    let draw = calls.iter().find(|c| c.name == "glDrawArrays").unwrap();
+    assert_eq!(draw.args[0], Value::UInt(0x0004));       // GL_TRIANGLES
+    assert_eq!(draw.args[1], Value::Int(0));
+    assert_eq!(draw.args[2], Value::Int(3));
+}
+
+#[test]
+fn clear_calls() {
+    let (backend, _context) = support::build_recording_context(Version(Api::Gl, 3, 3), &[]);
+
+    assert!(backend.get_calls().len() >= 1);
+    backend.clear_calls();
+    assert!(backend.get_calls().is_empty());
+}
@@ -0,0 +1,213 @@
+#[macro_use]
+extern crate glium;
+
+use glium::Surface;
+use glium::backend::Facade;
+
+mod support;
+
+fn build_green_program<F>(facade: &F) -> glium::Program where F: Facade {
+    program!(facade,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(0.0, 1.0, 0.0, 1.0);
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(0.0, 1.0, 0.0, 1.0);
+                }
+            ",
+        },
+    ).unwrap()
+}
+
+#[test]
+fn empty_queue() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    let queue = glium::RenderQueue::new();
+    assert!(queue.is_empty());
+
+    let stats = queue.submit(&mut texture.as_surface()).unwrap();
+    assert_eq!(stats.draws, 0);
+    assert_eq!(stats.state_changes, 0);
+    assert_eq!(stats.state_changes_saved, 0);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sorted_by_program() {
+    let display = support::build_display();
+    let (vb, ib, red) = support::build_fullscreen_red_pipeline(&display);
+    let green = build_green_program(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = Default::default();
+    let mut queue = glium::RenderQueue::new();
+    queue.draw(&vb, &ib, &green, glium::uniforms::EmptyUniforms, &params);
+    queue.draw(&vb, &ib, &red, glium::uniforms::EmptyUniforms, &params);
+    queue.draw(&vb, &ib, &green, glium::uniforms::EmptyUniforms, &params);
+    assert_eq!(queue.len(), 3);
+
+    let stats = queue.submit(&mut texture.as_surface()).unwrap();
+    assert_eq!(stats.draws, 3);
+    assert_eq!(stats.state_changes, 1);
+    assert_eq!(stats.state_changes_saved, 1);
+
+    // the draws with the green program are executed first
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sorted_by_draw_parameters() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+
+    let a = Default::default();
+    let b = glium::DrawParameters::new(&display).without_dithering();
+
+    let mut queue = glium::RenderQueue::new();
+    queue.draw(&vb, &ib, &program, glium::uniforms::EmptyUniforms, &a);
+    queue.draw(&vb, &ib, &program, glium::uniforms::EmptyUniforms, &b);
+    queue.draw(&vb, &ib, &program, glium::uniforms::EmptyUniforms, &a);
+    queue.draw(&vb, &ib, &program, glium::uniforms::EmptyUniforms, &b);
+
+    let stats = queue.submit(&mut texture.as_surface()).unwrap();
+    assert_eq!(stats.draws, 4);
+    assert_eq!(stats.state_changes, 1);
+    assert_eq!(stats.state_changes_saved, 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sorted_by_textures() {
+    let display = support::build_display();
+    let (vb, ib, _) = support::build_fullscreen_red_pipeline(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform sampler2D tex;
+
+                void main() {
+                    gl_FragColor = texture2D(tex, vec2(0.5, 0.5));
+                }
+            ",
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                uniform lowp sampler2D tex;
+
+                void main() {
+                    gl_FragColor = texture2D(tex, vec2(0.5, 0.5));
+                }
+            ",
+        },
+    ).unwrap();
+
+    let red = glium::Texture2d::new(&display, vec![vec![(255u8, 0u8, 0u8, 255u8)]]).unwrap();
+    let green = glium::Texture2d::new(&display, vec![vec![(0u8, 255u8, 0u8, 255u8)]]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = Default::default();
+    let mut queue = glium::RenderQueue::new();
+    queue.draw(&vb, &ib, &program, uniform! { tex: &green }, &params);
+    queue.draw(&vb, &ib, &program, uniform! { tex: &red }, &params);
+    queue.draw(&vb, &ib, &program, uniform! { tex: &green }, &params);
+
+    let stats = queue.submit(&mut texture.as_surface()).unwrap();
+    assert_eq!(stats.draws, 3);
+    assert_eq!(stats.state_changes, 1);
+    assert_eq!(stats.state_changes_saved, 1);
+
+    // the draws with the green texture are executed first
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn recorded_order_kept_if_already_sorted() {
+    let display = support::build_display();
+    let (vb, ib, red) = support::build_fullscreen_red_pipeline(&display);
+    let green = build_green_program(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = Default::default();
+    let mut queue = glium::RenderQueue::new();
+    queue.draw(&vb, &ib, &red, glium::uniforms::EmptyUniforms, &params);
+    queue.draw(&vb, &ib, &green, glium::uniforms::EmptyUniforms, &params);
+
+    let stats = queue.submit(&mut texture.as_surface()).unwrap();
+    assert_eq!(stats.draws, 2);
+    assert_eq!(stats.state_changes, 1);
+    assert_eq!(stats.state_changes_saved, 0);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+
+    display.assert_no_error(None);
+}
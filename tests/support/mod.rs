@@ -6,10 +6,12 @@ Test supports module.
 #![allow(dead_code)]
 
 use glium::{self, glutin, DisplayBuild};
-use glium::backend::Facade;
+use glium::backend::{Context, Facade};
+use glium::backend::recording::RecordingBackend;
 use glium::index::PrimitiveType;
 
 use std::env;
+use std::rc::Rc;
 
 /// Builds a headless display for tests.
 pub fn build_display() -> glium::Display {
@@ -44,6 +46,17 @@ pub fn rebuild_display(display: &glium::Display) {
     }
 }
 
+/// Builds a context that uses a `RecordingBackend` advertising the given version and extensions.
+///
+/// This doesn't need any OpenGL implementation.
+pub fn build_recording_context(version: glium::Version, extensions: &[&str])
+                               -> (Rc<RecordingBackend>, Rc<Context>)
+{
+    let backend = Rc::new(RecordingBackend::new(version, extensions));
+    let context = unsafe { Context::new::<_, ()>(backend.clone(), false) }.unwrap();
+    (backend, context)
+}
+
 fn parse_version() -> glutin::GlRequest {
     match env::var("GLIUM_GL_VERSION") {
         Ok(version) => {
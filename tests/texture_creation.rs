@@ -190,6 +190,10 @@ empty_texture_test!(empty_compressedtexture1darray, CompressedTexture1dArray, [6
 empty_texture_test!(empty_compressedtexture2d, CompressedTexture2d, [64, 32], 64, Some(32), None, None);
 empty_texture_test!(empty_compressedtexture2darray, CompressedTexture2dArray, [64, 32, 16], 64, Some(32), None, Some(16));
 empty_texture_test!(empty_compressedtexture3d, CompressedTexture3d, [64, 32, 16], 64, Some(32), Some(16), None);*/
+empty_texture_test!(empty_cubemap, Cubemap, [64], 64, Some(64), None, None);
+empty_texture_test!(empty_cubemaparray, CubemapArray, [64, 16], 64, Some(64), None, Some(16));
+empty_texture_test!(empty_depthcubemap, DepthCubemap, [64], 64, Some(64), None, None);
+empty_texture_test!(empty_depthcubemaparray, DepthCubemapArray, [64, 16], 64, Some(64), None, Some(16));
 empty_texture_test!(empty_depthstenciltexture1d, DepthStencilTexture1d, [64], 64, None, None, None);
 empty_texture_test!(empty_depthstenciltexture1darray, DepthStencilTexture1dArray, [64, 32], 64, None, None, Some(32));
 empty_texture_test!(empty_depthstenciltexture2d, DepthStencilTexture2d, [64, 32], 64, Some(32), None, None);